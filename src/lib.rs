@@ -7,15 +7,20 @@
 //! complexity of inserting, deleting or replacing a piece of text is always
 //! logarithmic in the size of the `Rope`.
 //!
-//! The crate has a relatively straightforward API. There are 3 structs to be
+//! The crate has a relatively straightforward API. There are 4 structs to be
 //! aware of:
 //!
 //! - [`Rope`]: the star of the crate;
 //! - [`RopeSlice`]: an immutable slice of a `Rope`;
-//! - [`RopeBuilder`]: an incremental `Rope` builder.
+//! - [`RopeBuilder`]: an incremental `Rope` builder;
+//! - [`Cursor`]: a seekable cursor over a `Rope` or a `RopeSlice`.
 //!
 //! plus the [`iter`] module which contains iterators over `Rope`s and
-//! `RopeSlice`s. That's it.
+//! `RopeSlice`s, the [`diff`] module which computes line diffs between them,
+//! the [`merge`] module which merges them, the [`history`] module which
+//! records undo/redo histories of `Rope`s, the [`changes`] module which
+//! represents edits as data and the [`anchors`] and [`decorations`] modules
+//! which track positions and ranges through them. That's it.
 //!
 //! # Example usage
 //!
@@ -134,10 +139,37 @@
 //!   on `Rope`s and `RopeSlice`s such as the
 //!   [`Graphemes`](crate::iter::Graphemes) iterator and others;
 //!
+//! - `char-metric` (disabled by default): makes the `Rope` and `RopeSlice`
+//!   track the number of [`char`]s they contain, allowing them to efficiently
+//!   convert char offsets to and from byte offsets in logarithmic time;
+//!
 //! - `utf16-metric` (disabled by default): makes the `Rope` and `RopeSlice`
 //!   track the UTF-16 code units they'd have if their content was stored as
 //!   UTF-16 instead of UTF-8, allowing them to efficiently convert UTF-16
-//!   code unit offsets to and from byte offsets in logarithmic time.
+//!   code unit offsets to and from byte offsets in logarithmic time;
+//!
+//! - `serde` (disabled by default): implements `Serialize` for `Rope`s and
+//!   `RopeSlice`s and `Deserialize` for `Rope`s. They're serialized as a
+//!   sequence of strings, one for every chunk;
+//!
+//! - `std` (disabled by default): adds the [`RopeReader`](crate::RopeReader)
+//!   type, which implements the `Read`, `BufRead` and `Seek` traits from
+//!   `std::io` over the contents of a `Rope` or `RopeSlice`, the
+//!   `Rope::from_reader()` and `Rope::from_reader_lossy()` constructors, and
+//!   `std::error::Error` impls for the crate's error types. Note that the
+//!   crate is not `no_std` when this feature is disabled, it only gates these
+//!   items;
+//!
+//! - `regex` (disabled by default): adds the [`regex`] module, which allows
+//!   to run regular expressions directly on the chunks of `Rope`s and
+//!   `RopeSlice`s without collecting them into a string first. Regexes with
+//!   Unicode word boundaries (`\b` and `\B`) are the exception, since they
+//!   copy non-ASCII texts into a string;
+//!
+//! - `tree-sitter` (disabled by default): adds the [`tree_sitter`] module,
+//!   which allows to parse `Rope`s and `RopeSlice`s with tree-sitter without
+//!   copying them into a string, and to describe their edits as
+//!   `InputEdit`s to reparse them incrementally.
 
 #![allow(clippy::explicit_auto_deref)]
 #![allow(clippy::module_inception)]
@@ -148,6 +180,39 @@
 
 extern crate alloc;
 
+pub mod anchors {
+    //! Positions in [`Rope`](crate::Rope)s which follow the edits made to
+    //! them.
+
+    pub use crate::rope::anchors::{Anchor, AnchorSet, AnchoredRope};
+}
+
+pub mod changes {
+    //! Edits to [`Rope`](crate::Rope)s expressed as data.
+
+    pub use crate::rope::changes::{xform, Bias, ChangeSet, Operation};
+}
+
+pub mod decorations {
+    //! Values attached to byte ranges of [`Rope`](crate::Rope)s which follow
+    //! the edits made to them.
+
+    pub use crate::rope::decorations::{Decorations, Overlapping};
+}
+
+pub mod diff {
+    //! Line diffs between [`Rope`](crate::Rope)s and
+    //! [`RopeSlice`](crate::RopeSlice)s.
+
+    pub use crate::rope::diff::{Hunk, LineDiff, UnifiedDiff};
+}
+
+pub mod history {
+    //! Undo/redo histories of [`Rope`](crate::Rope)s.
+
+    pub use crate::rope::history::{Edit, History, Transaction};
+}
+
 pub mod iter {
     //! Iterators over [`Rope`](crate::Rope)s and
     //! [`RopeSlice`](crate::RopeSlice)s.
@@ -155,11 +220,48 @@ pub mod iter {
     pub use crate::rope::iterators::*;
 }
 
+pub mod lsp {
+    //! Positions and document changes of the [Language Server
+    //! Protocol](https://microsoft.github.io/language-server-protocol/).
+
+    #[cfg(feature = "utf16-metric")]
+    pub use crate::rope::lsp::{ContentChange, ContentChangeError};
+    pub use crate::rope::lsp::Encoding;
+}
+
+pub mod merge {
+    //! Three-way merges of [`Rope`](crate::Rope)s and
+    //! [`RopeSlice`](crate::RopeSlice)s.
+
+    pub use crate::rope::merge::{merge3, Conflict, MergeResult};
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+#[cfg(feature = "regex")]
+pub mod regex {
+    //! Regex search over [`Rope`](crate::Rope)s and
+    //! [`RopeSlice`](crate::RopeSlice)s.
+
+    pub use regex_automata::meta::BuildError;
+
+    pub use crate::rope::regex::{Regex, RegexCaptures, RegexMatches};
+}
+
 mod rope;
 
 #[doc(hidden)]
 pub mod tree;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "tree-sitter")))]
+#[cfg(feature = "tree-sitter")]
+pub mod tree_sitter {
+    //! Incremental parsing of [`Rope`](crate::Rope)s and
+    //! [`RopeSlice`](crate::RopeSlice)s with
+    //! [tree-sitter](https://tree-sitter.github.io).
+
+    pub use crate::rope::tree_sitter::{chunk_callback, input_edit};
+}
+
 // These are not part of the public API, we only export them to be able to run
 // doctests.
 #[doc(hidden)]
@@ -168,7 +270,19 @@ pub use rope::{
     gap_slice::GapSlice,
     metrics::ChunkSummary,
 };
-pub use rope::{Rope, RopeBuilder, RopeSlice};
+pub use rope::{
+    Cursor,
+    DiffRange,
+    PatchError,
+    Pattern,
+    Rope,
+    RopeBuilder,
+    RopeSlice,
+    Utf8Error,
+};
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use rope::{FromReaderError, RopeReader};
 
 #[inline]
 pub(crate) fn range_bounds_to_start_end<T, B>(
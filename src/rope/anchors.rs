@@ -0,0 +1,644 @@
+use alloc::vec::Vec;
+use core::ops::RangeBounds;
+
+use super::changes::Bias;
+use super::Rope;
+use crate::range_bounds_to_start_end;
+
+/// A handle to a byte offset in a text which moves along with the edits made
+/// to it.
+///
+/// Anchors are created by [`AnchorSet::create()`] or
+/// [`AnchoredRope::anchor()`], and their current offset is returned by the
+/// `offset()` method of the same type.
+///
+/// An anchor is only meaningful to the set that created it (or to its
+/// clones). Passing it to a different set is a logic error whose result is
+/// unspecified: it won't panic, but it can return `None` or the offset of an
+/// unrelated anchor, and removing it can remove that anchor.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Anchor {
+    slot: usize,
+    generation: u32,
+}
+
+/// A set of [`Anchor`]s into a text which are shifted by the edits made to
+/// it.
+///
+/// The `AnchorSet` doesn't own the text, so every edit has to be reported to
+/// it via [`edit()`](Self::edit()). Use an [`AnchoredRope`] to have the edits
+/// made to a [`Rope`] reported automatically.
+///
+/// Anchors are mapped through edits like offsets are mapped through a
+/// [`ChangeSet`](crate::changes::ChangeSet): anchors within a deleted range
+/// end up at the position of the deletion, while their [`Bias`] decides
+/// whether anchors at the position of an insertion stay before or move after
+/// the inserted text.
+///
+/// The anchors are stored in a balanced binary tree where every anchor only
+/// records its distance from the previous one, so creating, removing and
+/// resolving an anchor takes logarithmic time, and an edit takes logarithmic
+/// time plus the number of anchors within its byte range.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::anchors::AnchorSet;
+/// # use crop::changes::Bias;
+/// #
+/// let mut anchors = AnchorSet::new(11);
+///
+/// let hello = anchors.create(0, Bias::Left);
+/// let world = anchors.create(6, Bias::Right);
+///
+/// // Insert 3 bytes at the start of the text.
+/// anchors.edit(0..0, 3);
+/// assert_eq!(anchors.offset(hello), Some(0));
+/// assert_eq!(anchors.offset(world), Some(9));
+///
+/// // Delete the 3 bytes before the second anchor.
+/// anchors.edit(6..9, 0);
+/// assert_eq!(anchors.offset(world), Some(6));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnchorSet {
+    nodes: Vec<Node>,
+
+    /// The slots of the removed anchors, which are reused by the next ones.
+    free: Vec<usize>,
+
+    root: Option<usize>,
+
+    /// The number of anchors in the set.
+    len: usize,
+
+    /// The length of the text the anchors point into.
+    byte_len: usize,
+
+    /// The state of the generator of the nodes' priorities.
+    seed: u64,
+}
+
+/// A node of the treap storing the anchors, ordered by their offsets.
+#[derive(Debug, Clone)]
+struct Node {
+    /// The distance in bytes from the previous anchor, or from the start of
+    /// the text for the first one.
+    gap: usize,
+
+    /// The sum of the gaps in the subtree rooted at this node, i.e. the
+    /// distance between its last anchor and the anchor right before the
+    /// subtree.
+    span: usize,
+
+    bias: Bias,
+
+    /// Parents always have higher priorities than their children.
+    priority: u64,
+
+    /// Incremented every time the anchor in this slot is removed, so that
+    /// handles to it can't resolve to the anchor that reuses the slot.
+    generation: u32,
+
+    parent: Option<usize>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl AnchorSet {
+    /// Returns the length of the text the anchors point into.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.byte_len
+    }
+
+    /// Creates a new anchor at the given byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn create(&mut self, byte_offset: usize, bias: Bias) -> Anchor {
+        assert!(
+            byte_offset <= self.byte_len,
+            "byte offset {} is out of bounds in an AnchorSet over {} bytes",
+            byte_offset,
+            self.byte_len
+        );
+
+        let priority = self.next_priority();
+
+        let node = Node {
+            gap: 0,
+            span: 0,
+            bias,
+            priority,
+            generation: 0,
+            parent: None,
+            left: None,
+            right: None,
+        };
+
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                let generation = self.nodes[slot].generation;
+                self.nodes[slot] = Node { generation, ..node };
+                slot
+            },
+
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            },
+        };
+
+        // Right-biased anchors go after the ones already at the same offset
+        // and left-biased ones before them, which is where they'd be after
+        // an insertion at that offset.
+        let root = self.root.take();
+        let (before, after) =
+            self.split(root, byte_offset, bias == Bias::Right);
+
+        let base = self.span(before);
+        self.nodes[slot].gap = byte_offset - base;
+        self.nodes[slot].span = byte_offset - base;
+        self.shift_first(after, base, byte_offset);
+
+        let root = self.merge(before, Some(slot));
+        let root = self.merge(root, after);
+        self.set_root(root);
+
+        self.len += 1;
+
+        Anchor { slot, generation: self.nodes[slot].generation }
+    }
+
+    /// Updates the anchors after the text in the specified byte range was
+    /// replaced by `text_len` bytes of new text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn edit<R>(&mut self, byte_range: R, text_len: usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len);
+
+        assert!(
+            start <= end,
+            "byte start {} is greater than byte end {}",
+            start,
+            end
+        );
+
+        assert!(
+            end <= self.byte_len,
+            "byte end {} is out of bounds in an AnchorSet over {} bytes",
+            end,
+            self.byte_len
+        );
+
+        let root = self.root.take();
+        let (before, rest) = self.split(root, start, false);
+        let base = self.span(before);
+        let (within, after) = self.split(rest, end - base, true);
+
+        let mut anchors = Vec::new();
+        self.collect(within, &mut anchors);
+
+        // Only left-biased anchors at the start of the range stay where they
+        // are, all the others end up after the inserted text.
+        let mut old_last = base;
+        let mut moved = Vec::new();
+        let mut within = None;
+        let mut new_last = base;
+
+        for slot in anchors {
+            old_last += self.nodes[slot].gap;
+
+            if old_last == start && self.nodes[slot].bias == Bias::Left {
+                self.reset(slot, start - new_last);
+                within = self.merge(within, Some(slot));
+                new_last = start;
+            } else {
+                moved.push(slot);
+            }
+        }
+
+        for slot in moved {
+            self.reset(slot, start + text_len - new_last);
+            within = self.merge(within, Some(slot));
+            new_last = start + text_len;
+        }
+
+        self.shift_first(after, old_last + text_len, new_last + end - start);
+
+        let root = self.merge(before, within);
+        let root = self.merge(root, after);
+        self.set_root(root);
+
+        self.byte_len = self.byte_len - (end - start) + text_len;
+    }
+
+    /// Returns `true` if the set doesn't contain any anchors.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of anchors in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Creates an empty `AnchorSet` over a text of `byte_len` bytes.
+    #[inline]
+    pub fn new(byte_len: usize) -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: None,
+            len: 0,
+            byte_len,
+            seed: 0,
+        }
+    }
+
+    /// Returns the current byte offset of the anchor, or `None` if it was
+    /// removed.
+    ///
+    /// The result is unspecified if the anchor was created by a different
+    /// set, see [`Anchor`].
+    #[inline]
+    pub fn offset(&self, anchor: Anchor) -> Option<usize> {
+        let node = self.nodes.get(anchor.slot)?;
+
+        if node.generation != anchor.generation {
+            return None;
+        }
+
+        let mut offset = self.span(node.left) + node.gap;
+        let mut child = anchor.slot;
+
+        while let Some(parent) = self.nodes[child].parent {
+            let parent_node = &self.nodes[parent];
+
+            if parent_node.right == Some(child) {
+                offset += self.span(parent_node.left) + parent_node.gap;
+            }
+
+            child = parent;
+        }
+
+        Some(offset)
+    }
+
+    /// Removes the anchor from the set, returning its last byte offset, or
+    /// `None` if it was already removed.
+    ///
+    /// The result is unspecified if the anchor was created by a different
+    /// set, see [`Anchor`].
+    #[inline]
+    pub fn remove(&mut self, anchor: Anchor) -> Option<usize> {
+        let offset = self.offset(anchor)?;
+
+        let slot = anchor.slot;
+        let Node { gap, left, right, parent, .. } = self.nodes[slot];
+
+        let replacement = if right.is_some() {
+            // The anchors after this one in its subtree now start from the
+            // previous one, so the span of the subtree doesn't change.
+            self.shift_first(right, gap, 0);
+            self.merge(left, right)
+        } else {
+            // This was the last anchor of its subtree, so its gap goes to the
+            // next anchor, which is one of its ancestors, and the subtrees
+            // ending with it get shorter.
+            let mut child = slot;
+
+            while let Some(ancestor) = self.nodes[child].parent {
+                if self.nodes[ancestor].left == Some(child) {
+                    self.nodes[ancestor].gap += gap;
+                    break;
+                }
+
+                self.nodes[ancestor].span -= gap;
+                child = ancestor;
+            }
+
+            left
+        };
+
+        match parent {
+            Some(parent) => {
+                if self.nodes[parent].left == Some(slot) {
+                    self.nodes[parent].left = replacement;
+                } else {
+                    self.nodes[parent].right = replacement;
+                }
+
+                if let Some(replacement) = replacement {
+                    self.nodes[replacement].parent = Some(parent);
+                }
+            },
+
+            None => self.set_root(replacement),
+        }
+
+        let node = &mut self.nodes[slot];
+        node.generation = node.generation.wrapping_add(1);
+        self.free.push(slot);
+        self.len -= 1;
+
+        Some(offset)
+    }
+
+    /// Pushes the anchors in the subtree to `anchors` in order.
+    #[inline]
+    fn collect(&self, subtree: Option<usize>, anchors: &mut Vec<usize>) {
+        if let Some(slot) = subtree {
+            self.collect(self.nodes[slot].left, anchors);
+            anchors.push(slot);
+            self.collect(self.nodes[slot].right, anchors);
+        }
+    }
+
+    /// Concatenates two subtrees, where the gaps in `right` start from the
+    /// last anchor in `left`.
+    #[inline]
+    fn merge(
+        &mut self,
+        left: Option<usize>,
+        right: Option<usize>,
+    ) -> Option<usize> {
+        let (Some(l), Some(r)) = (left, right) else {
+            return left.or(right);
+        };
+
+        if self.nodes[l].priority > self.nodes[r].priority {
+            let merged = self.merge(self.nodes[l].right, right);
+            self.nodes[l].right = merged;
+            self.update(l);
+            left
+        } else {
+            let merged = self.merge(left, self.nodes[r].left);
+            self.nodes[r].left = merged;
+            self.update(r);
+            right
+        }
+    }
+
+    /// Returns the priority of the next node using the SplitMix64
+    /// generator, which is good enough to keep the treap balanced.
+    #[inline]
+    fn next_priority(&mut self) -> u64 {
+        self.seed = self.seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Detaches the node from its children and sets its gap.
+    #[inline]
+    fn reset(&mut self, slot: usize, gap: usize) {
+        let node = &mut self.nodes[slot];
+        node.gap = gap;
+        node.span = gap;
+        node.left = None;
+        node.right = None;
+    }
+
+    #[inline]
+    fn set_root(&mut self, root: Option<usize>) {
+        if let Some(root) = root {
+            self.nodes[root].parent = None;
+        }
+        self.root = root;
+    }
+
+    /// Changes the gap of the first anchor in the subtree to `gap + add -
+    /// sub`, updating the spans along the way.
+    #[inline]
+    fn shift_first(
+        &mut self,
+        subtree: Option<usize>,
+        add: usize,
+        sub: usize,
+    ) {
+        let mut next = subtree;
+        let mut first = None;
+
+        while let Some(slot) = next {
+            self.nodes[slot].span = self.nodes[slot].span + add - sub;
+            first = Some(slot);
+            next = self.nodes[slot].left;
+        }
+
+        if let Some(first) = first {
+            self.nodes[first].gap = self.nodes[first].gap + add - sub;
+        }
+    }
+
+    #[inline]
+    fn span(&self, subtree: Option<usize>) -> usize {
+        subtree.map_or(0, |slot| self.nodes[slot].span)
+    }
+
+    /// Splits the subtree in the anchors before the given offset (or at it,
+    /// if `inclusive` is `true`) and the ones after it, where the gaps in the
+    /// second subtree start from the last anchor in the first one.
+    #[inline]
+    fn split(
+        &mut self,
+        subtree: Option<usize>,
+        byte_offset: usize,
+        inclusive: bool,
+    ) -> (Option<usize>, Option<usize>) {
+        let Some(slot) = subtree else {
+            return (None, None);
+        };
+
+        let offset = self.span(self.nodes[slot].left) + self.nodes[slot].gap;
+
+        if offset < byte_offset || (inclusive && offset == byte_offset) {
+            let (left, right) = self.split(
+                self.nodes[slot].right,
+                byte_offset - offset,
+                inclusive,
+            );
+            self.nodes[slot].right = left;
+            self.update(slot);
+            (subtree, right)
+        } else {
+            let (left, right) =
+                self.split(self.nodes[slot].left, byte_offset, inclusive);
+            self.nodes[slot].left = right;
+            self.update(slot);
+            (left, subtree)
+        }
+    }
+
+    /// Recomputes the span of the node and the parents of its children.
+    #[inline]
+    fn update(&mut self, slot: usize) {
+        let Node { gap, left, right, .. } = self.nodes[slot];
+
+        self.nodes[slot].span = self.span(left) + gap + self.span(right);
+
+        for child in [left, right].into_iter().flatten() {
+            self.nodes[child].parent = Some(slot);
+        }
+    }
+}
+
+/// A [`Rope`] together with an [`AnchorSet`] into it, which is updated by
+/// every edit made through the `AnchoredRope`.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::anchors::AnchoredRope;
+/// # use crop::changes::Bias;
+/// # use crop::Rope;
+/// #
+/// let mut rope = AnchoredRope::new(Rope::from("let foo = bar;"));
+///
+/// let breakpoint = rope.anchor(4, Bias::Left);
+///
+/// rope.insert(0, "// A comment.\n");
+/// assert_eq!(rope.offset(breakpoint), Some(18));
+///
+/// rope.replace(14..18, "const ");
+/// assert_eq!(rope.offset(breakpoint), Some(20));
+/// assert_eq!(rope.rope().byte_slice(20..23), "foo");
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnchoredRope {
+    rope: Rope,
+    anchors: AnchorSet,
+}
+
+impl AnchoredRope {
+    /// Creates a new anchor at the given byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset doesn't lie on a code point boundary or if
+    /// it's out of bounds (i.e. greater than the `Rope`'s
+    /// [`byte_len()`](Rope::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn anchor(&mut self, byte_offset: usize, bias: Bias) -> Anchor {
+        assert!(
+            byte_offset > self.rope.byte_len()
+                || self.rope.is_char_boundary(byte_offset),
+            "byte offset {} is not a char boundary",
+            byte_offset
+        );
+
+        self.anchors.create(byte_offset, bias)
+    }
+
+    /// Returns the anchors into the `Rope`.
+    #[inline]
+    pub fn anchors(&self) -> &AnchorSet {
+        &self.anchors
+    }
+
+    /// Deletes the text in the specified byte range, updating the anchors.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Rope::delete()`].
+    #[track_caller]
+    #[inline]
+    pub fn delete<R>(&mut self, byte_range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.replace(byte_range, "");
+    }
+
+    /// Inserts `text` at the given byte offset, updating the anchors.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Rope::insert()`].
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, text: T)
+    where
+        T: AsRef<str>,
+    {
+        self.replace(byte_offset..byte_offset, text);
+    }
+
+    /// Returns the `Rope`, consuming the `AnchoredRope`.
+    #[inline]
+    pub fn into_rope(self) -> Rope {
+        self.rope
+    }
+
+    /// Creates a new `AnchoredRope` with no anchors.
+    #[inline]
+    pub fn new(rope: Rope) -> Self {
+        let anchors = AnchorSet::new(rope.byte_len());
+        Self { rope, anchors }
+    }
+
+    /// Returns the current byte offset of the anchor, or `None` if it was
+    /// removed.
+    ///
+    /// The result is unspecified if the anchor was created by a different
+    /// `AnchoredRope`, see [`Anchor`].
+    #[inline]
+    pub fn offset(&self, anchor: Anchor) -> Option<usize> {
+        self.anchors.offset(anchor)
+    }
+
+    /// Removes the anchor, returning its last byte offset, or `None` if it
+    /// was already removed.
+    ///
+    /// The result is unspecified if the anchor was created by a different
+    /// `AnchoredRope`, see [`Anchor`].
+    #[inline]
+    pub fn remove_anchor(&mut self, anchor: Anchor) -> Option<usize> {
+        self.anchors.remove(anchor)
+    }
+
+    /// Replaces the text in the specified byte range with `text`, updating
+    /// the anchors.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Rope::replace()`].
+    #[track_caller]
+    #[inline]
+    pub fn replace<R, T>(&mut self, byte_range: R, text: T)
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<str>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.rope.byte_len());
+
+        let text = text.as_ref();
+        self.rope.replace(start..end, text);
+        self.anchors.edit(start..end, text.len());
+    }
+
+    /// Returns the `Rope`.
+    #[inline]
+    pub fn rope(&self) -> &Rope {
+        &self.rope
+    }
+}
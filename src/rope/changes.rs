@@ -0,0 +1,670 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::Rope;
+
+/// A single operation of a [`ChangeSet`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// Keeps the given number of bytes unchanged.
+    Retain(usize),
+
+    /// Deletes the given number of bytes.
+    Delete(usize),
+
+    /// Inserts the given text.
+    Insert(String),
+}
+
+/// Which side of an insertion an offset sticks to when it's mapped through a
+/// [`ChangeSet`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Bias {
+    /// The offset stays before text inserted at its position.
+    Left,
+
+    /// The offset moves after text inserted at its position.
+    Right,
+}
+
+/// A set of changes to the text of a [`Rope`], expressed as a sequence of
+/// [`Operation`]s spanning its whole byte range.
+///
+/// A `ChangeSet` is plain data: it can be built once and applied to any
+/// `Rope` of the right length, composed with the `ChangeSet` that follows it,
+/// inverted, and used to map byte offsets from the text before the changes to
+/// the text after them.
+///
+/// The operations are kept in a canonical form: consecutive operations of
+/// the same kind are merged, and an insertion at the same position as a
+/// deletion always comes first. This means that two `ChangeSet`s making the
+/// same changes are equal.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::changes::{Bias, ChangeSet};
+/// # use crop::Rope;
+/// #
+/// let mut r = Rope::from("Hello World");
+///
+/// let mut changes = ChangeSet::new();
+/// changes.retain(6).delete(5).insert("Earth").insert("!");
+///
+/// assert_eq!(changes.map_offset(11, Bias::Left), 12);
+///
+/// let inverse = changes.invert(&r);
+///
+/// changes.apply(&mut r);
+/// assert_eq!(r, "Hello Earth!");
+///
+/// inverse.apply(&mut r);
+/// assert_eq!(r, "Hello World");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ChangeSet {
+    ops: Vec<Operation>,
+
+    /// The byte length of the text before the changes.
+    len: usize,
+
+    /// The byte length of the text after the changes.
+    len_after: usize,
+}
+
+impl ChangeSet {
+    /// Applies the changes to the `Rope` via [`Rope::replace()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte length of the `Rope` isn't equal to
+    /// [`byte_len()`](Self::byte_len()), or if one of the changes doesn't
+    /// start or end on a code point boundary.
+    #[track_caller]
+    #[inline]
+    pub fn apply(&self, rope: &mut Rope) {
+        assert_eq!(
+            rope.byte_len(),
+            self.len,
+            "the ChangeSet expects a Rope of {} bytes, but this one has {}",
+            self.len,
+            rope.byte_len()
+        );
+
+        // The offset in the edited rope.
+        let mut offset = 0;
+
+        let mut ops = self.ops.iter().peekable();
+
+        while let Some(op) = ops.next() {
+            match op {
+                Operation::Retain(n) => offset += n,
+
+                Operation::Delete(n) => rope.delete(offset..offset + n),
+
+                Operation::Insert(text) => {
+                    // An insertion followed by a deletion is a replacement.
+                    let deleted = match ops.peek() {
+                        Some(Operation::Delete(n)) => {
+                            ops.next();
+                            *n
+                        },
+                        _ => 0,
+                    };
+
+                    rope.replace(offset..offset + deleted, text);
+                    offset += text.len();
+                },
+            }
+        }
+    }
+
+    /// Returns the byte length of the text the changes apply to.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the byte length of the text after the changes are applied.
+    #[inline]
+    pub fn byte_len_after(&self) -> usize {
+        self.len_after
+    }
+
+    /// Returns a `ChangeSet` with the same effect as applying `self` followed
+    /// by `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`byte_len()`](Self::byte_len()) of `other` isn't equal to
+    /// [`byte_len_after()`](Self::byte_len_after()) of `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::changes::ChangeSet;
+    /// # use crop::Rope;
+    /// #
+    /// let mut first = ChangeSet::new();
+    /// first.retain(5).insert(" World");
+    ///
+    /// let mut second = ChangeSet::new();
+    /// second.delete(1).insert("J").retain(10);
+    ///
+    /// let mut r = Rope::from("Hello");
+    /// first.compose(second).apply(&mut r);
+    /// assert_eq!(r, "Jello World");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn compose(self, other: Self) -> Self {
+        assert_eq!(
+            self.len_after, other.len,
+            "can't compose a ChangeSet resulting in {} bytes with one \
+             expecting {} bytes",
+            self.len_after, other.len
+        );
+
+        let mut composed = Self::new();
+
+        let mut lhs = self.ops.into_iter();
+        let mut rhs = other.ops.into_iter();
+
+        let mut next_lhs = lhs.next();
+        let mut next_rhs = rhs.next();
+
+        loop {
+            use core::cmp::Ordering::*;
+            use Operation::*;
+
+            match (next_lhs, next_rhs) {
+                (None, None) => break,
+
+                // Deletions in `self` are kept as they are.
+                (Some(Delete(n)), op) => {
+                    composed.delete(n);
+                    next_lhs = lhs.next();
+                    next_rhs = op;
+                },
+
+                // So are insertions in `other`.
+                (op, Some(Insert(text))) => {
+                    composed.insert(text);
+                    next_lhs = op;
+                    next_rhs = rhs.next();
+                },
+
+                (None, _) | (_, None) => {
+                    unreachable!("the lengths have already been checked")
+                },
+
+                (Some(Retain(l)), Some(Retain(r))) => {
+                    composed.retain(l.min(r));
+                    (next_lhs, next_rhs) = match l.cmp(&r) {
+                        Less => (lhs.next(), Some(Retain(r - l))),
+                        Equal => (lhs.next(), rhs.next()),
+                        Greater => (Some(Retain(l - r)), rhs.next()),
+                    };
+                },
+
+                // Text inserted by `self` and deleted by `other` is skipped.
+                (Some(Insert(text)), Some(Delete(r))) => {
+                    (next_lhs, next_rhs) = match text.len().cmp(&r) {
+                        Less => (lhs.next(), Some(Delete(r - text.len()))),
+                        Equal => (lhs.next(), rhs.next()),
+                        Greater => {
+                            (Some(Insert(text[r..].to_string())), rhs.next())
+                        },
+                    };
+                },
+
+                (Some(Insert(mut text)), Some(Retain(r))) => {
+                    (next_lhs, next_rhs) = match text.len().cmp(&r) {
+                        Less => {
+                            let len = text.len();
+                            composed.insert(text);
+                            (lhs.next(), Some(Retain(r - len)))
+                        },
+                        Equal => {
+                            composed.insert(text);
+                            (lhs.next(), rhs.next())
+                        },
+                        Greater => {
+                            let rest = text.split_off(r);
+                            composed.insert(text);
+                            (Some(Insert(rest)), rhs.next())
+                        },
+                    };
+                },
+
+                (Some(Retain(l)), Some(Delete(r))) => {
+                    composed.delete(l.min(r));
+                    (next_lhs, next_rhs) = match l.cmp(&r) {
+                        Less => (lhs.next(), Some(Delete(r - l))),
+                        Equal => (lhs.next(), rhs.next()),
+                        Greater => (Some(Retain(l - r)), rhs.next()),
+                    };
+                },
+            }
+        }
+
+        composed
+    }
+
+    /// Appends an operation deleting `byte_len` bytes.
+    #[inline]
+    pub fn delete(&mut self, byte_len: usize) -> &mut Self {
+        if byte_len == 0 {
+            return self;
+        }
+
+        self.len += byte_len;
+
+        if let Some(Operation::Delete(n)) = self.ops.last_mut() {
+            *n += byte_len;
+        } else {
+            self.ops.push(Operation::Delete(byte_len));
+        }
+
+        self
+    }
+
+    /// Creates a `ChangeSet` for a text of `byte_len` bytes which replaces
+    /// the given byte ranges with the given texts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ranges aren't sorted, if they overlap or if one of them
+    /// ends after `byte_len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::changes::ChangeSet;
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("foo bar baz");
+    ///
+    /// let changes =
+    ///     ChangeSet::from_replacements(11, [(0..3, "FOO"), (8..11, "")]);
+    ///
+    /// changes.apply(&mut r);
+    /// assert_eq!(r, "FOO bar ");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn from_replacements<I, T>(byte_len: usize, replacements: I) -> Self
+    where
+        I: IntoIterator<Item = (Range<usize>, T)>,
+        T: AsRef<str>,
+    {
+        let mut changes = Self::new();
+
+        let mut offset = 0;
+
+        for (range, text) in replacements {
+            assert!(
+                offset <= range.start && range.start <= range.end,
+                "the replaced ranges must be sorted and non-overlapping, but \
+                 {:?} starts before {}",
+                range,
+                offset
+            );
+
+            changes
+                .retain(range.start - offset)
+                .delete(range.len())
+                .insert(text.as_ref());
+
+            offset = range.end;
+        }
+
+        assert!(
+            offset <= byte_len,
+            "the replaced ranges end at {offset}, after the end of the text \
+             ({byte_len})"
+        );
+
+        changes.retain(byte_len - offset);
+
+        changes
+    }
+
+    /// Appends an operation inserting `text`.
+    #[inline]
+    pub fn insert<T>(&mut self, text: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        let text = text.as_ref();
+
+        if text.is_empty() {
+            return self;
+        }
+
+        self.len_after += text.len();
+
+        // Keep insertions before deletions at the same position.
+        let idx = match self.ops.last() {
+            Some(Operation::Delete(_)) => self.ops.len() - 1,
+            _ => self.ops.len(),
+        };
+
+        match idx.checked_sub(1).map(|prev| &mut self.ops[prev]) {
+            Some(Operation::Insert(prev)) => prev.push_str(text),
+            _ => self.ops.insert(idx, Operation::Insert(text.into())),
+        }
+
+        self
+    }
+
+    /// Returns a `ChangeSet` which undoes these changes, given the text they
+    /// apply to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte length of the `Rope` isn't equal to
+    /// [`byte_len()`](Self::byte_len()).
+    #[track_caller]
+    #[inline]
+    pub fn invert(&self, original: &Rope) -> Self {
+        assert_eq!(
+            original.byte_len(),
+            self.len,
+            "the ChangeSet expects a Rope of {} bytes, but this one has {}",
+            self.len,
+            original.byte_len()
+        );
+
+        let mut inverse = Self::new();
+
+        let mut offset = 0;
+
+        for op in &self.ops {
+            match op {
+                Operation::Retain(n) => {
+                    inverse.retain(*n);
+                    offset += n;
+                },
+
+                Operation::Delete(n) => {
+                    let deleted = original.byte_slice(offset..offset + n);
+                    for chunk in deleted.chunks() {
+                        inverse.insert(chunk);
+                    }
+                    offset += n;
+                },
+
+                Operation::Insert(text) => {
+                    inverse.delete(text.len());
+                },
+            }
+        }
+
+        inverse
+    }
+
+    /// Returns `true` if applying the changes doesn't modify the text.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ops.iter().all(|op| matches!(op, Operation::Retain(_)))
+    }
+
+    /// Maps a byte offset in the text before the changes to the
+    /// corresponding offset in the text after them.
+    ///
+    /// Offsets within deleted text are mapped to the position where the
+    /// deletion happened, while the `bias` decides whether an offset where
+    /// some text was inserted ends up before or after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::changes::{Bias, ChangeSet};
+    /// #
+    /// let mut changes = ChangeSet::new();
+    /// changes.retain(2).insert("foo").delete(2).retain(2);
+    ///
+    /// assert_eq!(changes.map_offset(1, Bias::Left), 1);
+    /// assert_eq!(changes.map_offset(2, Bias::Left), 2);
+    /// assert_eq!(changes.map_offset(2, Bias::Right), 5);
+    /// assert_eq!(changes.map_offset(3, Bias::Left), 5);
+    /// assert_eq!(changes.map_offset(4, Bias::Left), 5);
+    /// assert_eq!(changes.map_offset(6, Bias::Left), 7);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn map_offset(&self, byte_offset: usize, bias: Bias) -> usize {
+        assert!(
+            byte_offset <= self.len,
+            "byte offset {} is out of bounds in a ChangeSet over {} bytes",
+            byte_offset,
+            self.len
+        );
+
+        let mut old_offset = 0;
+        let mut new_offset = 0;
+
+        for op in &self.ops {
+            match op {
+                Operation::Retain(n) => {
+                    let end = old_offset + n;
+
+                    if byte_offset < end
+                        || (byte_offset == end && bias == Bias::Left)
+                    {
+                        return new_offset + (byte_offset - old_offset);
+                    }
+
+                    old_offset = end;
+                    new_offset += n;
+                },
+
+                Operation::Delete(n) => {
+                    if byte_offset < old_offset + n {
+                        return new_offset;
+                    }
+
+                    old_offset += n;
+                },
+
+                Operation::Insert(text) => {
+                    if byte_offset == old_offset && bias == Bias::Left {
+                        return new_offset;
+                    }
+
+                    new_offset += text.len();
+                },
+            }
+        }
+
+        new_offset
+    }
+
+    /// Creates an empty `ChangeSet`, which applies to an empty text.
+    ///
+    /// Use [`retain()`](Self::retain()), [`delete()`](Self::delete()) and
+    /// [`insert()`](Self::insert()) to add operations to it.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the operations making up the `ChangeSet`.
+    #[inline]
+    pub fn operations(&self) -> &[Operation] {
+        &self.ops
+    }
+
+    /// Appends an operation keeping `byte_len` bytes unchanged.
+    #[inline]
+    pub fn retain(&mut self, byte_len: usize) -> &mut Self {
+        if byte_len == 0 {
+            return self;
+        }
+
+        self.len += byte_len;
+        self.len_after += byte_len;
+
+        if let Some(Operation::Retain(n)) = self.ops.last_mut() {
+            *n += byte_len;
+        } else {
+            self.ops.push(Operation::Retain(byte_len));
+        }
+
+        self
+    }
+}
+
+impl Extend<Operation> for ChangeSet {
+    #[inline]
+    fn extend<I: IntoIterator<Item = Operation>>(&mut self, ops: I) {
+        for op in ops {
+            match op {
+                Operation::Retain(n) => self.retain(n),
+                Operation::Delete(n) => self.delete(n),
+                Operation::Insert(text) => self.insert(text),
+            };
+        }
+    }
+}
+
+impl FromIterator<Operation> for ChangeSet {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Operation>>(ops: I) -> Self {
+        let mut changes = Self::new();
+        changes.extend(ops);
+        changes
+    }
+}
+
+/// Transforms two `ChangeSet`s made concurrently on the same text, returning
+/// `(a', b')` such that applying `a` followed by `b'` gives the same text as
+/// applying `b` followed by `a'`.
+///
+/// When both `ChangeSet`s insert some text at the same position, the text
+/// inserted by `a` comes first.
+///
+/// # Panics
+///
+/// Panics if the two `ChangeSet`s don't apply to texts of the same length.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::changes::{xform, ChangeSet};
+/// # use crop::Rope;
+/// #
+/// let r = Rope::from("Hello");
+///
+/// let a = ChangeSet::from_replacements(5, [(5..5, " World")]);
+/// let b = ChangeSet::from_replacements(5, [(0..1, "J"), (5..5, "!")]);
+///
+/// let (a_prime, b_prime) = xform(&a, &b);
+///
+/// let mut r1 = r.clone();
+/// a.apply(&mut r1);
+/// b_prime.apply(&mut r1);
+///
+/// let mut r2 = r.clone();
+/// b.apply(&mut r2);
+/// a_prime.apply(&mut r2);
+///
+/// assert_eq!(r1, "Jello World!");
+/// assert_eq!(r1, r2);
+/// ```
+#[track_caller]
+#[inline]
+pub fn xform(a: &ChangeSet, b: &ChangeSet) -> (ChangeSet, ChangeSet) {
+    use Operation::*;
+
+    assert_eq!(
+        a.len, b.len,
+        "can't transform a ChangeSet over {} bytes against one over {} bytes",
+        a.len, b.len
+    );
+
+    let mut a_prime = ChangeSet::new();
+    let mut b_prime = ChangeSet::new();
+
+    let mut a_ops = a.ops.iter().cloned();
+    let mut b_ops = b.ops.iter().cloned();
+
+    let mut next_a = a_ops.next();
+    let mut next_b = b_ops.next();
+
+    loop {
+        match (next_a, next_b) {
+            (None, None) => break,
+
+            // Insertions from `a` go first, and `b'` has to skip over them.
+            (Some(Insert(text)), op) => {
+                b_prime.retain(text.len());
+                a_prime.insert(text);
+                next_a = a_ops.next();
+                next_b = op;
+            },
+
+            (op, Some(Insert(text))) => {
+                a_prime.retain(text.len());
+                b_prime.insert(text);
+                next_a = op;
+                next_b = b_ops.next();
+            },
+
+            (None, _) | (_, None) => {
+                unreachable!("the lengths have already been checked")
+            },
+
+            (Some(a_op), Some(b_op)) => {
+                let (a_len, b_len) = match (&a_op, &b_op) {
+                    (Retain(l) | Delete(l), Retain(r) | Delete(r)) => (*l, *r),
+                    _ => unreachable!("insertions were handled above"),
+                };
+
+                let len = a_len.min(b_len);
+
+                match (&a_op, &b_op) {
+                    (Retain(_), Retain(_)) => {
+                        a_prime.retain(len);
+                        b_prime.retain(len);
+                    },
+
+                    // Text deleted by `b` doesn't exist anymore for `a'`, and
+                    // vice versa.
+                    (Delete(_), Retain(_)) => {
+                        a_prime.delete(len);
+                    },
+                    (Retain(_), Delete(_)) => {
+                        b_prime.delete(len);
+                    },
+
+                    // Text deleted by both is already gone for both.
+                    _ => {},
+                }
+
+                next_a = shorten(a_op, len).or_else(|| a_ops.next());
+                next_b = shorten(b_op, len).or_else(|| b_ops.next());
+            },
+        }
+    }
+
+    (a_prime, b_prime)
+}
+
+/// Removes `len` bytes from a retain or a deletion, returning `None` if
+/// there's nothing left of it.
+#[inline]
+fn shorten(op: Operation, len: usize) -> Option<Operation> {
+    match op {
+        Operation::Retain(n) if n > len => Some(Operation::Retain(n - len)),
+        Operation::Delete(n) if n > len => Some(Operation::Delete(n - len)),
+        _ => None,
+    }
+}
@@ -0,0 +1,552 @@
+use super::gap_slice::GapSlice;
+use super::metrics::{ByteMetric, ChunkSummary, RawLineMetric};
+use super::rope::RopeChunk;
+use super::utils::panic_messages as panic;
+use super::{Rope, RopeSlice};
+use crate::tree::{LeafCursor, SlicingMetric};
+
+/// A seekable cursor over the contents of `Rope`s and `RopeSlice`s.
+///
+/// A `Cursor` points to a byte offset in the text and can be repositioned at
+/// a byte, line or UTF-16 offset, or moved forward and backward by chars,
+/// lines and chunks. It caches the path from the root of the tree down to the
+/// chunk it's currently in, so moves to nearby positions don't have to start
+/// over from the root like e.g. [`Rope::byte_of_line()`] does.
+///
+/// This struct is created by the `cursor` method on
+/// [`Rope`](Rope::cursor()) and [`RopeSlice`](RopeSlice::cursor()). See
+/// their documentation for more.
+#[derive(Clone)]
+pub struct Cursor<'a> {
+    /// The slice we're moving over.
+    slice: RopeSlice<'a>,
+
+    /// The cursor over the leaves of the slice.
+    leaves: LeafCursor<'a, { Rope::arity() }, RopeChunk>,
+
+    /// The current leaf, clipped to the range of the slice.
+    leaf: GapSlice<'a>,
+
+    /// The summary of [`leaf`](Self::leaf).
+    leaf_summary: ChunkSummary,
+
+    /// The summary of the slice up to the start of [`leaf`](Self::leaf).
+    leaf_offset: ChunkSummary,
+
+    /// The byte offset of the cursor within [`leaf`](Self::leaf). It's only
+    /// equal to the length of the leaf if the cursor is at the end of the
+    /// slice.
+    byte_offset: usize,
+}
+
+impl<'a> From<&'a Rope> for Cursor<'a> {
+    #[inline]
+    fn from(rope: &'a Rope) -> Self {
+        Self::from(&rope.byte_slice(..))
+    }
+}
+
+impl<'a> From<&RopeSlice<'a>> for Cursor<'a> {
+    #[inline]
+    fn from(slice: &RopeSlice<'a>) -> Self {
+        let leaves = LeafCursor::from(&slice.tree_slice);
+        let (leaf, leaf_summary) = leaves.leaf();
+        let mut cursor = Self {
+            slice: *slice,
+            leaves,
+            leaf,
+            leaf_summary,
+            leaf_offset: ChunkSummary::default(),
+            byte_offset: 0,
+        };
+        cursor.skip_to_next_leaf_if_at_end();
+        cursor
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Returns the byte offset of the cursor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// let mut cursor = r.cursor();
+    /// assert_eq!(cursor.byte_offset(), 0);
+    ///
+    /// cursor.seek_to_line(1);
+    /// assert_eq!(cursor.byte_offset(), 4);
+    /// ```
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.leaf_offset.bytes() + self.byte_offset
+    }
+
+    /// Returns the chunk the cursor is currently in, i.e. the chunk
+    /// containing the byte at [`byte_offset()`](Self::byte_offset()), or the
+    /// last chunk if the cursor is at the end of the text.
+    ///
+    /// The returned chunk is only empty if the text is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_byte(5);
+    ///
+    /// let chunk = cursor.chunk();
+    /// let offset = cursor.byte_offset() - cursor.chunk_byte_offset();
+    /// assert_eq!(chunk.as_bytes()[offset], b'a');
+    /// ```
+    #[inline]
+    pub fn chunk(&self) -> &'a str {
+        self.chunk_and_start().0
+    }
+
+    /// Returns the byte offset of the start of the [current
+    /// chunk](Self::chunk()).
+    #[inline]
+    pub fn chunk_byte_offset(&self) -> usize {
+        self.leaf_offset.bytes() + self.chunk_and_start().1
+    }
+
+    /// Returns the current chunk together with the byte offset of its start
+    /// within the current leaf.
+    #[inline]
+    fn chunk_and_start(&self) -> (&'a str, usize) {
+        let left = self.leaf.left_chunk();
+
+        if self.byte_offset < left.len() || self.leaf.len_right() == 0 {
+            (left, 0)
+        } else {
+            (self.leaf.right_chunk(), left.len())
+        }
+    }
+
+    /// Returns the line offset of the cursor, i.e. the number of line breaks
+    /// preceding it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\nbaz");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_byte(9);
+    /// assert_eq!(cursor.line_offset(), 2);
+    /// ```
+    #[inline]
+    pub fn line_offset(&self) -> usize {
+        let ((_, up_to_cursor), _) = self
+            .leaf
+            .split_at_offset(ByteMetric(self.byte_offset), self.leaf_summary);
+
+        self.leaf_offset.line_breaks() + up_to_cursor.line_breaks()
+    }
+
+    /// Updates the current leaf after the leaf cursor has moved.
+    #[inline]
+    fn load_leaf(&mut self) {
+        (self.leaf, self.leaf_summary) = self.leaves.leaf();
+        self.leaf_offset = self.leaves.offset();
+    }
+
+    /// Moves the cursor forward by one [`char`], returning the char it moved
+    /// over, or `None` if the cursor was already at the end of the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("ab");
+    ///
+    /// let mut cursor = r.cursor();
+    /// assert_eq!(cursor.next_char(), Some('a'));
+    /// assert_eq!(cursor.next_char(), Some('b'));
+    /// assert_eq!(cursor.next_char(), None);
+    /// assert_eq!(cursor.byte_offset(), 2);
+    /// ```
+    #[inline]
+    pub fn next_char(&mut self) -> Option<char> {
+        let (chunk, start) = self.chunk_and_start();
+
+        let ch = chunk[self.byte_offset - start..].chars().next()?;
+
+        self.byte_offset += ch.len_utf8();
+        self.skip_to_next_leaf_if_at_end();
+        Some(ch)
+    }
+
+    /// Moves the cursor to the start of the next chunk, returning it, or
+    /// `None` if the cursor is already in the last chunk (in which case the
+    /// cursor is not moved).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// let mut cursor = r.cursor();
+    /// let mut chunks = vec![cursor.chunk()];
+    ///
+    /// while let Some(chunk) = cursor.next_chunk() {
+    ///     assert_eq!(cursor.byte_offset(), cursor.chunk_byte_offset());
+    ///     chunks.push(chunk);
+    /// }
+    ///
+    /// assert_eq!(chunks.concat(), "foo\nbar");
+    /// ```
+    #[inline]
+    pub fn next_chunk(&mut self) -> Option<&'a str> {
+        let (chunk, start) = self.chunk_and_start();
+
+        let end = start + chunk.len();
+
+        if end < self.leaf.len() {
+            self.byte_offset = end;
+        } else if self.leaves.next_leaf() {
+            self.load_leaf();
+            self.byte_offset = 0;
+        } else {
+            return None;
+        }
+
+        Some(self.chunk())
+    }
+
+    /// Moves the cursor to the start of the next line, returning `false`
+    /// without moving it if there are no line breaks after the cursor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\n");
+    ///
+    /// let mut cursor = r.cursor();
+    /// assert!(cursor.next_line());
+    /// assert_eq!(cursor.byte_offset(), 4);
+    /// assert!(cursor.next_line());
+    /// assert_eq!(cursor.byte_offset(), 8);
+    /// assert!(!cursor.next_line());
+    /// ```
+    #[inline]
+    pub fn next_line(&mut self) -> bool {
+        let (chunk, start) = self.chunk_and_start();
+
+        if let Some(idx) = chunk[self.byte_offset - start..].find('\n') {
+            self.byte_offset += idx + 1;
+            self.skip_to_next_leaf_if_at_end();
+            return true;
+        }
+
+        let line_offset = self.line_offset();
+
+        if line_offset == self.slice.tree_slice.summary().line_breaks() {
+            false
+        } else {
+            self.seek(RawLineMetric(line_offset + 1));
+            true
+        }
+    }
+
+    /// Moves the cursor back by one [`char`], returning the char it moved
+    /// over, or `None` if the cursor was already at the start of the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("ab");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_byte(2);
+    /// assert_eq!(cursor.previous_char(), Some('b'));
+    /// assert_eq!(cursor.previous_char(), Some('a'));
+    /// assert_eq!(cursor.previous_char(), None);
+    /// ```
+    #[inline]
+    pub fn previous_char(&mut self) -> Option<char> {
+        if self.byte_offset == 0 {
+            if !self.leaves.previous_leaf() {
+                return None;
+            }
+            self.load_leaf();
+            self.byte_offset = self.leaf.len();
+        }
+
+        let left = self.leaf.left_chunk();
+
+        let ch = if self.byte_offset <= left.len() {
+            left[..self.byte_offset].chars().next_back()
+        } else {
+            self.leaf.right_chunk()[..self.byte_offset - left.len()]
+                .chars()
+                .next_back()
+        }?;
+
+        self.byte_offset -= ch.len_utf8();
+        Some(ch)
+    }
+
+    /// Moves the cursor to the start of the previous chunk, returning it, or
+    /// `None` if the cursor is already in the first chunk (in which case the
+    /// cursor is not moved).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_byte(r.byte_len());
+    /// let mut chunks = vec![cursor.chunk()];
+    ///
+    /// while let Some(chunk) = cursor.previous_chunk() {
+    ///     chunks.insert(0, chunk);
+    /// }
+    ///
+    /// assert_eq!(cursor.byte_offset(), 0);
+    /// assert_eq!(chunks.concat(), "foo\nbar");
+    /// ```
+    #[inline]
+    pub fn previous_chunk(&mut self) -> Option<&'a str> {
+        let (_, start) = self.chunk_and_start();
+
+        if start > 0 {
+            self.byte_offset = 0;
+        } else if self.leaves.previous_leaf() {
+            self.load_leaf();
+            self.byte_offset = if self.leaf.len_right() > 0 {
+                self.leaf.len_left()
+            } else {
+                0
+            };
+        } else {
+            return None;
+        }
+
+        Some(self.chunk())
+    }
+
+    /// Moves the cursor to the start of the previous line, returning `false`
+    /// without moving it if the cursor is in the first line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\nbaz");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_byte(9);
+    /// assert!(cursor.previous_line());
+    /// assert_eq!(cursor.byte_offset(), 4);
+    /// assert!(cursor.previous_line());
+    /// assert_eq!(cursor.byte_offset(), 0);
+    /// assert!(!cursor.previous_line());
+    /// ```
+    #[inline]
+    pub fn previous_line(&mut self) -> bool {
+        let line_offset = self.line_offset();
+
+        if line_offset == 0 {
+            false
+        } else {
+            self.seek(RawLineMetric(line_offset - 1));
+            true
+        }
+    }
+
+    /// Moves the cursor to the position at the given `M`-offset.
+    #[track_caller]
+    #[inline]
+    fn seek<M>(&mut self, offset: M)
+    where
+        M: SlicingMetric<RopeChunk>,
+    {
+        self.leaves.seek(offset);
+        self.load_leaf();
+
+        let (_, up_to_offset) = M::slice_up_to(
+            self.leaf,
+            offset - M::measure(&self.leaf_offset),
+            &self.leaf_summary,
+        );
+
+        self.byte_offset = up_to_offset.bytes();
+        self.skip_to_next_leaf_if_at_end();
+    }
+
+    /// Moves the cursor to the given byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than the
+    /// byte length of the text) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_byte(5);
+    /// assert_eq!(cursor.next_char(), Some('a'));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn seek_to_byte(&mut self, byte_offset: usize) {
+        if byte_offset > self.slice.byte_len() {
+            panic::byte_offset_out_of_bounds(
+                byte_offset,
+                self.slice.byte_len(),
+            );
+        }
+
+        self.leaves.seek(ByteMetric(byte_offset));
+        self.load_leaf();
+
+        let byte_offset = byte_offset - self.leaf_offset.bytes();
+
+        self.leaf.assert_char_boundary(byte_offset);
+
+        self.byte_offset = byte_offset;
+        self.skip_to_next_leaf_if_at_end();
+    }
+
+    /// Moves the cursor to the given char offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the char offset is out of bounds (i.e. greater than the
+    /// number of chars in the text).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🐸a");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_char(1);
+    /// assert_eq!(cursor.byte_offset(), 4);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn seek_to_char(&mut self, char_offset: usize) {
+        if char_offset > self.slice.char_len() {
+            panic::char_offset_out_of_bounds(
+                char_offset,
+                self.slice.char_len(),
+            );
+        }
+
+        self.seek(super::metrics::CharMetric(char_offset));
+    }
+
+    /// Moves the cursor to the start of the given line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than the
+    /// number of lines in the text).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_line(1);
+    /// assert_eq!(cursor.byte_offset(), 4);
+    /// cursor.seek_to_line(2);
+    /// assert_eq!(cursor.byte_offset(), 7);
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn seek_to_line(&mut self, line_offset: usize) {
+        if line_offset > self.slice.line_len() {
+            panic::line_offset_out_of_bounds(
+                line_offset,
+                self.slice.line_len(),
+            );
+        }
+
+        if line_offset > self.slice.tree_slice.summary().line_breaks() {
+            self.seek(ByteMetric(self.slice.byte_len()));
+        } else {
+            self.seek(RawLineMetric(line_offset));
+        }
+    }
+
+    /// Moves the cursor to the given UTF-16 code unit offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the UTF-16 code unit offset is out of bounds (i.e. greater
+    /// than the UTF-16 length of the text).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // The "𐐀" character is encoded using two code units in UTF-16 and
+    /// // four bytes in UTF-8.
+    /// let r = Rope::from("a𐐀b");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_utf16_code_unit(3);
+    /// assert_eq!(cursor.byte_offset(), 5);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn seek_to_utf16_code_unit(&mut self, utf16_offset: usize) {
+        if utf16_offset > self.slice.utf16_len() {
+            panic::utf16_offset_out_of_bounds(
+                utf16_offset,
+                self.slice.utf16_len(),
+            );
+        }
+
+        self.seek(super::metrics::Utf16Metric(utf16_offset));
+    }
+
+    /// Moves to the start of the next leaf if the cursor is at the end of the
+    /// current one and that isn't the end of the slice.
+    #[inline]
+    fn skip_to_next_leaf_if_at_end(&mut self) {
+        while self.byte_offset == self.leaf.len() && self.leaves.next_leaf() {
+            self.load_leaf();
+            self.byte_offset = 0;
+        }
+    }
+}
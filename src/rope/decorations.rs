@@ -0,0 +1,865 @@
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::ops::{Add, AddAssign, Range, RangeBounds, Sub, SubAssign};
+
+use crate::range_bounds_to_start_end;
+use crate::tree::{
+    AsSlice,
+    BalancedLeaf,
+    BaseMeasured,
+    Metric,
+    ReplaceableLeaf,
+    Subtrees,
+    Summarize,
+    Tree,
+};
+
+#[cfg(any(test, fuzzing, feature = "arity_4"))]
+const ARITY: usize = 4;
+
+#[cfg(not(any(test, fuzzing, feature = "arity_4")))]
+const ARITY: usize = 16;
+
+#[cfg(any(test, feature = "small_chunks"))]
+const CHUNK_MAX_ITEMS: usize = 4;
+
+#[cfg(not(any(test, feature = "small_chunks")))]
+const CHUNK_MAX_ITEMS: usize = 64;
+
+const CHUNK_MIN_ITEMS: usize = CHUNK_MAX_ITEMS / 2;
+
+/// A collection of values attached to byte ranges of a text, like syntax
+/// highlights, search matches or diagnostics, whose ranges move along with
+/// the edits made to the text.
+///
+/// The `Decorations` don't own the text, so every edit has to be reported to
+/// them via [`edit()`](Self::edit()), passing the same byte range and the
+/// length of the same text given to [`Rope::replace()`](crate::Rope::replace).
+/// A decoration moves when text is inserted before it or at its start, grows
+/// when text is inserted strictly inside it and shrinks when some of its text
+/// is deleted, possibly becoming empty. Decorations are never removed by an
+/// edit.
+///
+/// The decorations are stored in a B-tree ordered by their start, where each
+/// decoration records its distance from the previous one and every node
+/// keeps the furthest end of the decorations below it. This
+/// allows [`overlapping()`](Self::overlapping()) to skip the subtrees which
+/// can't intersect the given range, so querying the decorations in a
+/// viewport takes logarithmic time plus the number of decorations returned,
+/// while an edit only rewrites the decorations it overlaps.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::decorations::Decorations;
+/// # use crop::Rope;
+/// #
+/// let mut rope = Rope::from("fn foo() {}");
+///
+/// let mut decorations = Decorations::new(rope.byte_len());
+/// decorations.insert(0..2, "keyword");
+/// decorations.insert(3..6, "function");
+///
+/// rope.insert(4, "oo");
+/// decorations.edit(4..4, 2);
+///
+/// rope.insert(0, "pub ");
+/// decorations.edit(0..0, 4);
+///
+/// assert_eq!(rope, "pub fn foooo() {}");
+///
+/// let highlights = decorations.overlapping(5..).collect::<Vec<_>>();
+/// assert_eq!(highlights, [(4..6, &"keyword"), (7..12, &"function")]);
+/// ```
+pub struct Decorations<T> {
+    tree: Tree<ARITY, DecorationChunk<T>>,
+    byte_len: usize,
+}
+
+impl<T> Clone for Decorations<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { tree: self.tree.clone(), byte_len: self.byte_len }
+    }
+}
+
+impl<T: Debug> Debug for Decorations<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_list().entries(self.overlapping(..)).finish()
+    }
+}
+
+impl<T> Decorations<T> {
+    /// Returns the length of the text the decorations are attached to.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        self.byte_len
+    }
+
+    /// Removes all the decorations.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.tree = Tree::default();
+    }
+
+    /// Updates the decorations after the text in the specified byte range
+    /// was replaced by `text_len` bytes of new text.
+    ///
+    /// Only the decorations intersecting the range and the first one after
+    /// it are rewritten, so this takes logarithmic time for each of them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn edit<R>(&mut self, byte_range: R, text_len: usize)
+    where
+        R: RangeBounds<usize>,
+        T: Clone + 'static,
+    {
+        let (start, end) = self.check_range(byte_range);
+
+        let map = |offset: usize, is_start: bool| {
+            if offset < start || (offset == start && !is_start) {
+                offset
+            } else if offset <= end {
+                start + text_len
+            } else {
+                offset + text_len - (end - start)
+            }
+        };
+
+        // The decorations starting before the edit keep their start, so only
+        // the ones ending after the start of the edit have to be updated.
+        // They're found by skipping the subtrees which end before it.
+        let mut resized = Vec::new();
+
+        let mut walker = Walker::new(&self.tree);
+
+        while let Some((item_start, item)) = walker
+            .next(|last_start, max_end| last_start < start && max_end <= start)
+        {
+            if item_start >= start {
+                break;
+            }
+
+            let item_end = item_start + item.len;
+
+            if item_end > start {
+                let len = map(item_end, false) - item_start;
+                let value = item.value.clone();
+                let item = Item { gap: item.gap, len, value };
+                resized.push((walker.idx - 1, item));
+            }
+        }
+
+        // The decorations starting inside the edit move, which changes the
+        // distance from the previous one of the first decoration after it.
+        let (first_idx, mut prev_start, old) = self.collect(
+            |last_start, _| last_start < start,
+            |item_start, _| item_start >= start,
+            |item_start| item_start > end,
+        );
+
+        let replaced = old.len();
+
+        let new = old
+            .into_iter()
+            .map(|(item_start, len, value)| {
+                let new_start = map(item_start, true);
+                let new_end = map(item_start + len, false).max(new_start);
+                let gap = new_start - prev_start;
+                prev_start = new_start;
+                Item { gap, len: new_end - new_start, value }
+            })
+            .collect::<Vec<_>>();
+
+        self.tree.replace(
+            ItemMetric(first_idx)..ItemMetric(first_idx + replaced),
+            new,
+        );
+
+        // Edits never add or remove decorations, so the indices of the
+        // resized ones are still valid.
+        for (idx, item) in resized {
+            self.tree.replace(
+                ItemMetric(idx)..ItemMetric(idx + 1),
+                alloc::vec![item],
+            );
+        }
+
+        self.byte_len = self.byte_len - (end - start) + text_len;
+    }
+
+    /// Attaches `value` to the given byte range. If other decorations start
+    /// at the same offset, the new one comes after them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn insert<R>(&mut self, byte_range: R, value: T)
+    where
+        R: RangeBounds<usize>,
+        T: Clone + 'static,
+    {
+        let (start, end) = self.check_range(byte_range);
+
+        let (idx, prev_start, next) = self.collect(
+            |last_start, _| last_start <= start,
+            |item_start, _| item_start > start,
+            |_| true,
+        );
+
+        let replaced = next.len();
+
+        let mut new = Vec::with_capacity(2);
+
+        new.push(Item { gap: start - prev_start, len: end - start, value });
+
+        // The next decoration now starts from the new one.
+        if let Some((next_start, len, value)) = next.into_iter().next() {
+            new.push(Item { gap: next_start - start, len, value });
+        }
+
+        self.tree.replace(ItemMetric(idx)..ItemMetric(idx + replaced), new);
+    }
+
+    /// Returns `true` if there are no decorations.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of decorations.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tree.summary().items
+    }
+
+    /// Creates an empty set of decorations over a text of `byte_len` bytes.
+    #[inline]
+    pub fn new(byte_len: usize) -> Self {
+        Self { tree: Tree::default(), byte_len }
+    }
+
+    /// Returns an iterator over the byte ranges and values of the decorations
+    /// intersecting the given byte range, ordered by their start.
+    ///
+    /// Decorations which only touch the range at one of its ends aren't
+    /// included, unless either the decoration or the range is empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn overlapping<R>(&self, byte_range: R) -> Overlapping<'_, T>
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = self.check_range(byte_range);
+        Overlapping { walker: Some(Walker::new(&self.tree)), start, end }
+    }
+
+    /// Removes all the decorations intersecting the given byte range, as
+    /// defined by [`overlapping()`](Self::overlapping()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    #[track_caller]
+    #[inline]
+    pub fn remove_overlapping<R>(&mut self, byte_range: R)
+    where
+        R: RangeBounds<usize>,
+        T: Clone + 'static,
+    {
+        let (start, end) = self.check_range(byte_range);
+
+        let (first_idx, mut prev_start, old) = self.collect(
+            |_, max_end| max_end < start,
+            |item_start, item_end| {
+                intersects(item_start..item_end, start..end)
+            },
+            |item_start| item_start > end,
+        );
+
+        let replaced = old.len();
+
+        let kept = old
+            .into_iter()
+            .filter(|(item_start, len, _)| {
+                !intersects(*item_start..item_start + len, start..end)
+            })
+            .map(|(item_start, len, value)| {
+                let gap = item_start - prev_start;
+                prev_start = item_start;
+                Item { gap, len, value }
+            })
+            .collect::<Vec<_>>();
+
+        self.tree.replace(
+            ItemMetric(first_idx)..ItemMetric(first_idx + replaced),
+            kept,
+        );
+    }
+
+    #[track_caller]
+    #[inline]
+    fn check_range<R>(&self, byte_range: R) -> (usize, usize)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len);
+
+        assert!(
+            start <= end,
+            "byte start {} is greater than byte end {}",
+            start,
+            end
+        );
+
+        assert!(
+            end <= self.byte_len,
+            "byte end {} is out of bounds in Decorations over {} bytes",
+            end,
+            self.byte_len
+        );
+
+        (start, end)
+    }
+
+    /// Collects the run of decorations going from the first one for which
+    /// `is_first` returns `true` up to and including the first one after it
+    /// for which `is_last` returns `true`, returning the index of the first
+    /// decoration in the run, the start of the one before it and the
+    /// `(start, len, value)` of every decoration in the run.
+    ///
+    /// The subtrees for which `can_skip` returns `true` when given the start
+    /// of their last decoration and the furthest end of their decorations
+    /// are skipped while looking for the first decoration.
+    #[inline]
+    fn collect(
+        &self,
+        can_skip: impl Fn(usize, usize) -> bool,
+        is_first: impl Fn(usize, usize) -> bool,
+        is_last: impl Fn(usize) -> bool,
+    ) -> (usize, usize, Vec<(usize, usize, T)>)
+    where
+        T: Clone,
+    {
+        let mut walker = Walker::new(&self.tree);
+
+        while let Some((start, item)) = walker.next(&can_skip) {
+            if !is_first(start, start + item.len) {
+                continue;
+            }
+
+            let first_idx = walker.idx - 1;
+            let prev_start = start - item.gap;
+
+            let mut run = alloc::vec![(start, item.len, item.value.clone())];
+
+            if !is_last(start) {
+                while let Some((start, item)) = walker.next(|_, _| false) {
+                    run.push((start, item.len, item.value.clone()));
+
+                    if is_last(start) {
+                        break;
+                    }
+                }
+            }
+
+            return (first_idx, prev_start, run);
+        }
+
+        (walker.idx, walker.offset, Vec::new())
+    }
+}
+
+/// An iterator over the decorations intersecting a byte range.
+///
+/// This struct is created by [`Decorations::overlapping()`]. See its
+/// documentation for more.
+pub struct Overlapping<'a, T> {
+    /// `None` once a decoration starting after the end of the range was
+    /// found.
+    walker: Option<Walker<'a, T>>,
+    start: usize,
+    end: usize,
+}
+
+impl<T> Debug for Overlapping<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Overlapping")
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> Iterator for Overlapping<'a, T> {
+    type Item = (Range<usize>, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.start;
+
+        loop {
+            let walker = self.walker.as_mut()?;
+
+            let (item_start, item) =
+                walker.next(|_, max_end| max_end < start)?;
+
+            if item_start > self.end {
+                self.walker = None;
+                return None;
+            }
+
+            let range = item_start..item_start + item.len;
+
+            if intersects(range.clone(), start..self.end) {
+                return Some((range, &item.value));
+            }
+        }
+    }
+}
+
+impl<T> core::iter::FusedIterator for Overlapping<'_, T> {}
+
+/// Returns `true` if the two ranges have some bytes in common, or if one of
+/// them is empty and lies within the other.
+#[inline]
+fn intersects(a: Range<usize>, b: Range<usize>) -> bool {
+    a.start <= b.end
+        && b.start <= a.end
+        && ((a.start < b.end && b.start < a.end)
+            || a.is_empty()
+            || b.is_empty())
+}
+
+/// Walks over the decorations in order, keeping track of their starts.
+struct Walker<'a, T> {
+    subtrees: Subtrees<'a, ARITY, DecorationChunk<T>>,
+    items: core::slice::Iter<'a, Item<T>>,
+
+    /// The start of the last decoration walked over, or zero.
+    offset: usize,
+
+    /// The number of decorations walked over.
+    idx: usize,
+}
+
+impl<'a, T> Walker<'a, T> {
+    #[inline]
+    fn new(tree: &'a Tree<ARITY, DecorationChunk<T>>) -> Self {
+        Self { subtrees: tree.subtrees(), items: [].iter(), offset: 0, idx: 0 }
+    }
+
+    /// Returns the start of the next decoration together with the decoration
+    /// itself, skipping over the subtrees for which `can_skip` returns `true`
+    /// when given the start of their last decoration and the furthest end of
+    /// their decorations.
+    #[inline]
+    fn next(
+        &mut self,
+        can_skip: impl Fn(usize, usize) -> bool,
+    ) -> Option<(usize, &'a Item<T>)> {
+        loop {
+            if let Some(item) = self.items.next() {
+                self.offset += item.gap;
+                self.idx += 1;
+                return Some((self.offset, item));
+            }
+
+            let subtree = self.subtrees.next()?;
+            let summary = subtree.summary();
+            let last_start = self.offset + summary.bytes;
+
+            if can_skip(last_start, self.offset + summary.max_end) {
+                self.offset = last_start;
+                self.idx += summary.items;
+            } else if let Some(leaf) = subtree.leaf() {
+                self.items = leaf.items.iter();
+            } else {
+                self.subtrees.descend(subtree);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Item<T> {
+    /// The distance between the start of this decoration and the start of
+    /// the previous one, or the start of the text for the first one.
+    gap: usize,
+
+    len: usize,
+    value: T,
+}
+
+#[derive(Clone)]
+struct DecorationChunk<T> {
+    items: Vec<Item<T>>,
+}
+
+impl<T> Default for DecorationChunk<T> {
+    #[inline]
+    fn default() -> Self {
+        Self { items: Vec::new() }
+    }
+}
+
+impl<T> Debug for DecorationChunk<T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl<T> Summarize for DecorationChunk<T> {
+    type Summary = DecorationSummary;
+
+    const INVERTIBLE_SUMMARY: bool = false;
+
+    #[inline]
+    fn summarize(&self) -> Self::Summary {
+        self.as_slice().summarize()
+    }
+}
+
+impl<T> BaseMeasured for DecorationChunk<T> {
+    type BaseMetric = ItemMetric;
+}
+
+impl<T> AsSlice for DecorationChunk<T> {
+    type Slice<'a> = DecorationSlice<'a, T>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn as_slice(&self) -> DecorationSlice<'_, T> {
+        DecorationSlice { items: &self.items }
+    }
+}
+
+impl<T: Clone> From<DecorationSlice<'_, T>> for DecorationChunk<T> {
+    #[inline]
+    fn from(slice: DecorationSlice<'_, T>) -> Self {
+        Self { items: slice.items.to_vec() }
+    }
+}
+
+impl<T: Clone + 'static> BalancedLeaf for DecorationChunk<T> {
+    #[inline]
+    fn is_underfilled(&self, summary: &DecorationSummary) -> bool {
+        summary.items < CHUNK_MIN_ITEMS
+    }
+
+    #[inline]
+    fn balance_leaves(
+        (left, left_summary): (&mut Self, &mut DecorationSummary),
+        (right, right_summary): (&mut Self, &mut DecorationSummary),
+    ) {
+        // The two chunks can be combined in a single one.
+        if left.items.len() + right.items.len() <= CHUNK_MAX_ITEMS {
+            left.items.append(&mut right.items);
+        }
+        // The left side is underfilled => take items from the right side.
+        else if left.items.len() < CHUNK_MIN_ITEMS {
+            let missing = CHUNK_MIN_ITEMS - left.items.len();
+            left.items.extend(right.items.drain(..missing));
+        }
+        // The right side is underfilled => take items from the left side.
+        else if right.items.len() < CHUNK_MIN_ITEMS {
+            let missing = CHUNK_MIN_ITEMS - right.items.len();
+            let split = left.items.len() - missing;
+            right.items.splice(..0, left.items.drain(split..));
+        }
+
+        *left_summary = left.summarize();
+        *right_summary = right.summarize();
+    }
+}
+
+impl<T: Clone + 'static> ReplaceableLeaf<ItemMetric>
+    for DecorationChunk<T>
+{
+    type Replacement<'a> = Vec<Item<T>>;
+
+    type ExtraLeaves = alloc::vec::IntoIter<Self>;
+
+    #[track_caller]
+    #[inline]
+    fn replace<R>(
+        &mut self,
+        summary: &mut DecorationSummary,
+        range: R,
+        replacement: Vec<Item<T>>,
+    ) -> Option<Self::ExtraLeaves>
+    where
+        R: RangeBounds<ItemMetric>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(range, 0, self.items.len());
+
+        debug_assert!(start <= end);
+        debug_assert!(end <= self.items.len());
+
+        self.items.splice(start..end, replacement);
+
+        if self.items.len() <= CHUNK_MAX_ITEMS {
+            *summary = self.summarize();
+            return None;
+        }
+
+        // Split the items evenly in as few chunks as possible, which all end
+        // up having at least `CHUNK_MIN_ITEMS` items.
+        let total = self.items.len();
+        let chunks = (total + CHUNK_MAX_ITEMS - 1) / CHUNK_MAX_ITEMS;
+
+        let mut items = core::mem::take(&mut self.items).into_iter();
+
+        let mut leaves = (0..chunks)
+            .map(|idx| {
+                let len = total / chunks + usize::from(idx < total % chunks);
+                Self { items: items.by_ref().take(len).collect() }
+            })
+            .collect::<Vec<_>>();
+
+        *self = leaves.remove(0);
+        *summary = self.summarize();
+
+        Some(leaves.into_iter())
+    }
+
+    #[inline]
+    fn remove_up_to(
+        &mut self,
+        summary: &mut DecorationSummary,
+        ItemMetric(up_to): ItemMetric,
+    ) {
+        self.items.drain(..up_to);
+        *summary = self.summarize();
+    }
+}
+
+struct DecorationSlice<'a, T> {
+    items: &'a [Item<T>],
+}
+
+impl<T> Clone for DecorationSlice<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DecorationSlice<'_, T> {}
+
+impl<T> Debug for DecorationSlice<'_, T> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let items = self.items.iter().map(|item| (item.gap, item.len));
+        f.debug_list().entries(items).finish()
+    }
+}
+
+impl<T> Summarize for DecorationSlice<'_, T> {
+    type Summary = DecorationSummary;
+
+    const INVERTIBLE_SUMMARY: bool = false;
+
+    #[inline]
+    fn summarize(&self) -> Self::Summary {
+        let mut bytes = 0;
+        let mut max_end = 0;
+
+        for item in self.items {
+            bytes += item.gap;
+            max_end = max_end.max(bytes + item.len);
+        }
+
+        DecorationSummary { items: self.items.len(), bytes, max_end }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct DecorationSummary {
+    /// The number of decorations.
+    items: usize,
+
+    /// The sum of the gaps of the decorations, i.e. the distance between the
+    /// start of the last decoration and the start of the one before the
+    /// first.
+    bytes: usize,
+
+    /// The furthest end of the decorations, measured from the start of the
+    /// one before the first.
+    ///
+    /// It can't be recovered when subtracting a summary, which leaves it
+    /// unchanged, so internal nodes recompute it from their children instead
+    /// (see [`Summarize::INVERTIBLE_SUMMARY`]).
+    max_end: usize,
+}
+
+impl Add<&Self> for DecorationSummary {
+    type Output = Self;
+
+    #[inline]
+    fn add(mut self, rhs: &Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl Sub<&Self> for DecorationSummary {
+    type Output = Self;
+
+    #[inline]
+    fn sub(mut self, rhs: &Self) -> Self {
+        self -= rhs;
+        self
+    }
+}
+
+impl AddAssign<&Self> for DecorationSummary {
+    #[inline]
+    fn add_assign(&mut self, rhs: &Self) {
+        self.max_end = self.max_end.max(self.bytes + rhs.max_end);
+        self.items += rhs.items;
+        self.bytes += rhs.bytes;
+    }
+}
+
+impl SubAssign<&Self> for DecorationSummary {
+    #[inline]
+    fn sub_assign(&mut self, rhs: &Self) {
+        self.items -= rhs.items;
+        self.bytes -= rhs.bytes;
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct ItemMetric(usize);
+
+impl Add for ItemMetric {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Sub for ItemMetric {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl AddAssign for ItemMetric {
+    #[inline]
+    fn add_assign(&mut self, other: Self) {
+        self.0 += other.0
+    }
+}
+
+impl SubAssign for ItemMetric {
+    #[inline]
+    fn sub_assign(&mut self, other: Self) {
+        self.0 -= other.0
+    }
+}
+
+impl Add<usize> for ItemMetric {
+    type Output = usize;
+
+    #[inline]
+    fn add(self, other: usize) -> usize {
+        self.0 + other
+    }
+}
+
+impl From<ItemMetric> for usize {
+    #[inline]
+    fn from(ItemMetric(value): ItemMetric) -> usize {
+        value
+    }
+}
+
+impl Metric<DecorationSummary> for ItemMetric {
+    #[inline]
+    fn zero() -> Self {
+        Self(0)
+    }
+
+    #[inline]
+    fn one() -> Self {
+        Self(1)
+    }
+
+    #[inline]
+    fn measure(summary: &DecorationSummary) -> Self {
+        Self(summary.items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks that the furthest end stored in the root is the end of the
+    /// decoration reaching the furthest.
+    fn assert_max_end<T>(decorations: &Decorations<T>) {
+        let max_end = decorations
+            .overlapping(..)
+            .map(|(range, _)| range.end)
+            .max()
+            .unwrap_or(0);
+
+        assert_eq!(decorations.tree.summary().max_end, max_end);
+    }
+
+    #[test]
+    fn max_end_after_edits() {
+        let mut decorations = Decorations::new(1000);
+
+        for start in (0..1000).step_by(10) {
+            decorations.insert(start..start + 5, ());
+        }
+
+        decorations.insert(100..900, ());
+        assert_max_end(&decorations);
+
+        // Shrinking the decoration reaching the furthest lowers the end of
+        // all the nodes containing it.
+        decorations.edit(500..995, 0);
+        assert_max_end(&decorations);
+
+        decorations.edit(0..0, 30);
+        assert_max_end(&decorations);
+
+        decorations.remove_overlapping(99..100);
+        assert_max_end(&decorations);
+
+        decorations.edit(10..505, 0);
+        assert_max_end(&decorations);
+
+        decorations.clear();
+        assert_max_end(&decorations);
+    }
+}
@@ -0,0 +1,591 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Index, IndexMut, Range};
+
+use super::RopeSlice;
+
+/// A line diff between two [`Rope`](crate::Rope)s or
+/// [`RopeSlice`](crate::RopeSlice)s.
+///
+/// The diff is computed line by line using the [Myers diff algorithm][myers],
+/// after trimming the lines shared by the start and the end of the two texts.
+/// This means that diffing two large texts which only differ in a few places
+/// is fast.
+///
+/// Two lines are considered equal if they're equal including their line
+/// breaks (see [`Rope::raw_lines()`](crate::Rope::raw_lines())), so a line
+/// ending in `\r\n` never matches one ending in `\n`, and a last line that's
+/// missing its line break never matches a line that has one.
+///
+/// [myers]: http://www.xmailserver.org/diff2.pdf
+///
+/// # Examples
+///
+/// ```
+/// # use crop::diff::{Hunk, LineDiff};
+/// # use crop::Rope;
+/// #
+/// let old = Rope::from("foo\nbar\nbaz\n");
+/// let new = Rope::from("foo\nqux\nbaz\nquux\n");
+///
+/// let diff = LineDiff::new(&old, &new);
+///
+/// assert_eq!(
+///     diff.hunks(),
+///     [
+///         Hunk { old_lines: 1..2, new_lines: 1..2 },
+///         Hunk { old_lines: 3..3, new_lines: 3..4 },
+///     ]
+/// );
+///
+/// assert_eq!(
+///     diff.unified().to_string(),
+///     "@@ -1,3 +1,4 @@\n foo\n-bar\n+qux\n baz\n+quux\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct LineDiff<'a> {
+    old: RopeSlice<'a>,
+    new: RopeSlice<'a>,
+    hunks: Vec<Hunk>,
+}
+
+/// A range of lines of the old text that was replaced by a range of lines of
+/// the new text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hunk {
+    /// The replaced lines of the old text. It's empty if lines were only
+    /// added, in which case its start is the line before which they were
+    /// added.
+    pub old_lines: Range<usize>,
+
+    /// The lines of the new text that replaced
+    /// [`old_lines`](Self::old_lines). It's empty if lines were only
+    /// deleted, in which case its start is the line where they used to be.
+    pub new_lines: Range<usize>,
+}
+
+impl<'a> LineDiff<'a> {
+    /// Computes the line diff between two texts.
+    #[inline]
+    pub fn new<O, N>(old: O, new: N) -> Self
+    where
+        O: Into<RopeSlice<'a>>,
+        N: Into<RopeSlice<'a>>,
+    {
+        let (old, new) = (old.into(), new.into());
+        let hunks = diff(old, new);
+        Self { old, new, hunks }
+    }
+
+    /// Returns the hunks of this diff, sorted by their position. Two
+    /// consecutive hunks are always separated by at least one unchanged line.
+    #[inline]
+    pub fn hunks(&self) -> &[Hunk] {
+        &self.hunks
+    }
+
+    /// Returns `true` if the two texts have the same lines.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    /// Returns the old text.
+    #[inline]
+    pub fn old(&self) -> RopeSlice<'a> {
+        self.old
+    }
+
+    /// Returns the new text.
+    #[inline]
+    pub fn new_text(&self) -> RopeSlice<'a> {
+        self.new
+    }
+
+    /// Returns a [`UnifiedDiff`] which can be used to format this diff in the
+    /// unified format via its `Display` implementation.
+    #[inline]
+    pub fn unified(&self) -> UnifiedDiff<'_, 'a> {
+        UnifiedDiff { diff: self, context_len: 3, header: None }
+    }
+}
+
+/// Formats a [`LineDiff`] in the unified format.
+///
+/// This struct is created by [`LineDiff::unified()`]. By default it includes
+/// 3 lines of context around every change and no `---`/`+++` header.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::diff::LineDiff;
+/// # use crop::Rope;
+/// #
+/// let old = Rope::from("foo\nbar\nbaz");
+/// let new = Rope::from("foo\nbar\nqux");
+///
+/// let diff = LineDiff::new(&old, &new);
+///
+/// assert_eq!(
+///     diff.unified().context_len(1).header("a/foo", "b/foo").to_string(),
+///     "--- a/foo\n\
+///      +++ b/foo\n\
+///      @@ -2,2 +2,2 @@\n \
+///      bar\n\
+///      -baz\n\
+///      \\ No newline at end of file\n\
+///      +qux\n\
+///      \\ No newline at end of file\n"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnifiedDiff<'d, 'a> {
+    diff: &'d LineDiff<'a>,
+    context_len: usize,
+    header: Option<(&'d str, &'d str)>,
+}
+
+impl<'d> UnifiedDiff<'d, '_> {
+    /// Sets the number of unchanged lines to show before and after every
+    /// change. Changes separated by at most twice as many lines are shown in
+    /// the same hunk.
+    #[inline]
+    pub fn context_len(mut self, context_len: usize) -> Self {
+        self.context_len = context_len;
+        self
+    }
+
+    /// Sets the names of the old and the new file to show in the `---` and
+    /// `+++` header lines.
+    #[inline]
+    pub fn header(mut self, old_name: &'d str, new_name: &'d str) -> Self {
+        self.header = Some((old_name, new_name));
+        self
+    }
+
+    /// Writes the lines in the given range of a text, prefixing each of them
+    /// with `prefix`.
+    #[inline]
+    fn write_lines(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        text: RopeSlice<'_>,
+        lines: Range<usize>,
+        prefix: char,
+    ) -> fmt::Result {
+        let line_len = text.line_len();
+
+        for line_idx in lines {
+            // Write the line together with its own line break, so that the
+            // diff of a CRLF text keeps its `\r`s.
+            let line = text.line_slice(line_idx..line_idx + 1);
+            write!(f, "{prefix}{line}")?;
+
+            if is_last(line_idx, line_len, text) {
+                f.write_str("\n\\ No newline at end of file\n")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for UnifiedDiff<'_, '_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LineDiff { old, new, hunks } = self.diff;
+
+        if let Some((old_name, new_name)) = self.header {
+            writeln!(f, "--- {old_name}")?;
+            writeln!(f, "+++ {new_name}")?;
+        }
+
+        let old_line_len = old.line_len();
+
+        let mut hunks = hunks.iter().peekable();
+
+        while let Some(first) = hunks.next() {
+            // Group together all the hunks whose context lines would touch or
+            // overlap.
+            let mut group = alloc::vec![first];
+
+            while let Some(next) = hunks.peek() {
+                let last = group[group.len() - 1];
+
+                if next.old_lines.start - last.old_lines.end
+                    > 2 * self.context_len
+                {
+                    break;
+                }
+
+                group.push(next);
+                hunks.next();
+            }
+
+            let last = group[group.len() - 1];
+
+            let before = first.old_lines.start.min(self.context_len);
+
+            let after = (old_line_len - last.old_lines.end)
+                .min(self.context_len);
+
+            let old_start = first.old_lines.start - before;
+            let new_start = first.new_lines.start - before;
+
+            let old_end = last.old_lines.end + after;
+            let new_end = last.new_lines.end + after;
+
+            writeln!(
+                f,
+                "@@ -{} +{} @@",
+                HunkRange(old_start..old_end),
+                HunkRange(new_start..new_end),
+            )?;
+
+            let mut old_line = old_start;
+
+            for hunk in group {
+                let unchanged = old_line..hunk.old_lines.start;
+                self.write_lines(f, *old, unchanged, ' ')?;
+                self.write_lines(f, *old, hunk.old_lines.clone(), '-')?;
+                self.write_lines(f, *new, hunk.new_lines.clone(), '+')?;
+                old_line = hunk.old_lines.end;
+            }
+
+            self.write_lines(f, *old, old_line..old_end, ' ')?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Formats a range of lines in a hunk header, i.e. as the 1-based index of
+/// its first line followed by its length, which is omitted if it's 1. Empty
+/// ranges start at the line before them.
+struct HunkRange(Range<usize>);
+
+impl fmt::Display for HunkRange {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.len() {
+            0 => write!(f, "{},0", self.0.start),
+            1 => write!(f, "{}", self.0.start + 1),
+            len => write!(f, "{},{}", self.0.start + 1, len),
+        }
+    }
+}
+
+/// Computes the hunks of the line diff between `old` and `new`.
+#[inline]
+fn diff<'a>(old: RopeSlice<'a>, new: RopeSlice<'a>) -> Vec<Hunk> {
+    let old_line_len = old.line_len();
+    let new_line_len = new.line_len();
+
+    // Trim the lines shared by the start of both texts,
+    let prefix = old
+        .raw_lines()
+        .zip(new.raw_lines())
+        .take_while(|(o, n)| o == n)
+        .count();
+
+    let max_suffix = old_line_len.min(new_line_len) - prefix;
+
+    // and by the end.
+    let suffix = old
+        .raw_lines()
+        .rev()
+        .zip(new.raw_lines().rev())
+        .take(max_suffix)
+        .take_while(|(o, n)| o == n)
+        .count();
+
+    let old_lines = prefix..old_line_len - suffix;
+    let new_lines = prefix..new_line_len - suffix;
+
+    if old_lines.is_empty() || new_lines.is_empty() {
+        return if old_lines.is_empty() && new_lines.is_empty() {
+            Vec::new()
+        } else {
+            alloc::vec![Hunk { old_lines, new_lines }]
+        };
+    }
+
+    // Map every line in the middle to an id, so that the diff algorithm only
+    // has to compare integers.
+
+    let mut ids = BTreeMap::new();
+
+    let mut id_of = |key| {
+        let next_id = ids.len();
+        *ids.entry(key).or_insert(next_id)
+    };
+
+    let old_ids = old
+        .raw_lines()
+        .skip(prefix)
+        .take(old_lines.len())
+        .map(&mut id_of)
+        .collect::<Vec<_>>();
+
+    let new_ids = new
+        .raw_lines()
+        .skip(prefix)
+        .take(new_lines.len())
+        .map(&mut id_of)
+        .collect::<Vec<_>>();
+
+    let mut myers = Myers::new(&old_ids, &new_ids, prefix);
+    myers.diff(0..old_ids.len(), 0..new_ids.len());
+    myers.hunks
+}
+
+/// Returns whether the line is the last one of a text that doesn't end with a
+/// line break.
+#[inline]
+fn is_last(line_idx: usize, line_len: usize, text: RopeSlice<'_>) -> bool {
+    line_idx + 1 == line_len && !text.has_trailing_newline
+}
+
+/// The linear space refinement of the Myers diff algorithm described in
+/// section 4b of the paper, which looks for the middle snake of an optimal
+/// edit path by searching forward from the start and backward from the end
+/// of the edit graph at the same time, then recurses on the two halves.
+///
+/// Diagonals are numbered by `x - y`, where `x` is a line offset in `old`
+/// and `y` one in `new`.
+struct Myers<'a> {
+    old: &'a [usize],
+    new: &'a [usize],
+
+    /// The furthest `x` reached on every diagonal by the forward search.
+    forward: V,
+
+    /// The smallest `x` reached on every diagonal by the backward search,
+    /// indexed by the diagonal's distance from the one of the end.
+    backward: V,
+
+    /// The number of lines trimmed before `old` and `new`, which have to be
+    /// added to the line ranges of the hunks.
+    offset: usize,
+
+    hunks: Vec<Hunk>,
+}
+
+impl<'a> Myers<'a> {
+    #[inline]
+    fn new(old: &'a [usize], new: &'a [usize], offset: usize) -> Self {
+        let max_d = max_d(old.len(), new.len());
+
+        Self {
+            old,
+            new,
+            forward: V::new(max_d),
+            backward: V::new(max_d),
+            offset,
+            hunks: Vec::new(),
+        }
+    }
+
+    /// Records that the lines in `old` were replaced by the ones in `new`,
+    /// merging them with the last hunk if they're adjacent.
+    #[inline]
+    fn push_hunk(&mut self, old: Range<usize>, new: Range<usize>) {
+        let old = old.start + self.offset..old.end + self.offset;
+        let new = new.start + self.offset..new.end + self.offset;
+
+        if let Some(last) = self.hunks.last_mut() {
+            if last.old_lines.end == old.start
+                && last.new_lines.end == new.start
+            {
+                last.old_lines.end = old.end;
+                last.new_lines.end = new.end;
+                return;
+            }
+        }
+
+        self.hunks.push(Hunk { old_lines: old, new_lines: new });
+    }
+
+    /// Diffs the given ranges of `old` and `new`, pushing the resulting hunks
+    /// in order.
+    #[inline]
+    fn diff(&mut self, mut old: Range<usize>, mut new: Range<usize>) {
+        while old.start < old.end
+            && new.start < new.end
+            && self.old[old.start] == self.new[new.start]
+        {
+            old.start += 1;
+            new.start += 1;
+        }
+
+        while old.start < old.end
+            && new.start < new.end
+            && self.old[old.end - 1] == self.new[new.end - 1]
+        {
+            old.end -= 1;
+            new.end -= 1;
+        }
+
+        if old.is_empty() && new.is_empty() {
+            return;
+        }
+
+        // If one of the ranges is empty the other one is all insertions or
+        // deletions. If neither is then the ranges differ by at least two
+        // edits, since they start and end with different lines, so the
+        // split point is strictly inside the edit graph.
+        if old.is_empty() || new.is_empty() {
+            self.push_hunk(old, new);
+            return;
+        }
+
+        match self.middle_snake(old.clone(), new.clone()) {
+            Some((x, y)) => {
+                self.diff(old.start..x, new.start..y);
+                self.diff(x..old.end, y..new.end);
+            },
+            None => self.push_hunk(old, new),
+        }
+    }
+
+    /// Returns a point on the middle snake of an optimal edit path between
+    /// the two ranges, as absolute line offsets in `old` and `new`.
+    #[inline]
+    fn middle_snake(
+        &mut self,
+        old: Range<usize>,
+        new: Range<usize>,
+    ) -> Option<(usize, usize)> {
+        let a = &self.old[old.clone()];
+        let b = &self.new[new.clone()];
+
+        let (n, m) = (a.len() as isize, b.len() as isize);
+
+        // The diagonal of the end of the edit graph.
+        let delta = n - m;
+
+        let (forward, backward) = (&mut self.forward, &mut self.backward);
+
+        forward[1] = 0;
+        backward[-1] = n;
+
+        for d in 0..=max_d(a.len(), b.len()) as isize {
+            for k in (-d..=d).step_by(2) {
+                // Either move down from diagonal `k + 1` or right from
+                // diagonal `k - 1`, whichever gets further.
+                let mut x = if k == -d
+                    || (k != d && forward[k - 1] < forward[k + 1])
+                {
+                    forward[k + 1]
+                } else {
+                    forward[k - 1] + 1
+                };
+
+                let mut y = x - k;
+
+                while x < n && y < m && a[x as usize] == b[y as usize] {
+                    x += 1;
+                    y += 1;
+                }
+
+                forward[k] = x;
+
+                // When `delta` is odd the paths can only meet after a
+                // forward step, on a diagonal the backward search has
+                // already reached in `d - 1` steps.
+                if delta % 2 != 0
+                    && (k - delta).abs() < d
+                    && backward[k - delta] <= x
+                {
+                    return Some(point(&old, &new, x, y));
+                }
+            }
+
+            for k in (-d..=d).step_by(2) {
+                // Either move up from diagonal `k - 1` or left from diagonal
+                // `k + 1`, whichever gets further.
+                let mut x = if k == d
+                    || (k != -d && backward[k - 1] < backward[k + 1] - 1)
+                {
+                    backward[k - 1]
+                } else {
+                    backward[k + 1] - 1
+                };
+
+                let mut y = x - (k + delta);
+
+                while x > 0 && y > 0 && a[x as usize - 1] == b[y as usize - 1]
+                {
+                    x -= 1;
+                    y -= 1;
+                }
+
+                backward[k] = x;
+
+                // When `delta` is even the paths can only meet after a
+                // backward step, on a diagonal the forward search has
+                // already reached in `d` steps.
+                if delta % 2 == 0
+                    && (k + delta).abs() <= d
+                    && forward[k + delta] >= x
+                {
+                    return Some(point(&old, &new, x, y));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Converts a point of the edit graph between the two ranges to absolute
+/// line offsets.
+#[inline]
+fn point(
+    old: &Range<usize>,
+    new: &Range<usize>,
+    x: isize,
+    y: isize,
+) -> (usize, usize) {
+    (old.start + x as usize, new.start + y as usize)
+}
+
+/// Returns the number of steps after which the forward and backward searches
+/// are guaranteed to have met, i.e. half the maximum number of edits needed
+/// to go from a sequence of length `n` to one of length `m`, rounded up.
+#[inline]
+fn max_d(n: usize, m: usize) -> usize {
+    (n + m + 1) / 2
+}
+
+/// An array of furthest reaching paths indexed by diagonal, which can be
+/// negative.
+struct V {
+    offset: isize,
+    v: Vec<isize>,
+}
+
+impl V {
+    #[inline]
+    fn new(max_d: usize) -> Self {
+        Self { offset: max_d as isize + 1, v: alloc::vec![0; 2 * max_d + 3] }
+    }
+}
+
+impl Index<isize> for V {
+    type Output = isize;
+
+    #[inline]
+    fn index(&self, index: isize) -> &isize {
+        &self.v[(index + self.offset) as usize]
+    }
+}
+
+impl IndexMut<isize> for V {
+    #[inline]
+    fn index_mut(&mut self, index: isize) -> &mut isize {
+        &mut self.v[(index + self.offset) as usize]
+    }
+}
@@ -0,0 +1,333 @@
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::rope::RopeChunk;
+use super::Rope;
+use crate::tree::{Subtree, Subtrees};
+
+type RopeSubtree<'a> = Subtree<'a, { Rope::arity() }, RopeChunk>;
+
+/// A range of text that differs between two snapshots of a [`Rope`].
+///
+/// This struct is returned by [`Rope::diff_ranges()`]. See its documentation
+/// for more.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiffRange {
+    /// The byte range in the older `Rope`.
+    pub old_bytes: Range<usize>,
+
+    /// The byte range in the newer `Rope` which replaced
+    /// [`old_bytes`](Self::old_bytes).
+    pub new_bytes: Range<usize>,
+
+    /// The lines of the older `Rope` touched by
+    /// [`old_bytes`](Self::old_bytes).
+    pub old_lines: Range<usize>,
+
+    /// The lines of the newer `Rope` touched by
+    /// [`new_bytes`](Self::new_bytes).
+    pub new_lines: Range<usize>,
+}
+
+impl DiffRange {
+    #[inline]
+    fn new(
+        old: &Rope,
+        new: &Rope,
+        old_bytes: Range<usize>,
+        new_bytes: Range<usize>,
+    ) -> Self {
+        Self {
+            old_lines: lines_touched(old, &old_bytes),
+            new_lines: lines_touched(new, &new_bytes),
+            old_bytes,
+            new_bytes,
+        }
+    }
+}
+
+/// Returns the range of lines containing the given byte range. An empty
+/// range touches the line it's on, unless it's at the end of a text ending
+/// with a line break (or of an empty text), in which case it touches none
+/// and `line_len..line_len` is returned.
+#[inline]
+fn lines_touched(rope: &Rope, byte_range: &Range<usize>) -> Range<usize> {
+    let start = rope.line_of_byte(byte_range.start);
+
+    let mut end = rope.line_of_byte(byte_range.end);
+
+    // If the range ends right at the start of a line, that line isn't touched
+    // by it.
+    if byte_range.is_empty() || rope.byte_of_line(end) != byte_range.end {
+        end += 1;
+    }
+
+    // The only line that can be past the last one is the empty line after a
+    // final line break, which isn't counted by `line_len()`.
+    start..end.min(rope.line_len())
+}
+
+/// Walks the subtrees of a `Rope`, keeping track of the byte offset of the
+/// next one.
+struct Walker<'a> {
+    subtrees: Subtrees<'a, { Rope::arity() }, RopeChunk>,
+
+    /// Leaves that have already been yielded by `subtrees` and have to be
+    /// walked again after a resynchronization. Only the last element can be
+    /// an internal node.
+    pending: VecDeque<RopeSubtree<'a>>,
+
+    /// The next subtree, starting at `offset`.
+    next: Option<RopeSubtree<'a>>,
+
+    /// The byte offset of `next`.
+    offset: usize,
+}
+
+impl<'a> Walker<'a> {
+    #[inline]
+    fn new(rope: &'a Rope) -> Self {
+        let mut subtrees = rope.tree.subtrees();
+        let next = subtrees.next();
+        Self { subtrees, pending: VecDeque::new(), next, offset: 0 }
+    }
+
+    /// Moves past the next subtree.
+    #[inline]
+    fn advance(&mut self) {
+        if let Some(next) = self.next {
+            self.offset += next.base_measure().0;
+        }
+        self.next = self.pending.pop_front().or_else(|| self.subtrees.next());
+    }
+
+    /// Replaces the next subtree, which has to be an internal node, with its
+    /// children.
+    #[inline]
+    fn descend(&mut self) {
+        debug_assert!(self.pending.is_empty());
+        let next = self.next.take().unwrap();
+        self.subtrees.descend(next);
+        self.next = self.subtrees.next();
+    }
+
+    /// Moves past the next leaf, descending into internal nodes as needed,
+    /// and returns it together with its offset.
+    #[inline]
+    fn next_leaf(&mut self) -> Option<(RopeSubtree<'a>, usize)> {
+        loop {
+            let next = self.next?;
+
+            if next.is_internal() {
+                self.descend();
+            } else {
+                let offset = self.offset;
+                self.advance();
+                return Some((next, offset));
+            }
+        }
+    }
+
+    /// Walks back to right after the leaf at `offset`, so that the given
+    /// leaves (which have already been walked) are yielded again.
+    #[inline]
+    fn rewind(&mut self, offset: usize, leaves: &[(RopeSubtree<'a>, usize)]) {
+        if let Some(next) = self.next.take() {
+            self.pending.push_front(next);
+        }
+
+        for &(leaf, _) in leaves.iter().rev() {
+            self.pending.push_front(leaf);
+        }
+
+        self.next = self.pending.pop_front();
+        self.offset = offset;
+    }
+}
+
+/// The leaves walked on one side of a changed region while looking for a
+/// leaf shared with the other side.
+#[derive(Default)]
+struct Seen<'a> {
+    leaves: Vec<(RopeSubtree<'a>, usize)>,
+
+    /// Maps the address of a leaf to its index in `leaves`.
+    indices: BTreeMap<usize, usize>,
+}
+
+impl<'a> Seen<'a> {
+    #[inline]
+    fn get(&self, leaf: &RopeSubtree<'_>) -> Option<usize> {
+        leaf.node_address().and_then(|addr| self.indices.get(&addr).copied())
+    }
+
+    #[inline]
+    fn insert(&mut self, leaf: RopeSubtree<'a>, offset: usize) {
+        if let Some(addr) = leaf.node_address() {
+            self.indices.entry(addr).or_insert(self.leaves.len());
+        }
+        self.leaves.push((leaf, offset));
+    }
+}
+
+/// Returns the minimal list of byte ranges that changed between `old` and
+/// `new`.
+///
+/// Both trees are walked together and the nodes they share are skipped
+/// without looking at their contents. When two leaves differ, we keep walking
+/// both sides (the one that's behind first) until we find a leaf that's
+/// shared between them, which is where the changed region ends. Its bounds
+/// are then trimmed by comparing the bytes at its edges.
+#[inline]
+pub(super) fn diff_ranges(old: &Rope, new: &Rope) -> Vec<DiffRange> {
+    let mut old_walker = Walker::new(old);
+    let mut new_walker = Walker::new(new);
+
+    let mut ranges = Vec::new();
+
+    loop {
+        match (old_walker.next, new_walker.next) {
+            (None, None) => break,
+
+            (Some(o), Some(n)) if o.ptr_eq(&n) => {
+                old_walker.advance();
+                new_walker.advance();
+                continue;
+            },
+
+            // Descend into the bigger of the two nodes, hoping to find a
+            // shared subtree in its children.
+            (Some(o), Some(n)) if o.is_internal() || n.is_internal() => {
+                let descend_old = o.is_internal()
+                    && (!n.is_internal()
+                        || o.base_measure() >= n.base_measure());
+
+                if descend_old {
+                    old_walker.descend();
+                } else {
+                    new_walker.descend();
+                }
+                continue;
+            },
+
+            _ => {},
+        }
+
+        let start = (old_walker.offset, new_walker.offset);
+
+        let end = resynchronize(&mut old_walker, &mut new_walker);
+
+        if let Some((old_bytes, new_bytes)) =
+            trim(old, new, start.0..end.0, start.1..end.1)
+        {
+            ranges.push(DiffRange::new(old, new, old_bytes, new_bytes));
+        }
+    }
+
+    ranges
+}
+
+/// Walks both sides until finding a leaf they share, returning the offsets
+/// at which the changed region ends on the old and the new side, in that
+/// order. When this returns both walkers are positioned right after the
+/// shared leaf.
+#[inline]
+fn resynchronize(
+    old_walker: &mut Walker<'_>,
+    new_walker: &mut Walker<'_>,
+) -> (usize, usize) {
+    let (old_start, new_start) = (old_walker.offset, new_walker.offset);
+
+    let mut old_seen = Seen::default();
+    let mut new_seen = Seen::default();
+
+    loop {
+        let old_walked = old_walker.offset - old_start;
+        let new_walked = new_walker.offset - new_start;
+
+        let walk_old = match (old_walker.next, new_walker.next) {
+            (None, None) => {
+                return (old_walker.offset, new_walker.offset);
+            },
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (Some(_), Some(_)) => old_walked <= new_walked,
+        };
+
+        if walk_old {
+            let (leaf, offset) = old_walker.next_leaf().unwrap();
+
+            if let Some(idx) = new_seen.get(&leaf) {
+                let (shared, shared_offset) = new_seen.leaves[idx];
+                new_walker.rewind(
+                    shared_offset + shared.base_measure().0,
+                    &new_seen.leaves[idx + 1..],
+                );
+                return (offset, shared_offset);
+            }
+
+            old_seen.insert(leaf, offset);
+        } else {
+            let (leaf, offset) = new_walker.next_leaf().unwrap();
+
+            if let Some(idx) = old_seen.get(&leaf) {
+                let (shared, shared_offset) = old_seen.leaves[idx];
+                old_walker.rewind(
+                    shared_offset + shared.base_measure().0,
+                    &old_seen.leaves[idx + 1..],
+                );
+                return (shared_offset, offset);
+            }
+
+            new_seen.insert(leaf, offset);
+        }
+    }
+}
+
+/// Trims the bytes shared by the start and the end of the two ranges,
+/// returning `None` if their contents are equal.
+#[inline]
+fn trim(
+    old: &Rope,
+    new: &Rope,
+    mut old_range: Range<usize>,
+    mut new_range: Range<usize>,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let mut prefix = old
+        .byte_slice(old_range.clone())
+        .bytes()
+        .zip(new.byte_slice(new_range.clone()).bytes())
+        .take_while(|(o, n)| o == n)
+        .count();
+
+    // The shared prefix could end in the middle of a multi-byte char, in
+    // which case we have to include all of it in the changed range.
+    while !old.is_char_boundary(old_range.start + prefix) {
+        prefix -= 1;
+    }
+
+    old_range.start += prefix;
+    new_range.start += prefix;
+
+    let mut suffix = old
+        .byte_slice(old_range.clone())
+        .bytes()
+        .rev()
+        .zip(new.byte_slice(new_range.clone()).bytes().rev())
+        .take_while(|(o, n)| o == n)
+        .count();
+
+    while !old.is_char_boundary(old_range.end - suffix) {
+        suffix -= 1;
+    }
+
+    old_range.end -= suffix;
+    new_range.end -= suffix;
+
+    if old_range.is_empty() && new_range.is_empty() {
+        None
+    } else {
+        Some((old_range, new_range))
+    }
+}
@@ -0,0 +1,480 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::ops::{Range, RangeBounds};
+
+use super::Rope;
+use crate::range_bounds_to_start_end;
+
+/// An undo/redo history of the edits made to a [`Rope`].
+///
+/// A `History` owns the `Rope` it tracks, and every edit made through its
+/// [`replace()`](Self::replace()), [`insert()`](Self::insert()) and
+/// [`delete()`](Self::delete()) methods (or through a
+/// [`transaction()`](Self::transaction())) creates a new revision, recording
+/// both the edits and their inverses.
+///
+/// Revisions form a tree rooted at the initial `Rope`: making an edit after
+/// undoing some others starts a new branch instead of discarding them, and
+/// [`redo()`](Self::redo()) follows the branch that was visited last. Since
+/// cloning a `Rope` is cheap, every revision also keeps a snapshot of the
+/// text, so [`undo()`](Self::undo()), [`redo()`](Self::redo()) and
+/// [`jump_to()`](Self::jump_to()) run in constant time.
+///
+/// Consecutive single-line insertions or deletions made outside of a
+/// transaction, like the ones produced by typing or by pressing backspace,
+/// are coalesced into a single revision until
+/// [`checkpoint()`](Self::checkpoint()) is called.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::history::History;
+/// # use crop::Rope;
+/// #
+/// let mut history = History::new(Rope::from("Hello"));
+///
+/// // These are coalesced into a single revision.
+/// history.insert(5, " ");
+/// history.insert(6, "World");
+/// assert_eq!(history.rope(), "Hello World");
+///
+/// history.checkpoint();
+/// history.insert(11, "!");
+///
+/// history.undo();
+/// assert_eq!(history.rope(), "Hello World");
+///
+/// history.undo();
+/// assert_eq!(history.rope(), "Hello");
+///
+/// history.redo();
+/// assert_eq!(history.rope(), "Hello World");
+/// ```
+#[derive(Debug, Clone)]
+pub struct History {
+    rope: Rope,
+    revisions: Vec<Revision>,
+    current: usize,
+}
+
+/// An edit made to a [`Rope`], replacing a byte range with some text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Edit {
+    /// The replaced byte range.
+    pub byte_range: Range<usize>,
+
+    /// The text that replaced it.
+    pub text: String,
+}
+
+impl Edit {
+    /// Applies the edit to the rope, returning its inverse.
+    #[track_caller]
+    #[inline]
+    fn apply(&self, rope: &mut Rope) -> Self {
+        let removed = rope.byte_slice(self.byte_range.clone()).to_string();
+        rope.replace(self.byte_range.clone(), &self.text);
+
+        let start = self.byte_range.start;
+        Self { byte_range: start..start + self.text.len(), text: removed }
+    }
+
+    /// Returns `true` if this edit can be coalesced with `previous`, the last
+    /// edit of the current revision.
+    #[inline]
+    fn continues(&self, previous: &Self, inverse: &Self) -> bool {
+        let is_insertion = |edit: &Self| {
+            edit.byte_range.is_empty()
+                && !edit.text.is_empty()
+                && !edit.text.contains('\n')
+        };
+
+        let is_deletion = |edit: &Self, removed: &str| {
+            !edit.byte_range.is_empty()
+                && edit.text.is_empty()
+                && !removed.contains('\n')
+        };
+
+        let prev_end = previous.byte_range.start + previous.text.len();
+
+        (is_insertion(self)
+            && is_insertion(previous)
+            && self.byte_range.start == prev_end)
+            || (is_deletion(self, &inverse.text)
+                && previous.text.is_empty()
+                && !previous.byte_range.is_empty()
+                && (self.byte_range.end == previous.byte_range.start
+                    || self.byte_range.start == previous.byte_range.start))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Revision {
+    /// The index of the parent revision. The root revision is its own parent.
+    parent: usize,
+
+    /// The child revision to go to when redoing from this revision, i.e.
+    /// the one that was visited last.
+    last_child: Option<usize>,
+
+    /// The edits that turn the parent's text into this revision's text, to
+    /// be applied in order.
+    edits: Vec<Edit>,
+
+    /// The edits that turn this revision's text back into the parent's text,
+    /// to be applied in order.
+    ///
+    /// While the revision is open they're stored in the opposite order, i.e.
+    /// in the order their edits were made, so that coalescing an edit only
+    /// has to push its inverse. They're reversed once it's closed by
+    /// [`History::checkpoint()`].
+    inverse: Vec<Edit>,
+
+    /// The text at this revision.
+    snapshot: Rope,
+
+    /// Whether the next edit can be coalesced into this revision.
+    open: bool,
+}
+
+impl History {
+    /// Marks the end of the current revision, so that the next edit creates
+    /// a new one instead of being coalesced into it.
+    #[inline]
+    pub fn checkpoint(&mut self) {
+        let current = &mut self.revisions[self.current];
+
+        if current.open {
+            current.inverse.reverse();
+            current.open = false;
+        }
+    }
+
+    /// Deletes the text in the specified byte range, creating a new revision.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Rope::delete()`].
+    #[track_caller]
+    #[inline]
+    pub fn delete<R>(&mut self, byte_range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.replace(byte_range, "");
+    }
+
+    /// Inserts `text` at the given byte offset, creating a new revision.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Rope::insert()`].
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, text: T)
+    where
+        T: AsRef<str>,
+    {
+        self.replace(byte_offset..byte_offset, text);
+    }
+
+    /// Returns the `Rope`, consuming the `History`.
+    #[inline]
+    pub fn into_rope(self) -> Rope {
+        self.rope
+    }
+
+    /// Moves to the given revision, replacing the `Rope` with its snapshot.
+    ///
+    /// The revisions along the way become the ones followed by
+    /// [`redo()`](Self::redo()), so that redoing from one of the ancestors of
+    /// `revision` leads back to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the revision is out of bounds (i.e. greater than or equal
+    /// to [`revision_len()`](Self::revision_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::history::History;
+    /// # use crop::Rope;
+    /// #
+    /// let mut history = History::new(Rope::from("a"));
+    ///
+    /// history.insert(1, "b");
+    /// let ab = history.revision();
+    ///
+    /// history.undo();
+    /// history.insert(1, "c");
+    /// assert_eq!(history.rope(), "ac");
+    ///
+    /// // The first branch is still there.
+    /// history.jump_to(ab);
+    /// assert_eq!(history.rope(), "ab");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn jump_to(&mut self, revision: usize) {
+        assert!(
+            revision < self.revisions.len(),
+            "revision {} is out of bounds in history of {} revisions",
+            revision,
+            self.revisions.len()
+        );
+
+        self.checkpoint();
+
+        let mut child = revision;
+
+        while child != 0 {
+            let parent = self.revisions[child].parent;
+            self.revisions[parent].last_child = Some(child);
+            child = parent;
+        }
+
+        self.current = revision;
+        self.rope = self.revisions[revision].snapshot.clone();
+    }
+
+    /// Creates a new `History` whose root revision is `rope`.
+    #[inline]
+    pub fn new(rope: Rope) -> Self {
+        let root = Revision {
+            parent: 0,
+            last_child: None,
+            edits: Vec::new(),
+            inverse: Vec::new(),
+            snapshot: rope.clone(),
+            open: false,
+        };
+
+        Self { rope, revisions: alloc::vec![root], current: 0 }
+    }
+
+    /// Moves to the child revision that was visited last, returning the edits
+    /// that were applied to get there, or `None` if there's nothing to redo.
+    #[inline]
+    pub fn redo(&mut self) -> Option<&[Edit]> {
+        let child = self.revisions[self.current].last_child?;
+        self.checkpoint();
+        self.current = child;
+        self.rope = self.revisions[child].snapshot.clone();
+        Some(&self.revisions[child].edits)
+    }
+
+    /// Replaces the text in the specified byte range with `text`, creating a
+    /// new revision.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Rope::replace()`].
+    #[track_caller]
+    #[inline]
+    pub fn replace<R, T>(&mut self, byte_range: R, text: T)
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<str>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.rope.byte_len());
+
+        let edit = Edit { byte_range: start..end, text: text.as_ref().into() };
+
+        let inverse = edit.apply(&mut self.rope);
+
+        let current = &mut self.revisions[self.current];
+
+        let coalesce = current.open
+            && current.last_child.is_none()
+            && current
+                .edits
+                .last()
+                .map_or(false, |last| edit.continues(last, &inverse));
+
+        if coalesce {
+            current.edits.push(edit);
+            current.inverse.push(inverse);
+            current.snapshot = self.rope.clone();
+        } else {
+            self.push_revision(alloc::vec![edit], alloc::vec![inverse], true);
+        }
+    }
+
+    /// Returns the index of the current revision. The initial revision has
+    /// index 0.
+    #[inline]
+    pub fn revision(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the number of revisions recorded so far, including the
+    /// initial one.
+    #[inline]
+    pub fn revision_len(&self) -> usize {
+        self.revisions.len()
+    }
+
+    /// Returns the `Rope` at the current revision.
+    #[inline]
+    pub fn rope(&self) -> &Rope {
+        &self.rope
+    }
+
+    /// Groups all the edits made within the closure into a single revision,
+    /// which is then undone and redone as a whole.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::history::History;
+    /// # use crop::Rope;
+    /// #
+    /// let mut history = History::new(Rope::from("foo bar"));
+    ///
+    /// history.transaction(|tx| {
+    ///     tx.replace(0..3, "baz");
+    ///     tx.insert(tx.rope().byte_len(), "\n");
+    /// });
+    ///
+    /// assert_eq!(history.rope(), "baz bar\n");
+    ///
+    /// assert_eq!(history.undo().map(|edits| edits.len()), Some(2));
+    /// assert_eq!(history.rope(), "foo bar");
+    /// ```
+    #[inline]
+    pub fn transaction<F, R>(&mut self, f: F) -> R
+    where
+        F: FnOnce(&mut Transaction<'_>) -> R,
+    {
+        let mut tx = Transaction {
+            rope: &mut self.rope,
+            edits: Vec::new(),
+            inverse: Vec::new(),
+        };
+
+        let ret = f(&mut tx);
+
+        let Transaction { edits, mut inverse, .. } = tx;
+
+        if !edits.is_empty() {
+            inverse.reverse();
+            self.push_revision(edits, inverse, false);
+        }
+
+        ret
+    }
+
+    /// Moves to the parent of the current revision, returning the edits that
+    /// were applied to get there, or `None` if there's nothing to undo.
+    #[inline]
+    pub fn undo(&mut self) -> Option<&[Edit]> {
+        if self.current == 0 {
+            return None;
+        }
+
+        self.checkpoint();
+
+        let child = self.current;
+        let parent = self.revisions[child].parent;
+
+        self.revisions[parent].last_child = Some(child);
+        self.current = parent;
+        self.rope = self.revisions[parent].snapshot.clone();
+
+        Some(&self.revisions[child].inverse)
+    }
+
+    #[inline]
+    fn push_revision(
+        &mut self,
+        edits: Vec<Edit>,
+        inverse: Vec<Edit>,
+        open: bool,
+    ) {
+        self.checkpoint();
+
+        let idx = self.revisions.len();
+
+        self.revisions.push(Revision {
+            parent: self.current,
+            last_child: None,
+            edits,
+            inverse,
+            snapshot: self.rope.clone(),
+            open,
+        });
+
+        self.revisions[self.current].last_child = Some(idx);
+        self.current = idx;
+    }
+}
+
+/// A group of edits recorded as a single revision of a [`History`].
+///
+/// This struct is created by [`History::transaction()`]. See its
+/// documentation for more.
+#[derive(Debug)]
+pub struct Transaction<'h> {
+    rope: &'h mut Rope,
+    edits: Vec<Edit>,
+    inverse: Vec<Edit>,
+}
+
+impl Transaction<'_> {
+    /// Deletes the text in the specified byte range.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Rope::delete()`].
+    #[track_caller]
+    #[inline]
+    pub fn delete<R>(&mut self, byte_range: R)
+    where
+        R: RangeBounds<usize>,
+    {
+        self.replace(byte_range, "");
+    }
+
+    /// Inserts `text` at the given byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Rope::insert()`].
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, text: T)
+    where
+        T: AsRef<str>,
+    {
+        self.replace(byte_offset..byte_offset, text);
+    }
+
+    /// Replaces the text in the specified byte range with `text`.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the same cases as [`Rope::replace()`].
+    #[track_caller]
+    #[inline]
+    pub fn replace<R, T>(&mut self, byte_range: R, text: T)
+    where
+        R: RangeBounds<usize>,
+        T: AsRef<str>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.rope.byte_len());
+
+        let edit = Edit { byte_range: start..end, text: text.as_ref().into() };
+
+        self.inverse.push(edit.apply(self.rope));
+        self.edits.push(edit);
+    }
+
+    /// Returns the `Rope` with the edits made so far.
+    #[inline]
+    pub fn rope(&self) -> &Rope {
+        self.rope
+    }
+}
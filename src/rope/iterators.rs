@@ -1,7 +1,10 @@
-use super::metrics::{ByteMetric, LineMetric, RawLineMetric};
+use alloc::string::String;
+
+use super::metrics::{convert, count, ByteMetric, LineMetric, RawLineMetric};
 use super::rope::RopeChunk;
-use super::{Rope, RopeSlice};
-use crate::tree::{Leaves, Units};
+use super::utils::adjust_split_point;
+use super::{Cursor, Pattern, Rope, RopeSlice};
+use crate::tree::{Leaves, TreeSlice, Units};
 
 /// An iterator over the `&str` chunks of `Rope`s and `RopeSlice`s.
 ///
@@ -110,6 +113,10 @@ impl core::iter::FusedIterator for Chunks<'_> {}
 /// and [`RopeSlice`](RopeSlice::bytes()). See their documentation for more.
 #[derive(Clone)]
 pub struct Bytes<'a> {
+    /// The slice we're iterating over, used to reposition the iterator in
+    /// [`Bytes::nth()`] and [`Bytes::nth_back()`].
+    slice: RopeSlice<'a>,
+
     chunks: Chunks<'a>,
 
     /// The chunk used when calling [`Bytes::next()`].
@@ -125,24 +132,26 @@ pub struct Bytes<'a> {
     /// are yet to be yielded.
     backward_byte_idx: usize,
 
-    /// The number of bytes that have been yielded so far.
-    bytes_yielded: usize,
+    /// The byte offset of the next byte yielded by [`Bytes::next()`].
+    start: usize,
 
-    /// The total number of bytes this iterator will yield.
-    bytes_total: usize,
+    /// The byte offset right after the next byte yielded by
+    /// [`Bytes::next_back()`].
+    end: usize,
 }
 
 impl<'a> From<&'a Rope> for Bytes<'a> {
     #[inline]
     fn from(rope: &'a Rope) -> Self {
         Self {
+            slice: rope.byte_slice(..),
             chunks: rope.chunks(),
             forward_chunk: &[],
             forward_byte_idx: 0,
             backward_chunk: &[],
             backward_byte_idx: 0,
-            bytes_yielded: 0,
-            bytes_total: rope.byte_len(),
+            start: 0,
+            end: rope.byte_len(),
         }
     }
 }
@@ -151,13 +160,41 @@ impl<'a> From<&RopeSlice<'a>> for Bytes<'a> {
     #[inline]
     fn from(slice: &RopeSlice<'a>) -> Self {
         Self {
+            slice: *slice,
             chunks: slice.chunks(),
             forward_chunk: &[],
             forward_byte_idx: 0,
             backward_chunk: &[],
             backward_byte_idx: 0,
-            bytes_yielded: 0,
-            bytes_total: slice.byte_len(),
+            start: 0,
+            end: slice.byte_len(),
+        }
+    }
+}
+
+impl<'a> Bytes<'a> {
+    /// Returns a `Bytes` over the `start..end` byte range of `slice`, where
+    /// `start` and `end` don't have to lie on char boundaries.
+    #[inline]
+    fn in_range(slice: RopeSlice<'a>, start: usize, end: usize) -> Self {
+        let (chunks, first, first_idx, last, last_idx) =
+            chunks_in_range(slice, start, end);
+
+        let first = if last.is_empty() {
+            &first.as_bytes()[..first_idx + end - start]
+        } else {
+            first.as_bytes()
+        };
+
+        Self {
+            slice,
+            chunks,
+            forward_chunk: first,
+            forward_byte_idx: first_idx,
+            backward_chunk: last.as_bytes(),
+            backward_byte_idx: last_idx,
+            start,
+            end,
         }
     }
 }
@@ -177,17 +214,31 @@ impl Iterator for Bytes<'_> {
                 let byte = self.backward_chunk[0];
                 self.backward_chunk = &self.backward_chunk[1..];
                 self.backward_byte_idx -= 1;
-                self.bytes_yielded += 1;
+                self.start += 1;
                 return Some(byte);
             }
         }
 
         let byte = self.forward_chunk[self.forward_byte_idx];
         self.forward_byte_idx += 1;
-        self.bytes_yielded += 1;
+        self.start += 1;
         Some(byte)
     }
 
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.forward_chunk.len() - self.forward_byte_idx {
+            self.forward_byte_idx += n;
+            self.start += n;
+        } else if n < self.len() {
+            *self = Self::in_range(self.slice, self.start + n, self.end);
+        } else {
+            *self = Self::in_range(self.slice, self.end, self.end);
+        }
+
+        self.next()
+    }
+
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
         let exact = self.len();
@@ -208,27 +259,93 @@ impl DoubleEndedIterator for Bytes<'_> {
                 let byte_idx = self.forward_chunk.len() - 1;
                 let byte = self.forward_chunk[byte_idx];
                 self.forward_chunk = &self.forward_chunk[..byte_idx];
-                self.bytes_yielded += 1;
+                self.end -= 1;
                 return Some(byte);
             }
         }
 
         self.backward_byte_idx -= 1;
         let byte = self.backward_chunk[self.backward_byte_idx];
-        self.bytes_yielded += 1;
+        self.end -= 1;
         Some(byte)
     }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n < self.backward_byte_idx {
+            self.backward_byte_idx -= n;
+            self.end -= n;
+        } else if n < self.len() {
+            *self = Self::in_range(self.slice, self.start, self.end - n);
+        } else {
+            *self = Self::in_range(self.slice, self.start, self.start);
+        }
+
+        self.next_back()
+    }
 }
 
 impl ExactSizeIterator for Bytes<'_> {
     #[inline]
     fn len(&self) -> usize {
-        self.bytes_total - self.bytes_yielded
+        self.end - self.start
     }
 }
 
 impl core::iter::FusedIterator for Bytes<'_> {}
 
+/// Splits the `start..end` byte range of `slice` into the chunk containing
+/// `start`, the chunk containing the byte before `end`, and the chunks in
+/// between.
+///
+/// Returns a `(chunks, first, first_idx, last, last_idx)` tuple, where
+/// `first_idx` is the offset of `start` in `first` and `last_idx` is the
+/// offset of `end` in `last`.
+///
+/// If the range is contained in a single chunk that's returned as `first`
+/// and both `last` and `chunks` are empty. Note that in this case `first` is
+/// not truncated at `end`, as that's not always a char boundary.
+///
+/// This is used by the `nth` and `nth_back` methods of [`Bytes`] and
+/// [`Chars`] to jump to an arbitrary offset in logarithmic time.
+#[inline]
+pub(super) fn chunks_in_range(
+    slice: RopeSlice<'_>,
+    start: usize,
+    end: usize,
+) -> (Chunks<'_>, &str, usize, &str, usize) {
+    let chunk_at = |byte_offset: usize| {
+        let (leaf, ByteMetric(leaf_offset)) =
+            slice.tree_slice.leaf_at_measure(ByteMetric(byte_offset + 1));
+
+        if byte_offset - leaf_offset < leaf.len_left() {
+            (leaf.left_chunk(), leaf_offset)
+        } else {
+            (leaf.right_chunk(), leaf_offset + leaf.len_left())
+        }
+    };
+
+    let no_chunks = || slice.byte_slice(0..0).chunks();
+
+    if start == end {
+        return (no_chunks(), "", 0, "", 0);
+    }
+
+    let (first, first_offset) = chunk_at(start);
+
+    let (last, last_offset) = chunk_at(end - 1);
+
+    if first_offset == last_offset {
+        return (no_chunks(), first, start - first_offset, "", 0);
+    }
+
+    let chunks = slice
+        .byte_slice(first_offset + first.len()..last_offset)
+        .chunks();
+
+    (chunks, first, start - first_offset, last, end - last_offset)
+}
+
 /// An iterator over the code points (i.e. [`char`]s) of `Rope`s and
 /// `RopeSlice`s.
 ///
@@ -250,6 +367,20 @@ pub struct Chars<'a> {
 
     /// The number of bytes of `backward_chunk` which are yet to be yielded.
     backward_byte_idx: usize,
+
+    /// The slice we're iterating over, used to reposition the iterator in
+    /// [`Chars::nth()`] and [`Chars::nth_back()`].
+    #[cfg(feature = "char-metric")]
+    slice: RopeSlice<'a>,
+
+    /// The byte offset of the next char yielded by [`Chars::next()`].
+    #[cfg(feature = "char-metric")]
+    start: usize,
+
+    /// The byte offset right after the next char yielded by
+    /// [`Chars::next_back()`].
+    #[cfg(feature = "char-metric")]
+    end: usize,
 }
 
 impl<'a> From<&'a Rope> for Chars<'a> {
@@ -261,6 +392,12 @@ impl<'a> From<&'a Rope> for Chars<'a> {
             forward_byte_idx: 0,
             backward_chunk: "",
             backward_byte_idx: 0,
+            #[cfg(feature = "char-metric")]
+            slice: rope.byte_slice(..),
+            #[cfg(feature = "char-metric")]
+            start: 0,
+            #[cfg(feature = "char-metric")]
+            end: rope.byte_len(),
         }
     }
 }
@@ -274,6 +411,39 @@ impl<'a> From<&RopeSlice<'a>> for Chars<'a> {
             forward_byte_idx: 0,
             backward_chunk: "",
             backward_byte_idx: 0,
+            #[cfg(feature = "char-metric")]
+            slice: *slice,
+            #[cfg(feature = "char-metric")]
+            start: 0,
+            #[cfg(feature = "char-metric")]
+            end: slice.byte_len(),
+        }
+    }
+}
+
+#[cfg(feature = "char-metric")]
+impl<'a> Chars<'a> {
+    /// Returns a `Chars` over the `start..end` byte range of `slice`.
+    #[inline]
+    fn in_range(slice: RopeSlice<'a>, start: usize, end: usize) -> Self {
+        let (chunks, first, first_idx, last, last_idx) =
+            chunks_in_range(slice, start, end);
+
+        let first = if last.is_empty() {
+            &first[..first_idx + end - start]
+        } else {
+            first
+        };
+
+        Self {
+            chunks,
+            forward_chunk: first,
+            forward_byte_idx: first_idx,
+            backward_chunk: last,
+            backward_byte_idx: last_idx,
+            slice,
+            start,
+            end,
         }
     }
 }
@@ -302,6 +472,12 @@ impl<'a> Iterator for Chars<'a> {
                 let len = ch.len_utf8();
                 self.backward_chunk = &self.backward_chunk[len..];
                 self.backward_byte_idx -= len;
+
+                #[cfg(feature = "char-metric")]
+                {
+                    self.start += len;
+                }
+
                 return Some(ch);
             }
         }
@@ -318,8 +494,82 @@ impl<'a> Iterator for Chars<'a> {
 
         self.forward_byte_idx += ch.len_utf8();
 
+        #[cfg(feature = "char-metric")]
+        {
+            self.start += ch.len_utf8();
+        }
+
         Some(ch)
     }
+
+    /// Skips the chars of the current chunk without decoding them. If the
+    /// `n`-th char is in another chunk, it jumps there in logarithmic time
+    /// using the char counts stored in the rope's summaries if the
+    /// `char-metric` feature is enabled, or by counting the chars of the
+    /// chunks in between otherwise.
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let rest = &self.forward_chunk[self.forward_byte_idx..];
+
+        let rest_chars = count::chars(rest);
+
+        if n < rest_chars {
+            let skipped = convert::byte_of_char(rest, n);
+
+            self.forward_byte_idx += skipped;
+
+            #[cfg(feature = "char-metric")]
+            {
+                self.start += skipped;
+            }
+
+            return self.next();
+        }
+
+        #[cfg(feature = "char-metric")]
+        {
+            let char_offset = self.slice.char_of_byte(self.start) + n;
+
+            let start = if char_offset < self.slice.char_of_byte(self.end) {
+                self.slice.byte_of_char(char_offset)
+            } else {
+                self.end
+            };
+
+            *self = Self::in_range(self.slice, start, self.end);
+        }
+
+        #[cfg(not(feature = "char-metric"))]
+        {
+            let mut n = n - rest_chars;
+
+            self.forward_byte_idx = self.forward_chunk.len();
+
+            while let Some(chunk) = self.chunks.next() {
+                let chunk_chars = count::chars(chunk);
+
+                self.forward_chunk = chunk;
+
+                if n < chunk_chars {
+                    self.forward_byte_idx = convert::byte_of_char(chunk, n);
+                    return self.next();
+                }
+
+                self.forward_byte_idx = chunk.len();
+
+                n -= chunk_chars;
+            }
+
+            // The remaining chars are in the last chunk, which the backward
+            // side has already started yielding.
+            let rest = &self.backward_chunk[..self.backward_byte_idx];
+            let skipped = convert::byte_of_char(rest, n);
+            self.backward_chunk = &self.backward_chunk[skipped..];
+            self.backward_byte_idx -= skipped;
+        }
+
+        self.next()
+    }
 }
 
 impl DoubleEndedIterator for Chars<'_> {
@@ -345,6 +595,11 @@ impl DoubleEndedIterator for Chars<'_> {
                 self.forward_chunk = &self.forward_chunk
                     [..self.forward_chunk.len() - ch.len_utf8()];
 
+                #[cfg(feature = "char-metric")]
+                {
+                    self.end -= ch.len_utf8();
+                }
+
                 return Some(ch);
             }
         }
@@ -360,8 +615,80 @@ impl DoubleEndedIterator for Chars<'_> {
 
         self.backward_byte_idx -= ch.len_utf8();
 
+        #[cfg(feature = "char-metric")]
+        {
+            self.end -= ch.len_utf8();
+        }
+
         Some(ch)
     }
+
+    /// Same as [`Chars::nth()`], but from the back.
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let rest = &self.backward_chunk[..self.backward_byte_idx];
+
+        let rest_chars = count::chars(rest);
+
+        if n < rest_chars {
+            let new_idx = convert::byte_of_char(rest, rest_chars - n);
+
+            #[cfg(feature = "char-metric")]
+            {
+                self.end -= self.backward_byte_idx - new_idx;
+            }
+
+            self.backward_byte_idx = new_idx;
+
+            return self.next_back();
+        }
+
+        #[cfg(feature = "char-metric")]
+        {
+            let char_offset = self.slice.char_of_byte(self.end);
+
+            let end = if char_offset > self.slice.char_of_byte(self.start) + n
+            {
+                self.slice.byte_of_char(char_offset - n)
+            } else {
+                self.start
+            };
+
+            *self = Self::in_range(self.slice, self.start, end);
+        }
+
+        #[cfg(not(feature = "char-metric"))]
+        {
+            let mut n = n - rest_chars;
+
+            self.backward_byte_idx = 0;
+
+            while let Some(chunk) = self.chunks.next_back() {
+                let chunk_chars = count::chars(chunk);
+
+                self.backward_chunk = chunk;
+
+                if n < chunk_chars {
+                    self.backward_byte_idx =
+                        convert::byte_of_char(chunk, chunk_chars - n);
+                    return self.next_back();
+                }
+
+                n -= chunk_chars;
+            }
+
+            // The remaining chars are in the first chunk, which the forward
+            // side has already started yielding.
+            let rest = &self.forward_chunk[self.forward_byte_idx..];
+            let rest_chars = count::chars(rest);
+            let kept =
+                convert::byte_of_char(rest, rest_chars.saturating_sub(n));
+            self.forward_chunk =
+                &self.forward_chunk[..self.forward_byte_idx + kept];
+        }
+
+        self.next_back()
+    }
 }
 
 impl core::iter::FusedIterator for Chars<'_> {}
@@ -410,11 +737,27 @@ impl<'a> Iterator for RawLines<'a> {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, _) = self.units.next()?;
         self.lines_yielded += 1;
         Some(RopeSlice::from(tree_slice))
     }
 
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.lines_yielded = self.lines_total;
+            return None;
+        }
+
+        let (tree_slice, _) = self.units.nth(n)?;
+        self.lines_yielded += n + 1;
+        Some(RopeSlice::from(tree_slice))
+    }
+
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
         let exact = self.len();
@@ -425,10 +768,26 @@ impl<'a> Iterator for RawLines<'a> {
 impl DoubleEndedIterator for RawLines<'_> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, _) = self.units.next_back()?;
         self.lines_yielded += 1;
         Some(RopeSlice::from(tree_slice))
     }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.lines_yielded = self.lines_total;
+            return None;
+        }
+
+        let (tree_slice, _) = self.units.nth_back(n)?;
+        self.lines_yielded += n + 1;
+        Some(RopeSlice::from(tree_slice))
+    }
 }
 
 impl ExactSizeIterator for RawLines<'_> {
@@ -478,14 +837,13 @@ impl<'a> From<&RopeSlice<'a>> for Lines<'a> {
     }
 }
 
-impl<'a> Iterator for Lines<'a> {
-    type Item = RopeSlice<'a>;
-
+impl<'a> Lines<'a> {
+    /// Turns a `TreeSlice` yielded by the `LineMetric` units into a line.
     #[inline]
-    fn next(&mut self) -> Option<Self::Item> {
-        let (tree_slice, ByteMetric(advance)) = self.units.next()?;
-        self.lines_yielded += 1;
-
+    fn line(
+        tree_slice: TreeSlice<'a, { Rope::arity() }, RopeChunk>,
+        advance: usize,
+    ) -> RopeSlice<'a> {
         let mut slice = RopeSlice { tree_slice, has_trailing_newline: false };
 
         // This handles CRLF pairs that have been split across chunks. For
@@ -498,7 +856,34 @@ impl<'a> Iterator for Lines<'a> {
             slice.truncate_last_char();
         }
 
-        Some(slice)
+        slice
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
+        let (tree_slice, ByteMetric(advance)) = self.units.next()?;
+        self.lines_yielded += 1;
+        Some(Self::line(tree_slice, advance))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.lines_yielded = self.lines_total;
+            return None;
+        }
+
+        let (tree_slice, ByteMetric(advance)) = self.units.nth(n)?;
+        self.lines_yielded += n + 1;
+        Some(Self::line(tree_slice, advance))
     }
 
     #[inline]
@@ -511,19 +896,25 @@ impl<'a> Iterator for Lines<'a> {
 impl DoubleEndedIterator for Lines<'_> {
     #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.lines_yielded == self.lines_total {
+            return None;
+        }
+
         let (tree_slice, ByteMetric(advance)) = self.units.next_back()?;
         self.lines_yielded += 1;
+        Some(Self::line(tree_slice, advance))
+    }
 
-        let mut slice = RopeSlice { tree_slice, has_trailing_newline: false };
-
-        // Same as above.
-        if slice.tree_slice.end_slice().last_chunk().ends_with('\r')
-            && advance - slice.byte_len() == 1
-        {
-            slice.truncate_last_char();
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.lines_yielded = self.lines_total;
+            return None;
         }
 
-        Some(slice)
+        let (tree_slice, ByteMetric(advance)) = self.units.nth_back(n)?;
+        self.lines_yielded += n + 1;
+        Some(Self::line(tree_slice, advance))
     }
 }
 
@@ -536,6 +927,778 @@ impl ExactSizeIterator for Lines<'_> {
 
 impl core::iter::FusedIterator for Lines<'_> {}
 
+/// An iterator over the byte offsets of the non-overlapping matches of a
+/// [`Pattern`] in `Rope`s and `RopeSlice`s, from front to back.
+///
+/// This struct is created by the `find_iter` method on
+/// [`Rope`](Rope::find_iter()) and [`RopeSlice`](RopeSlice::find_iter()).
+/// See their documentation for more.
+#[derive(Clone)]
+pub struct FindIter<'a, P> {
+    pattern: P,
+
+    chunks: Chunks<'a>,
+
+    /// The chunk we're currently searching in.
+    chunk: &'a str,
+
+    /// The byte offset of `chunk`.
+    chunk_offset: usize,
+
+    /// The byte offset the next match can start at.
+    search_from: usize,
+
+    /// The end of the text preceding `chunk`, truncated to the last
+    /// [`Pattern::spanning_len()`] - 1 bytes. Matches spanning chunk
+    /// boundaries have to start in here.
+    carry: String,
+
+    /// A scratch buffer used to search for matches spanning chunk boundaries.
+    buffer: String,
+
+    /// Whether we still have to look for a match starting in `carry` and
+    /// ending in `chunk`.
+    check_boundary: bool,
+
+    /// The byte length of the `Rope` or `RopeSlice`.
+    byte_len: usize,
+
+    finished: bool,
+}
+
+impl<'a, P: Pattern> FindIter<'a, P> {
+    #[inline]
+    pub(super) fn new(slice: RopeSlice<'a>, pattern: P) -> Self {
+        Self {
+            pattern,
+            chunks: slice.chunks(),
+            chunk: "",
+            chunk_offset: 0,
+            search_from: 0,
+            carry: String::new(),
+            buffer: String::new(),
+            check_boundary: false,
+            byte_len: slice.byte_len(),
+            finished: false,
+        }
+    }
+
+    /// Looks for a match starting in `carry` and ending in `chunk`.
+    #[inline]
+    fn boundary_match(&mut self) -> Option<usize> {
+        let carry_offset = self.chunk_offset - self.carry.len();
+
+        let start = self.search_from.saturating_sub(carry_offset);
+
+        if start >= self.carry.len() {
+            return None;
+        }
+
+        let prefix_len = adjust_split_point::<false>(
+            self.chunk,
+            self.pattern.spanning_len() - 1,
+        );
+
+        self.buffer.clear();
+        self.buffer.push_str(&self.carry[start..]);
+        self.buffer.push_str(&self.chunk[..prefix_len]);
+
+        let range = self.pattern.first_match(&self.buffer)?;
+
+        if start + range.start < self.carry.len() {
+            self.search_from = carry_offset + start + range.end;
+            Some(carry_offset + start + range.start)
+        } else {
+            None
+        }
+    }
+
+    /// Moves to the next chunk, returning `false` if there isn't one.
+    #[inline]
+    fn next_chunk(&mut self) -> bool {
+        let Some(next) = self.chunks.next() else { return false };
+
+        let carry_len = self.pattern.spanning_len().saturating_sub(1);
+
+        if carry_len > 0 {
+            self.carry.push_str(self.chunk);
+
+            if self.carry.len() > carry_len {
+                let cut = adjust_split_point::<true>(
+                    &self.carry,
+                    self.carry.len() - carry_len,
+                );
+                self.carry.drain(..cut);
+            }
+
+            self.check_boundary = !self.carry.is_empty();
+        }
+
+        self.chunk_offset += self.chunk.len();
+        self.chunk = next;
+        true
+    }
+}
+
+impl<P: Pattern> Iterator for FindIter<'_, P> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            if self.check_boundary {
+                self.check_boundary = false;
+
+                if let Some(offset) = self.boundary_match() {
+                    return Some(offset);
+                }
+            }
+
+            let chunk_end = self.chunk_offset + self.chunk.len();
+
+            let start = self.search_from.saturating_sub(self.chunk_offset);
+
+            if let Some(range) = (start <= self.chunk.len())
+                .then(|| self.pattern.first_match(&self.chunk[start..]))
+                .flatten()
+            {
+                let match_start = self.chunk_offset + start + range.start;
+                let match_end = self.chunk_offset + start + range.end;
+
+                if match_start < match_end {
+                    self.search_from = match_end;
+                    return Some(match_start);
+                }
+
+                // Empty matches at the end of a chunk are yielded as the
+                // start of the next one, unless it's the last chunk.
+                if match_start < chunk_end || chunk_end == self.byte_len {
+                    match self.chunk[match_start - self.chunk_offset..]
+                        .chars()
+                        .next()
+                    {
+                        Some(ch) => self.search_from += ch.len_utf8(),
+                        None => self.finished = true,
+                    }
+                    return Some(match_start);
+                }
+            }
+
+            if !self.next_chunk() {
+                self.finished = true;
+                return None;
+            }
+        }
+    }
+}
+
+impl<P: Pattern> core::iter::FusedIterator for FindIter<'_, P> {}
+
+/// An iterator over the byte offsets of the non-overlapping matches of a
+/// [`Pattern`] in `Rope`s and `RopeSlice`s, from back to front.
+///
+/// This struct is created by the `rfind_iter` method on
+/// [`Rope`](Rope::rfind_iter()) and [`RopeSlice`](RopeSlice::rfind_iter()).
+/// See their documentation for more.
+#[derive(Clone)]
+pub struct RFindIter<'a, P> {
+    pattern: P,
+
+    chunks: Chunks<'a>,
+
+    /// The chunk we're currently searching in.
+    chunk: &'a str,
+
+    /// The byte offset of `chunk`.
+    chunk_offset: usize,
+
+    /// The byte offset the next match can end at.
+    search_to: usize,
+
+    /// The start of the text following `chunk`, truncated to the first
+    /// [`Pattern::spanning_len()`] - 1 bytes. Matches spanning chunk
+    /// boundaries have to end in here.
+    carry: String,
+
+    /// A scratch buffer used to search for matches spanning chunk boundaries.
+    buffer: String,
+
+    /// Whether we still have to look for a match starting in `chunk` and
+    /// ending in `carry`.
+    check_boundary: bool,
+
+    finished: bool,
+}
+
+impl<'a, P: Pattern> RFindIter<'a, P> {
+    #[inline]
+    pub(super) fn new(slice: RopeSlice<'a>, pattern: P) -> Self {
+        Self {
+            pattern,
+            chunks: slice.chunks(),
+            chunk: "",
+            chunk_offset: slice.byte_len(),
+            search_to: slice.byte_len(),
+            carry: String::new(),
+            buffer: String::new(),
+            check_boundary: false,
+            finished: false,
+        }
+    }
+
+    /// Looks for a match starting in `chunk` and ending in `carry`.
+    #[inline]
+    fn boundary_match(&mut self) -> Option<usize> {
+        let carry_offset = self.chunk_offset + self.chunk.len();
+
+        let end = core::cmp::min(
+            self.search_to.checked_sub(carry_offset)?,
+            self.carry.len(),
+        );
+
+        if end == 0 {
+            return None;
+        }
+
+        let suffix_start = adjust_split_point::<true>(
+            self.chunk,
+            self.chunk.len().saturating_sub(self.pattern.spanning_len() - 1),
+        );
+
+        let suffix_len = self.chunk.len() - suffix_start;
+
+        self.buffer.clear();
+        self.buffer.push_str(&self.chunk[suffix_start..]);
+        self.buffer.push_str(&self.carry[..end]);
+
+        let range = self.pattern.last_match(&self.buffer)?;
+
+        if range.end > suffix_len {
+            let match_start = self.chunk_offset + suffix_start + range.start;
+            self.search_to = match_start;
+            Some(match_start)
+        } else {
+            None
+        }
+    }
+
+    /// Moves to the previous chunk, returning `false` if there isn't one.
+    #[inline]
+    fn previous_chunk(&mut self) -> bool {
+        let Some(previous) = self.chunks.next_back() else { return false };
+
+        let carry_len = self.pattern.spanning_len().saturating_sub(1);
+
+        if carry_len > 0 {
+            self.carry.insert_str(0, self.chunk);
+
+            if self.carry.len() > carry_len {
+                let cut = adjust_split_point::<false>(&self.carry, carry_len);
+                self.carry.truncate(cut);
+            }
+
+            self.check_boundary = !self.carry.is_empty();
+        }
+
+        self.chunk_offset -= previous.len();
+        self.chunk = previous;
+        true
+    }
+}
+
+impl<P: Pattern> Iterator for RFindIter<'_, P> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            if self.check_boundary {
+                self.check_boundary = false;
+
+                if let Some(offset) = self.boundary_match() {
+                    return Some(offset);
+                }
+            }
+
+            let end = core::cmp::min(
+                self.search_to.saturating_sub(self.chunk_offset),
+                self.chunk.len(),
+            );
+
+            if let Some(range) = (self.search_to >= self.chunk_offset)
+                .then(|| self.pattern.last_match(&self.chunk[..end]))
+                .flatten()
+            {
+                let match_start = self.chunk_offset + range.start;
+
+                if range.start < range.end {
+                    self.search_to = match_start;
+                    return Some(match_start);
+                }
+
+                // Empty matches at the start of a chunk are yielded as the
+                // end of the previous one, unless it's the first chunk.
+                if range.start > 0 || self.chunk_offset == 0 {
+                    match self.chunk[..range.start].chars().next_back() {
+                        Some(ch) => self.search_to -= ch.len_utf8(),
+                        None => self.finished = true,
+                    }
+                    return Some(match_start);
+                }
+            }
+
+            if !self.previous_chunk() {
+                self.finished = true;
+                return None;
+            }
+        }
+    }
+}
+
+impl<P: Pattern> core::iter::FusedIterator for RFindIter<'_, P> {}
+
+/// The position of the `*At` iterators over bytes, chars and chunks: a
+/// [`Cursor`] in the current chunk, together with the byte offset of the
+/// position in it.
+#[derive(Clone)]
+struct ChunkPosition<'a> {
+    /// A cursor in [`chunk`](Self::chunk).
+    cursor: Cursor<'a>,
+
+    /// The current chunk.
+    chunk: &'a str,
+
+    /// The byte offset of the position in [`chunk`](Self::chunk).
+    byte_idx: usize,
+
+    /// The byte offset of the position in the slice.
+    byte_offset: usize,
+
+    /// The byte length of the slice.
+    byte_len: usize,
+}
+
+impl<'a> ChunkPosition<'a> {
+    #[track_caller]
+    #[inline]
+    fn new(slice: RopeSlice<'a>, byte_offset: usize) -> Self {
+        let mut cursor = slice.cursor();
+        cursor.seek_to_byte(byte_offset);
+
+        Self {
+            chunk: cursor.chunk(),
+            byte_idx: byte_offset - cursor.chunk_byte_offset(),
+            cursor,
+            byte_offset,
+            byte_len: slice.byte_len(),
+        }
+    }
+
+    /// Returns the current chunk and the byte offset of the position in it,
+    /// first moving to the start of the next chunk if the position is at the
+    /// end of the current one. Returns `None` if the position is at the end
+    /// of the slice.
+    #[inline]
+    fn after(&mut self) -> Option<(&'a str, usize)> {
+        if self.byte_idx == self.chunk.len() {
+            self.chunk = self.cursor.next_chunk()?;
+            self.byte_idx = 0;
+        }
+        Some((self.chunk, self.byte_idx))
+    }
+
+    /// Returns the current chunk and the byte offset of the position in it,
+    /// first moving to the end of the previous chunk if the position is at
+    /// the start of the current one. Returns `None` if the position is at the
+    /// start of the slice.
+    #[inline]
+    fn before(&mut self) -> Option<(&'a str, usize)> {
+        if self.byte_idx == 0 {
+            self.chunk = self.cursor.previous_chunk()?;
+            self.byte_idx = self.chunk.len();
+        }
+        Some((self.chunk, self.byte_idx))
+    }
+
+    /// Moves the position forward by `bytes` bytes within the current chunk.
+    #[inline]
+    fn advance(&mut self, bytes: usize) {
+        self.byte_idx += bytes;
+        self.byte_offset += bytes;
+    }
+
+    /// Moves the position back by `bytes` bytes within the current chunk.
+    #[inline]
+    fn retreat(&mut self, bytes: usize) {
+        self.byte_idx -= bytes;
+        self.byte_offset -= bytes;
+    }
+
+    /// Returns the smallest of the byte lengths of the text before and after
+    /// the position.
+    #[inline]
+    fn min_bytes_left(&self) -> usize {
+        core::cmp::min(self.byte_offset, self.byte_len - self.byte_offset)
+    }
+}
+
+/// An iterator over the bytes of `Rope`s and `RopeSlice`s starting at an
+/// arbitrary byte offset.
+///
+/// Unlike [`Bytes`], the two ends of this iterator don't move towards each
+/// other: both [`next()`](Iterator::next()) and
+/// [`next_back()`](DoubleEndedIterator::next_back()) move the same position,
+/// the former yielding the byte after it and the latter the byte before it.
+/// It follows that [`rev()`](Iterator::rev()) turns it into an iterator
+/// walking backward from the starting offset, and that calling `next_back()`
+/// right after `next()` yields the same byte again.
+///
+/// Since the number of items it yields depends on how the two methods are
+/// interleaved, the lower bound returned by
+/// [`size_hint()`](Iterator::size_hint()) is the number of bytes left in the
+/// shortest direction, and there's no upper bound.
+///
+/// This struct is created by the `bytes_at` method on
+/// [`Rope`](Rope::bytes_at()) and [`RopeSlice`](RopeSlice::bytes_at()). See
+/// their documentation for more.
+#[derive(Clone)]
+pub struct BytesAt<'a> {
+    position: ChunkPosition<'a>,
+}
+
+impl<'a> BytesAt<'a> {
+    #[track_caller]
+    #[inline]
+    pub(super) fn new(slice: RopeSlice<'a>, byte_offset: usize) -> Self {
+        Self { position: ChunkPosition::new(slice, byte_offset) }
+    }
+
+    /// Returns the byte offset of the iterator's position, i.e. the offset of
+    /// the byte that'll be yielded by the next call to
+    /// [`next()`](Iterator::next()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo");
+    ///
+    /// let mut bytes = r.bytes_at(1);
+    /// assert_eq!(bytes.byte_offset(), 1);
+    ///
+    /// bytes.next_back();
+    /// assert_eq!(bytes.byte_offset(), 0);
+    /// ```
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.position.byte_offset
+    }
+}
+
+impl Iterator for BytesAt<'_> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (chunk, byte_idx) = self.position.after()?;
+        self.position.advance(1);
+        Some(chunk.as_bytes()[byte_idx])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.position.min_bytes_left(), None)
+    }
+}
+
+impl DoubleEndedIterator for BytesAt<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (chunk, byte_idx) = self.position.before()?;
+        self.position.retreat(1);
+        Some(chunk.as_bytes()[byte_idx - 1])
+    }
+}
+
+/// An iterator over the [`char`]s of `Rope`s and `RopeSlice`s starting at an
+/// arbitrary byte offset.
+///
+/// Like [`BytesAt`], both [`next()`](Iterator::next()) and
+/// [`next_back()`](DoubleEndedIterator::next_back()) move the same position,
+/// the former yielding the char after it and the latter the char before it.
+///
+/// This struct is created by the `chars_at` method on
+/// [`Rope`](Rope::chars_at()) and [`RopeSlice`](RopeSlice::chars_at()). See
+/// their documentation for more.
+#[derive(Clone)]
+pub struct CharsAt<'a> {
+    position: ChunkPosition<'a>,
+}
+
+impl<'a> CharsAt<'a> {
+    #[track_caller]
+    #[inline]
+    pub(super) fn new(slice: RopeSlice<'a>, byte_offset: usize) -> Self {
+        Self { position: ChunkPosition::new(slice, byte_offset) }
+    }
+
+    /// Returns the byte offset of the iterator's position, i.e. the offset of
+    /// the char that'll be yielded by the next call to
+    /// [`next()`](Iterator::next()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🐸a");
+    ///
+    /// let mut chars = r.chars_at(0);
+    /// chars.next();
+    /// assert_eq!(chars.byte_offset(), 4);
+    /// ```
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.position.byte_offset
+    }
+}
+
+impl Iterator for CharsAt<'_> {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (chunk, byte_idx) = self.position.after()?;
+        let ch = chunk[byte_idx..].chars().next()?;
+        self.position.advance(ch.len_utf8());
+        Some(ch)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // A char is at most 4 bytes long.
+        ((self.position.min_bytes_left() + 3) / 4, None)
+    }
+}
+
+impl DoubleEndedIterator for CharsAt<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (chunk, byte_idx) = self.position.before()?;
+        let ch = chunk[..byte_idx].chars().next_back()?;
+        self.position.retreat(ch.len_utf8());
+        Some(ch)
+    }
+}
+
+/// An iterator over the `&str` chunks of `Rope`s and `RopeSlice`s starting at
+/// an arbitrary byte offset.
+///
+/// Like [`BytesAt`], both [`next()`](Iterator::next()) and
+/// [`next_back()`](DoubleEndedIterator::next_back()) move the same position,
+/// the former yielding the text from it to the end of its chunk and the
+/// latter the text from the start of its chunk to it. The chunk containing
+/// the starting offset is split in two, so the first chunk yielded in either
+/// direction might be shorter than the ones yielded by [`Chunks`].
+///
+/// This struct is created by the `chunks_at` method on
+/// [`Rope`](Rope::chunks_at()) and [`RopeSlice`](RopeSlice::chunks_at()).
+/// See their documentation for more.
+#[derive(Clone)]
+pub struct ChunksAt<'a> {
+    position: ChunkPosition<'a>,
+}
+
+impl<'a> ChunksAt<'a> {
+    #[track_caller]
+    #[inline]
+    pub(super) fn new(slice: RopeSlice<'a>, byte_offset: usize) -> Self {
+        Self { position: ChunkPosition::new(slice, byte_offset) }
+    }
+
+    /// Returns the byte offset of the iterator's position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// let mut chunks = r.chunks_at(2);
+    /// assert_eq!(chunks.by_ref().collect::<String>(), "o\nbar");
+    /// assert_eq!(chunks.byte_offset(), r.byte_len());
+    /// ```
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.position.byte_offset
+    }
+}
+
+impl<'a> Iterator for ChunksAt<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (chunk, byte_idx) = self.position.after()?;
+        self.position.advance(chunk.len() - byte_idx);
+        Some(&chunk[byte_idx..])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        ((self.position.min_bytes_left() > 0) as usize, None)
+    }
+}
+
+impl DoubleEndedIterator for ChunksAt<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (chunk, byte_idx) = self.position.before()?;
+        self.position.retreat(byte_idx);
+        Some(&chunk[..byte_idx])
+    }
+}
+
+/// An iterator over the lines of `Rope`s and `RopeSlice`s, not including the
+/// line terminators, starting at an arbitrary line offset.
+///
+/// Like [`BytesAt`], both [`next()`](Iterator::next()) and
+/// [`next_back()`](DoubleEndedIterator::next_back()) move the same position,
+/// which is always at the start of a line: the former yields the line
+/// starting at it and the latter the line ending at it.
+///
+/// This struct is created by the `lines_at` method on
+/// [`Rope`](Rope::lines_at()) and [`RopeSlice`](RopeSlice::lines_at()). See
+/// their documentation for more.
+#[derive(Clone)]
+pub struct LinesAt<'a> {
+    /// The slice we're iterating over.
+    slice: RopeSlice<'a>,
+
+    /// A cursor at the start of the `line_offset`-th line, or at the end of
+    /// the slice if `line_offset` is equal to `line_len`.
+    cursor: Cursor<'a>,
+
+    /// The line offset of the iterator's position.
+    line_offset: usize,
+
+    /// The number of lines in the slice.
+    line_len: usize,
+
+    /// The number of line breaks in the slice, i.e. the number of lines
+    /// which have a line terminator.
+    line_breaks: usize,
+}
+
+impl<'a> LinesAt<'a> {
+    #[track_caller]
+    #[inline]
+    pub(super) fn new(slice: RopeSlice<'a>, line_offset: usize) -> Self {
+        let mut cursor = slice.cursor();
+        cursor.seek_to_line(line_offset);
+
+        Self {
+            slice,
+            cursor,
+            line_offset,
+            line_len: slice.line_len(),
+            line_breaks: slice.tree_slice.summary().line_breaks(),
+        }
+    }
+
+    /// Returns the `line_offset`-th line, which spans the given byte range
+    /// (including its line terminator, if it has one).
+    #[inline]
+    fn line(
+        &self,
+        line_offset: usize,
+        start: usize,
+        end: usize,
+    ) -> RopeSlice<'a> {
+        let tree_slice = self.slice.byte_slice(start..end).tree_slice;
+
+        let mut line = RopeSlice { tree_slice, has_trailing_newline: false };
+
+        if line_offset < self.line_breaks {
+            line.truncate_trailing_line_break();
+        }
+
+        line
+    }
+
+    /// Returns the line offset of the iterator's position, i.e. the offset of
+    /// the line that'll be yielded by the next call to
+    /// [`next()`](Iterator::next()).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\nbaz");
+    ///
+    /// let mut lines = r.lines_at(2);
+    /// lines.next_back();
+    /// assert_eq!(lines.line_offset(), 1);
+    /// ```
+    #[inline]
+    pub fn line_offset(&self) -> usize {
+        self.line_offset
+    }
+}
+
+impl<'a> Iterator for LinesAt<'a> {
+    type Item = RopeSlice<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.line_offset == self.line_len {
+            return None;
+        }
+
+        let start = self.cursor.byte_offset();
+
+        if !self.cursor.next_line() {
+            self.cursor.seek_to_byte(self.slice.byte_len());
+        }
+
+        let end = self.cursor.byte_offset();
+        let line = self.line(self.line_offset, start, end);
+        self.line_offset += 1;
+        Some(line)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let after = self.line_len - self.line_offset;
+        (core::cmp::min(self.line_offset, after), None)
+    }
+}
+
+impl DoubleEndedIterator for LinesAt<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.line_offset == 0 {
+            return None;
+        }
+
+        let end = self.cursor.byte_offset();
+        self.line_offset -= 1;
+        self.cursor.seek_to_line(self.line_offset);
+        Some(self.line(self.line_offset, self.cursor.byte_offset(), end))
+    }
+}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "graphemes")))]
 #[cfg(feature = "graphemes")]
 pub use graphemes::Graphemes;
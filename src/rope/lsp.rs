@@ -0,0 +1,254 @@
+#[cfg(feature = "utf16-metric")]
+use alloc::string::String;
+#[cfg(feature = "utf16-metric")]
+use core::ops::Range;
+
+use super::utils::panic_messages as panic;
+#[cfg(feature = "utf16-metric")]
+use super::Rope;
+use super::RopeSlice;
+
+/// The unit in which the columns of positions are measured.
+///
+/// This corresponds to the `positionEncoding` negotiated between a client
+/// and a server of the [Language Server
+/// Protocol](https://microsoft.github.io/language-server-protocol/), which
+/// measures the `character` offset of a `Position` from the start of its
+/// line.
+///
+/// It's used by the `byte_of_position` and `position_of_byte` methods on
+/// [`Rope`](crate::Rope::byte_of_position()) and
+/// [`RopeSlice`](crate::RopeSlice::byte_of_position()).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// Columns are measured in bytes (`"utf-8"`).
+    Utf8,
+
+    /// Columns are measured in UTF-16 code units (`"utf-16"`). This is the
+    /// encoding clients and servers have to support, and the one used when
+    /// no other encoding was negotiated.
+    ///
+    /// The conversions take logarithmic time if the `utf16-metric` feature is
+    /// enabled, and time linear in the length of the line otherwise.
+    #[default]
+    Utf16,
+
+    /// Columns are measured in [`char`]s, i.e. in Unicode code points
+    /// (`"utf-32"`).
+    ///
+    /// The conversions take logarithmic time if the `char-metric` feature is
+    /// enabled, and time linear in the length of the line otherwise.
+    Utf32,
+}
+
+/// A change to the contents of a text document, like the ones sent by a
+/// Language Server Protocol client in a `TextDocumentContentChangeEvent`.
+///
+/// It's used by [`Rope::apply_lsp_changes()`].
+#[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+#[cfg(feature = "utf16-metric")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContentChange {
+    /// The `(line, character)` positions of the start and end of the range
+    /// to replace, where characters are measured in UTF-16 code units, or
+    /// `None` to replace the whole document.
+    pub range: Option<Range<(usize, usize)>>,
+
+    /// The new text of the range.
+    pub text: String,
+}
+
+/// An error returned by [`Rope::apply_lsp_changes()`].
+#[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+#[cfg(feature = "utf16-metric")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ContentChangeError {
+    /// The start of the range of a change comes after its end.
+    StartAfterEnd {
+        /// The index of the change in the slice of changes.
+        change_idx: usize,
+
+        /// The range of the change.
+        range: Range<(usize, usize)>,
+    },
+}
+
+#[cfg(feature = "utf16-metric")]
+impl core::fmt::Display for ContentChangeError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::StartAfterEnd { change_idx, range } => write!(
+                f,
+                "the start of change #{} ({}:{}) is after its end ({}:{})",
+                change_idx + 1,
+                range.start.0 + 1,
+                range.start.1 + 1,
+                range.end.0 + 1,
+                range.end.1 + 1
+            ),
+        }
+    }
+}
+
+#[cfg(all(feature = "std", feature = "utf16-metric"))]
+impl std::error::Error for ContentChangeError {}
+
+/// See [`Rope::apply_lsp_changes()`] for the docs.
+#[cfg(feature = "utf16-metric")]
+#[inline]
+pub(super) fn apply_changes(
+    rope: &mut Rope,
+    changes: &[ContentChange],
+) -> Result<(), ContentChangeError> {
+    // Cloning is cheap, and it lets us leave the rope untouched if one of
+    // the changes is invalid.
+    let mut new = rope.clone();
+
+    for (change_idx, change) in changes.iter().enumerate() {
+        let Some(range) = &change.range else {
+            new = Rope::from(change.text.as_str());
+            continue;
+        };
+
+        if range.start > range.end {
+            return Err(ContentChangeError::StartAfterEnd {
+                change_idx,
+                range: range.clone(),
+            });
+        }
+
+        // Positions past the last line are clamped to the end of the
+        // document, so ordered positions give ordered byte offsets.
+        let (start, end) = (range.start, range.end);
+
+        let start = new.byte_of_position(start.0, start.1, Encoding::Utf16);
+        let end = new.byte_of_position(end.0, end.1, Encoding::Utf16);
+
+        new.replace(start..end, &change.text);
+    }
+
+    *rope = new;
+
+    Ok(())
+}
+
+/// See [`RopeSlice::byte_of_position()`] for the docs.
+#[inline]
+pub(super) fn byte_of_position(
+    slice: RopeSlice<'_>,
+    line_offset: usize,
+    column: usize,
+    encoding: Encoding,
+) -> usize {
+    // Positions past the last line are clamped to the end of the text.
+    if line_offset >= slice.line_len() {
+        return slice.byte_len();
+    }
+
+    let line_start = slice.byte_of_line(line_offset);
+
+    line_start + byte_of_column(slice.line(line_offset), column, encoding)
+}
+
+/// See [`RopeSlice::position_of_byte()`] for the docs.
+#[track_caller]
+#[inline]
+pub(super) fn position_of_byte(
+    slice: RopeSlice<'_>,
+    byte_offset: usize,
+    encoding: Encoding,
+) -> (usize, usize) {
+    if byte_offset > slice.byte_len() {
+        panic::byte_offset_out_of_bounds(byte_offset, slice.byte_len());
+    }
+
+    let line_offset = slice.line_of_byte(byte_offset);
+
+    let line_start = slice.byte_of_line(line_offset);
+
+    let column = len(slice.byte_slice(line_start..byte_offset), encoding);
+
+    (line_offset, column)
+}
+
+/// Returns the byte offset of the given column in the line, clamped to the
+/// length of the line. A column in the middle of a code point is rounded
+/// down to its start.
+#[inline]
+fn byte_of_column(
+    line: RopeSlice<'_>,
+    column: usize,
+    encoding: Encoding,
+) -> usize {
+    match encoding {
+        Encoding::Utf8 => {
+            let mut byte_offset = column.min(line.byte_len());
+            while !line.is_char_boundary(byte_offset) {
+                byte_offset -= 1;
+            }
+            byte_offset
+        },
+
+        // Offsets in the middle of a surrogate pair are converted to the
+        // start of the code point.
+        #[cfg(feature = "utf16-metric")]
+        Encoding::Utf16 => {
+            line.byte_of_utf16_code_unit(column.min(line.utf16_len()))
+        },
+
+        #[cfg(not(feature = "utf16-metric"))]
+        Encoding::Utf16 => byte_of_units(line, column, char::len_utf16),
+
+        #[cfg(feature = "char-metric")]
+        Encoding::Utf32 => line.byte_of_char(column.min(line.char_len())),
+
+        #[cfg(not(feature = "char-metric"))]
+        Encoding::Utf32 => byte_of_units(line, column, |_| 1),
+    }
+}
+
+/// Returns the length of the text in the given encoding.
+#[inline]
+fn len(text: RopeSlice<'_>, encoding: Encoding) -> usize {
+    match encoding {
+        Encoding::Utf8 => text.byte_len(),
+
+        #[cfg(feature = "utf16-metric")]
+        Encoding::Utf16 => text.utf16_len(),
+
+        #[cfg(not(feature = "utf16-metric"))]
+        Encoding::Utf16 => text.chars().map(char::len_utf16).sum(),
+
+        #[cfg(feature = "char-metric")]
+        Encoding::Utf32 => text.char_len(),
+
+        #[cfg(not(feature = "char-metric"))]
+        Encoding::Utf32 => text.chars().count(),
+    }
+}
+
+/// Returns the byte offset of the given column in the line by walking over
+/// its chars, where `units` returns the number of units taken by a char.
+#[cfg(not(all(feature = "utf16-metric", feature = "char-metric")))]
+#[inline]
+fn byte_of_units(
+    line: RopeSlice<'_>,
+    column: usize,
+    units: impl Fn(char) -> usize,
+) -> usize {
+    let mut byte_offset = 0;
+    let mut units_offset = 0;
+
+    for ch in line.chars() {
+        units_offset += units(ch);
+
+        if units_offset > column {
+            break;
+        }
+
+        byte_offset += ch.len_utf8();
+    }
+
+    byte_offset
+}
@@ -0,0 +1,335 @@
+use alloc::vec::Vec;
+use core::iter::Peekable;
+use core::ops::Range;
+use core::slice;
+
+use super::diff::{Hunk, LineDiff};
+use super::{Rope, RopeBuilder, RopeSlice};
+
+/// Merges the changes made to `base` in `ours` and in `theirs`.
+///
+/// Both texts are diffed line by line against their common ancestor, and the
+/// changes made by only one of them are applied automatically. Changes made
+/// by both sides to the same or to adjacent lines result in a conflict,
+/// unless they're identical. Like in [`LineDiff`], changing the line break
+/// of a line (e.g. from `\n` to `\r\n`) is a change to that line.
+///
+/// The merged [`Rope`] is built on top of `ours`, so its unchanged lines and
+/// its conflicting regions contain the text of `ours`. The conflicts can be
+/// inspected via [`MergeResult::conflicts()`] or rendered as conflict markers
+/// via [`MergeResult::render_conflicts()`].
+///
+/// # Examples
+///
+/// ```
+/// # use crop::merge::merge3;
+/// # use crop::Rope;
+/// #
+/// let base = Rope::from("a\nb\nc\nd\ne\n");
+/// let ours = Rope::from("a\nB\nc\nd\ne\n");
+/// let theirs = Rope::from("a\nb\nc\nd\nE\n");
+///
+/// let merge = merge3(&base, &ours, &theirs);
+/// assert!(merge.is_clean());
+/// assert_eq!(merge.rope(), "a\nB\nc\nd\nE\n");
+///
+/// let theirs = Rope::from("a\nβ\nc\nd\ne\n");
+///
+/// let merge = merge3(&base, &ours, &theirs);
+/// assert_eq!(merge.conflicts().len(), 1);
+/// assert_eq!(merge.conflicts()[0].theirs, 2..5);
+/// assert_eq!(merge.conflicts()[0].merged, 2..4);
+///
+/// assert_eq!(
+///     merge.render_conflicts("ours", "theirs"),
+///     "a\n<<<<<<< ours\nB\n=======\nβ\n>>>>>>> theirs\nc\nd\ne\n"
+/// );
+/// ```
+#[inline]
+pub fn merge3<'a, B, O, T>(base: B, ours: O, theirs: T) -> MergeResult<'a>
+where
+    B: Into<RopeSlice<'a>>,
+    O: Into<RopeSlice<'a>>,
+    T: Into<RopeSlice<'a>>,
+{
+    let (base, ours, theirs) = (base.into(), ours.into(), theirs.into());
+
+    let ours_diff = LineDiff::new(base, ours);
+    let theirs_diff = LineDiff::new(base, theirs);
+
+    let mut ours_side = Side::new(ours, ours_diff.hunks());
+    let mut theirs_side = Side::new(theirs, theirs_diff.hunks());
+
+    let mut builder = RopeBuilder::new();
+
+    let mut merged_len = 0;
+
+    let mut conflicts = Vec::new();
+
+    // The first base line after the last group of changes.
+    let mut base_line = 0;
+
+    loop {
+        // The next group of changes is the smallest range of base lines
+        // containing the next hunk of either side and all the hunks touching
+        // it.
+
+        let group_start = match (ours_side.peek(), theirs_side.peek()) {
+            (Some(o), Some(t)) => o.old_lines.start.min(t.old_lines.start),
+            (Some(h), None) | (None, Some(h)) => h.old_lines.start,
+            (None, None) => break,
+        };
+
+        let mut group_end = group_start;
+
+        let (mut ours_changed, mut theirs_changed) = (false, false);
+
+        loop {
+            if let Some(end) = ours_side.take_touching(group_end) {
+                group_end = group_end.max(end);
+                ours_changed = true;
+            } else if let Some(end) = theirs_side.take_touching(group_end) {
+                group_end = group_end.max(end);
+                theirs_changed = true;
+            } else {
+                break;
+            }
+        }
+
+        // The unchanged lines before the group,
+        let unchanged = ours_side.unchanged_lines(base_line..group_start);
+        let unchanged = ours_side.slice(unchanged);
+        merged_len += append(&mut builder, unchanged);
+
+        // and the group itself.
+
+        let group = group_start..group_end;
+
+        let ours_bytes = ours_side.byte_range(ours_side.lines_of(&group));
+
+        let theirs_bytes =
+            theirs_side.byte_range(theirs_side.lines_of(&group));
+
+        let ours_group = ours.byte_slice(ours_bytes.clone());
+        let theirs_group = theirs.byte_slice(theirs_bytes.clone());
+
+        if !theirs_changed || (ours_changed && ours_group == theirs_group) {
+            merged_len += append(&mut builder, ours_group);
+        } else if !ours_changed {
+            merged_len += append(&mut builder, theirs_group);
+        } else {
+            let start = merged_len;
+
+            merged_len += append(&mut builder, ours_group);
+
+            conflicts.push(Conflict {
+                base: base.byte_of_line(group.start)
+                    ..base.byte_of_line(group.end),
+                ours: ours_bytes,
+                theirs: theirs_bytes,
+                merged: start..merged_len,
+            });
+        }
+
+        ours_side.end_group();
+        theirs_side.end_group();
+
+        base_line = group_end;
+    }
+
+    let rest = ours_side.unchanged_lines(base_line..base.line_len());
+    append(&mut builder, ours_side.slice(rest));
+
+    MergeResult { theirs, merged: builder.build(), conflicts }
+}
+
+/// Appends the slice to the builder, returning its byte length.
+#[inline]
+fn append(builder: &mut RopeBuilder, slice: RopeSlice<'_>) -> usize {
+    for chunk in slice.chunks() {
+        builder.append(chunk);
+    }
+    slice.byte_len()
+}
+
+/// One side of a three-way merge, together with the hunks of its diff with
+/// the base text.
+struct Side<'a, 'h> {
+    text: RopeSlice<'a>,
+
+    hunks: Peekable<slice::Iter<'h, Hunk>>,
+
+    /// How many more lines this side has compared to the base before the
+    /// current group of changes.
+    delta: isize,
+
+    /// Same as `delta`, but also counting the hunks taken in the current
+    /// group.
+    group_delta: isize,
+}
+
+impl<'a, 'h> Side<'a, 'h> {
+    #[inline]
+    fn new(text: RopeSlice<'a>, hunks: &'h [Hunk]) -> Self {
+        Self { text, hunks: hunks.iter().peekable(), delta: 0, group_delta: 0 }
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<&'h Hunk> {
+        self.hunks.peek().copied()
+    }
+
+    /// Takes the next hunk if it starts at or before the given base line,
+    /// returning the base line where it ends.
+    #[inline]
+    fn take_touching(&mut self, base_line: usize) -> Option<usize> {
+        let hunk = self.hunks.next_if(|h| h.old_lines.start <= base_line)?;
+        self.group_delta +=
+            hunk.new_lines.len() as isize - hunk.old_lines.len() as isize;
+        Some(hunk.old_lines.end)
+    }
+
+    /// Returns the lines of this side corresponding to the given range of
+    /// base lines, which must come before the current group of changes and
+    /// after the previous one.
+    #[inline]
+    fn unchanged_lines(&self, base_lines: Range<usize>) -> Range<usize> {
+        let start = (base_lines.start as isize + self.delta) as usize;
+        let end = (base_lines.end as isize + self.delta) as usize;
+        start..end
+    }
+
+    /// Returns the lines of this side corresponding to the given group of base
+    /// lines.
+    #[inline]
+    fn lines_of(&self, group: &Range<usize>) -> Range<usize> {
+        let start = (group.start as isize + self.delta) as usize;
+        let end = (group.end as isize + self.group_delta) as usize;
+        start..end
+    }
+
+    #[inline]
+    fn byte_range(&self, lines: Range<usize>) -> Range<usize> {
+        self.text.byte_of_line(lines.start)..self.text.byte_of_line(lines.end)
+    }
+
+    #[inline]
+    fn slice(&self, lines: Range<usize>) -> RopeSlice<'a> {
+        self.text.byte_slice(self.byte_range(lines))
+    }
+
+    #[inline]
+    fn end_group(&mut self) {
+        self.delta = self.group_delta;
+    }
+}
+
+/// The result of a three-way merge.
+///
+/// This struct is created by [`merge3()`]. See its documentation for more.
+#[derive(Debug, Clone)]
+pub struct MergeResult<'a> {
+    theirs: RopeSlice<'a>,
+    merged: Rope,
+    conflicts: Vec<Conflict>,
+}
+
+/// A region that was changed differently by both sides of a three-way merge.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// The byte range of the region in the base text.
+    pub base: Range<usize>,
+
+    /// The byte range of the region in our text.
+    pub ours: Range<usize>,
+
+    /// The byte range of the region in their text.
+    pub theirs: Range<usize>,
+
+    /// The byte range of the region in the merged `Rope`, where it contains
+    /// our version of it.
+    pub merged: Range<usize>,
+}
+
+impl MergeResult<'_> {
+    /// Returns the regions that couldn't be merged automatically, sorted by
+    /// their position.
+    #[inline]
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.conflicts
+    }
+
+    /// Returns the merged `Rope`, consuming `self`.
+    #[inline]
+    pub fn into_rope(self) -> Rope {
+        self.merged
+    }
+
+    /// Returns `true` if there aren't any conflicts.
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Returns a copy of the merged `Rope` where both versions of every
+    /// conflicting region are delimited by `git`-style conflict markers:
+    ///
+    /// ```text
+    /// <<<<<<< {ours_label}
+    /// our version
+    /// =======
+    /// their version
+    /// >>>>>>> {theirs_label}
+    /// ```
+    #[inline]
+    pub fn render_conflicts(
+        &self,
+        ours_label: &str,
+        theirs_label: &str,
+    ) -> Rope {
+        let mut builder = RopeBuilder::new();
+
+        // Appends a version of a conflicting region, making sure the marker
+        // after it starts on a new line.
+        let append_version = |builder: &mut RopeBuilder, slice: RopeSlice| {
+            append(builder, slice);
+            if !slice.is_empty() && slice.byte(slice.byte_len() - 1) != b'\n'
+            {
+                builder.append("\n");
+            }
+        };
+
+        let mut offset = 0;
+
+        for conflict in &self.conflicts {
+            let before = self.merged.byte_slice(offset..conflict.merged.start);
+            append(&mut builder, before);
+
+            builder.append("<<<<<<< ").append(ours_label).append("\n");
+            append_version(
+                &mut builder,
+                self.merged.byte_slice(conflict.merged.clone()),
+            );
+
+            builder.append("=======\n");
+            append_version(
+                &mut builder,
+                self.theirs.byte_slice(conflict.theirs.clone()),
+            );
+
+            builder.append(">>>>>>> ").append(theirs_label).append("\n");
+
+            offset = conflict.merged.end;
+        }
+
+        append(&mut builder, self.merged.byte_slice(offset..));
+
+        builder.build()
+    }
+
+    /// Returns the merged `Rope`.
+    #[inline]
+    pub fn rope(&self) -> &Rope {
+        &self.merged
+    }
+}
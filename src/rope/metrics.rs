@@ -9,6 +9,8 @@ use crate::tree::{DoubleEndedUnitMetric, Metric, SlicingMetric, UnitMetric};
 pub struct ChunkSummary {
     bytes: usize,
     line_breaks: usize,
+    #[cfg(feature = "char-metric")]
+    chars: usize,
     #[cfg(feature = "utf16-metric")]
     utf16_code_units: usize,
 }
@@ -19,6 +21,8 @@ impl From<&str> for ChunkSummary {
         Self {
             bytes: s.len(),
             line_breaks: count::line_breaks(s),
+            #[cfg(feature = "char-metric")]
+            chars: count::chars(s),
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: count::utf16_code_units(s),
         }
@@ -31,6 +35,8 @@ impl From<char> for ChunkSummary {
         Self {
             bytes: ch.len_utf8(),
             line_breaks: (ch == '\n') as usize,
+            #[cfg(feature = "char-metric")]
+            chars: 1,
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: ch.len_utf16(),
         }
@@ -43,6 +49,12 @@ impl ChunkSummary {
         self.bytes
     }
 
+    #[cfg(feature = "char-metric")]
+    #[inline]
+    pub fn chars(&self) -> usize {
+        self.chars
+    }
+
     #[inline]
     pub fn line_breaks(&self) -> usize {
         self.line_breaks
@@ -106,6 +118,10 @@ impl AddAssign<Self> for ChunkSummary {
     fn add_assign(&mut self, rhs: Self) {
         self.bytes += rhs.bytes;
         self.line_breaks += rhs.line_breaks;
+        #[cfg(feature = "char-metric")]
+        {
+            self.chars += rhs.chars;
+        }
         #[cfg(feature = "utf16-metric")]
         {
             self.utf16_code_units += rhs.utf16_code_units;
@@ -118,6 +134,10 @@ impl SubAssign<Self> for ChunkSummary {
     fn sub_assign(&mut self, rhs: Self) {
         self.bytes -= rhs.bytes;
         self.line_breaks -= rhs.line_breaks;
+        #[cfg(feature = "char-metric")]
+        {
+            self.chars -= rhs.chars;
+        }
         #[cfg(feature = "utf16-metric")]
         {
             self.utf16_code_units -= rhs.utf16_code_units;
@@ -237,6 +257,9 @@ impl SummaryUpTo for ByteMetric {
                 str_summary.line_breaks,
             ),
 
+            #[cfg(feature = "char-metric")]
+            chars: count::chars_up_to(in_str, byte_offset, str_summary.chars),
+
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: count::utf16_code_units_up_to(
                 in_str,
@@ -343,7 +366,10 @@ impl ToByteOffset for RawLineMetric {
 }
 
 impl SummaryUpTo for RawLineMetric {
-    #[cfg_attr(not(feature = "utf16-metric"), allow(unused_variables))]
+    #[cfg_attr(
+        not(any(feature = "char-metric", feature = "utf16-metric")),
+        allow(unused_variables)
+    )]
     #[inline]
     fn up_to(
         in_str: &str,
@@ -356,6 +382,9 @@ impl SummaryUpTo for RawLineMetric {
 
             line_breaks: line_offset,
 
+            #[cfg(feature = "char-metric")]
+            chars: count::chars_up_to(in_str, byte_offset, str_summary.chars),
+
             #[cfg(feature = "utf16-metric")]
             utf16_code_units: count::utf16_code_units_up_to(
                 in_str,
@@ -573,6 +602,132 @@ impl<const MAX_BYTES: usize> DoubleEndedUnitMetric<GapBuffer<MAX_BYTES>>
     }
 }
 
+#[cfg(feature = "char-metric")]
+pub use char_metric::CharMetric;
+
+#[cfg(feature = "char-metric")]
+mod char_metric {
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct CharMetric(pub usize);
+
+    impl Add<Self> for CharMetric {
+        type Output = Self;
+
+        #[inline]
+        fn add(self, other: Self) -> Self {
+            Self(self.0 + other.0)
+        }
+    }
+
+    impl Sub for CharMetric {
+        type Output = Self;
+
+        #[inline]
+        fn sub(self, other: Self) -> Self {
+            Self(self.0 - other.0)
+        }
+    }
+
+    impl AddAssign for CharMetric {
+        #[inline]
+        fn add_assign(&mut self, other: Self) {
+            self.0 += other.0
+        }
+    }
+
+    impl SubAssign for CharMetric {
+        #[inline]
+        fn sub_assign(&mut self, other: Self) {
+            self.0 -= other.0
+        }
+    }
+
+    impl ToByteOffset for CharMetric {
+        #[inline]
+        fn to_byte_offset(&self, in_str: &str) -> usize {
+            convert::byte_of_char(in_str, self.0)
+        }
+    }
+
+    impl SummaryUpTo for CharMetric {
+        #[inline]
+        fn up_to(
+            in_str: &str,
+            str_summary: ChunkSummary,
+            Self(char_offset): Self,
+            byte_offset: usize,
+        ) -> ChunkSummary {
+            ChunkSummary {
+                bytes: byte_offset,
+
+                line_breaks: count::line_breaks_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.line_breaks,
+                ),
+
+                chars: char_offset,
+
+                #[cfg(feature = "utf16-metric")]
+                utf16_code_units: count::utf16_code_units_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.utf16_code_units,
+                ),
+            }
+        }
+    }
+
+    impl Metric<ChunkSummary> for CharMetric {
+        #[inline]
+        fn zero() -> Self {
+            Self(0)
+        }
+
+        #[inline]
+        fn one() -> Self {
+            Self(1)
+        }
+
+        #[inline]
+        fn measure(summary: &ChunkSummary) -> Self {
+            Self(summary.chars)
+        }
+    }
+
+    impl<const MAX_BYTES: usize> SlicingMetric<GapBuffer<MAX_BYTES>>
+        for CharMetric
+    {
+        #[inline]
+        fn slice_up_to<'a>(
+            chunk: GapSlice<'a>,
+            char_offset: Self,
+            &summary: &ChunkSummary,
+        ) -> (GapSlice<'a>, ChunkSummary)
+        where
+            'a: 'a,
+        {
+            let (left, _) = chunk.split_at_offset(char_offset, summary);
+            left
+        }
+
+        #[inline]
+        fn slice_from<'a>(
+            chunk: GapSlice<'a>,
+            char_offset: Self,
+            &summary: &ChunkSummary,
+        ) -> (GapSlice<'a>, ChunkSummary)
+        where
+            'a: 'a,
+        {
+            let (_, right) = chunk.split_at_offset(char_offset, summary);
+            right
+        }
+    }
+}
+
 #[cfg(feature = "utf16-metric")]
 pub use utf16_metric::Utf16Metric;
 
@@ -643,6 +798,13 @@ mod utf16_metric {
                     str_summary.line_breaks,
                 ),
 
+                #[cfg(feature = "char-metric")]
+                chars: count::chars_up_to(
+                    in_str,
+                    byte_offset,
+                    str_summary.chars,
+                ),
+
                 utf16_code_units: utf16_code_unit_offset,
             }
         }
@@ -700,9 +862,11 @@ mod utf16_metric {
     }
 }
 
-use str_utils::*;
+pub(super) use str_utils::*;
 
 mod str_utils {
+    #[cfg(not(miri))]
+    use str_indices::chars;
     #[cfg(not(miri))]
     use str_indices::lines_lf as lines;
     #[cfg(all(not(miri), feature = "utf16-metric"))]
@@ -724,6 +888,18 @@ mod str_utils {
             }
         }
 
+        #[inline]
+        pub fn chars(s: &str) -> usize {
+            #[cfg(not(miri))]
+            {
+                chars::count(s)
+            }
+            #[cfg(miri)]
+            {
+                s.chars().count()
+            }
+        }
+
         #[cfg(feature = "utf16-metric")]
         #[inline]
         pub fn utf16_code_units(s: &str) -> usize {
@@ -737,6 +913,16 @@ mod str_utils {
             }
         }
 
+        #[cfg(feature = "char-metric")]
+        #[inline(always)]
+        pub fn chars_up_to(
+            s: &str,
+            byte_offset: usize,
+            tot_chars: usize,
+        ) -> usize {
+            metric_up_to(s, byte_offset, tot_chars, chars)
+        }
+
         #[inline(always)]
         pub fn line_breaks_up_to(
             s: &str,
@@ -786,6 +972,22 @@ mod str_utils {
         #[cfg(not(miri))]
         use super::*;
 
+        #[inline]
+        pub fn byte_of_char(s: &str, char_offset: usize) -> usize {
+            #[cfg(not(miri))]
+            {
+                chars::to_byte_idx(s, char_offset)
+            }
+
+            #[cfg(miri)]
+            {
+                s.char_indices()
+                    .nth(char_offset)
+                    .map(|(byte_offset, _)| byte_offset)
+                    .unwrap_or(s.len())
+            }
+        }
+
         #[inline]
         pub fn byte_of_line(s: &str, line_offset: usize) -> usize {
             #[cfg(not(miri))]
@@ -1,12 +1,39 @@
+pub(crate) mod anchors;
+pub(crate) mod changes;
+mod cursor;
+pub(crate) mod decorations;
+pub(crate) mod diff;
+mod diff_ranges;
 pub(crate) mod gap_buffer;
 pub(crate) mod gap_slice;
+pub(crate) mod history;
 pub(crate) mod iterators;
+pub(crate) mod lsp;
+pub(crate) mod merge;
 pub mod metrics;
+mod patch;
+mod pattern;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "regex")]
+pub(crate) mod regex;
 mod rope;
 mod rope_builder;
 mod rope_slice;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "tree-sitter")]
+pub(crate) mod tree_sitter;
 mod utils;
 
+pub use cursor::Cursor;
+pub use diff_ranges::DiffRange;
+pub use patch::PatchError;
+pub use pattern::Pattern;
+#[cfg(feature = "std")]
+pub use reader::RopeReader;
 pub use rope::Rope;
-pub use rope_builder::RopeBuilder;
+#[cfg(feature = "std")]
+pub use rope_builder::FromReaderError;
+pub use rope_builder::{RopeBuilder, Utf8Error};
 pub use rope_slice::RopeSlice;
@@ -0,0 +1,353 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use super::Rope;
+
+/// An error returned by [`Rope::apply_patch()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatchError {
+    /// The patch couldn't be parsed as a unified diff.
+    Malformed {
+        /// The 0-based index of the line of the patch that couldn't be
+        /// parsed.
+        line_idx: usize,
+    },
+
+    /// The context and deleted lines of one of the hunks of the patch didn't
+    /// match the contents of the `Rope`, not even after shifting them up or
+    /// down.
+    Mismatch {
+        /// The 0-based index of the hunk that failed to match, counting all
+        /// the hunks in the patch.
+        hunk_idx: usize,
+
+        /// The range of lines of the `Rope` the hunk was expected to match
+        /// according to its `@@` header.
+        old_lines: Range<usize>,
+    },
+}
+
+impl core::fmt::Display for PatchError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Malformed { line_idx } => {
+                write!(f, "malformed patch at line {}", line_idx + 1)
+            },
+            Self::Mismatch { hunk_idx, old_lines } => write!(
+                f,
+                "hunk #{} doesn't match lines {}..{}",
+                hunk_idx + 1,
+                old_lines.start + 1,
+                old_lines.end + 1
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PatchError {}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum LineKind {
+    Context,
+    Delete,
+    Insert,
+}
+
+/// A line of a hunk, without its `' '`, `'-'` or `'+'` prefix.
+#[derive(Debug)]
+struct PatchLine<'p> {
+    kind: LineKind,
+
+    /// The text of the line, including its line break.
+    raw: &'p str,
+
+    /// Whether the line was followed by a `\ No newline at end of file`
+    /// marker.
+    no_newline: bool,
+}
+
+impl PatchLine<'_> {
+    #[inline]
+    fn is_old(&self) -> bool {
+        self.kind != LineKind::Insert
+    }
+
+    /// Returns the text of the line and its line break, which is empty if
+    /// the line was followed by a `\ No newline at end of file` marker.
+    #[inline]
+    fn split(&self) -> (&str, &str) {
+        if self.no_newline {
+            // The marker only refers to the `\n` added after the line, so a
+            // `\r` before it is part of the text.
+            let text = self.raw.strip_suffix('\n').unwrap_or(self.raw);
+            (text, "")
+        } else if let Some(text) = self.raw.strip_suffix("\r\n") {
+            (text, "\r\n")
+        } else if let Some(text) = self.raw.strip_suffix('\n') {
+            (text, "\n")
+        } else {
+            // The last line of a patch that doesn't end with a line break.
+            (self.raw, "\n")
+        }
+    }
+}
+
+/// A parsed hunk.
+#[derive(Debug)]
+struct PatchHunk<'p> {
+    /// The 0-based index of the first old line of the hunk, according to its
+    /// header.
+    old_start: usize,
+
+    /// The number of context and deleted lines.
+    old_len: usize,
+
+    lines: Vec<PatchLine<'p>>,
+}
+
+/// Parses a `@@ -a[,b] +c[,d] @@` header into `(a, b, d)`.
+#[inline]
+fn parse_header(line: &str) -> Option<(usize, usize, usize)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+
+    let parse_range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, len)) => {
+                Some((start.parse().ok()?, len.parse().ok()?))
+            },
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+
+    let (old_start, old_len) = parse_range(old)?;
+    let (_, new_len) = parse_range(new)?;
+
+    Some((old_start, old_len, new_len))
+}
+
+/// Parses the hunks of a unified diff. Lines outside of hunks, like the
+/// `---` and `+++` headers, are ignored.
+#[inline]
+fn parse(patch: &str) -> Result<Vec<PatchHunk<'_>>, PatchError> {
+    // Lines are split keeping their line breaks, so that the ones of CRLF
+    // texts make it into the rope.
+    let mut lines = patch.split_inclusive('\n').enumerate().peekable();
+
+    let mut hunks = Vec::new();
+
+    while let Some((line_idx, line)) = lines.next() {
+        if !line.starts_with("@@") {
+            continue;
+        }
+
+        let (old_start, old_len, new_len) =
+            parse_header(line).ok_or(PatchError::Malformed { line_idx })?;
+
+        // An empty range starts at the line before the hunk, a non-empty one
+        // at its first line (and both are 1-based).
+        let old_start = if old_len == 0 {
+            old_start
+        } else {
+            old_start
+                .checked_sub(1)
+                .ok_or(PatchError::Malformed { line_idx })?
+        };
+
+        let mut hunk = PatchHunk { old_start, old_len, lines: Vec::new() };
+
+        let (mut old_seen, mut new_seen) = (0, 0);
+
+        while old_seen < old_len || new_seen < new_len {
+            let Some((line_idx, line)) = lines.next() else {
+                let line_idx = patch.split_inclusive('\n').count();
+                return Err(PatchError::Malformed { line_idx });
+            };
+
+            let (kind, raw) = match line.as_bytes().first() {
+                Some(b' ') => (LineKind::Context, &line[1..]),
+                Some(b'-') => (LineKind::Delete, &line[1..]),
+                Some(b'+') => (LineKind::Insert, &line[1..]),
+
+                // Some tools strip the trailing whitespace of empty context
+                // lines.
+                Some(b'\r' | b'\n') => (LineKind::Context, line),
+
+                Some(b'\\') if !hunk.lines.is_empty() => {
+                    hunk.lines.last_mut().unwrap().no_newline = true;
+                    continue;
+                },
+
+                _ => return Err(PatchError::Malformed { line_idx }),
+            };
+
+            if kind != LineKind::Insert {
+                old_seen += 1;
+            }
+
+            if kind != LineKind::Delete {
+                new_seen += 1;
+            }
+
+            if old_seen > old_len || new_seen > new_len {
+                return Err(PatchError::Malformed { line_idx });
+            }
+
+            hunk.lines.push(PatchLine { kind, raw, no_newline: false });
+        }
+
+        // The marker of the last line comes after the end of the hunk.
+        if let Some((_, line)) = lines.peek() {
+            if line.starts_with('\\') && !hunk.lines.is_empty() {
+                hunk.lines.last_mut().unwrap().no_newline = true;
+                lines.next();
+            }
+        }
+
+        hunks.push(hunk);
+    }
+
+    Ok(hunks)
+}
+
+/// Returns whether the context and deleted lines of the hunk match the lines
+/// of the rope starting at `line_offset`.
+#[inline]
+fn matches_at(rope: &Rope, hunk: &PatchHunk<'_>, line_offset: usize) -> bool {
+    hunk.lines.iter().filter(|line| line.is_old()).enumerate().all(
+        |(idx, line)| {
+            let line_idx = line_offset + idx;
+
+            let (text, line_break) = line.split();
+
+            let raw_line = rope.line_slice(line_idx..line_idx + 1);
+            let rope_line = rope.line(line_idx);
+
+            // A line break is either empty, `\n` or `\r\n`, so comparing
+            // their lengths is enough.
+            rope_line == text
+                && raw_line.byte_len() - rope_line.byte_len()
+                    == line_break.len()
+        },
+    )
+}
+
+/// Finds the line offset at which the hunk matches the rope, starting from
+/// `expected` and moving further away from it in both directions, without
+/// going before `min_offset`.
+#[inline]
+fn find_match(
+    rope: &Rope,
+    hunk: &PatchHunk<'_>,
+    expected: usize,
+    min_offset: usize,
+) -> Option<usize> {
+    let max_offset = rope.line_len().checked_sub(hunk.old_len)?;
+
+    if min_offset > max_offset {
+        return None;
+    }
+
+    let expected = expected.clamp(min_offset, max_offset);
+
+    let max_distance = (expected - min_offset).max(max_offset - expected);
+
+    (0..=max_distance)
+        .flat_map(|distance| {
+            let before = expected.checked_sub(distance);
+            let after = Some(expected + distance).filter(|_| distance > 0);
+            before.into_iter().chain(after)
+        })
+        .filter(|&offset| offset >= min_offset && offset <= max_offset)
+        .find(|&offset| matches_at(rope, hunk, offset))
+}
+
+/// Applies a unified diff to the rope. See [`Rope::apply_patch()`] for more.
+#[inline]
+pub(super) fn apply_patch(
+    rope: &mut Rope,
+    patch: &str,
+) -> Result<(), PatchError> {
+    let hunks = parse(patch)?;
+
+    // First find where every hunk matches, so that the rope is left untouched
+    // if one of them doesn't.
+
+    let mut matches = Vec::with_capacity(hunks.len());
+
+    // The offset at which the previous hunk matched relative to its header,
+    // which is likely to be the same for the next one.
+    let mut shift = 0isize;
+
+    let mut min_offset = 0;
+
+    for (hunk_idx, hunk) in hunks.iter().enumerate() {
+        let expected = (hunk.old_start as isize + shift).max(0) as usize;
+
+        let line_offset = find_match(rope, hunk, expected, min_offset)
+            .ok_or_else(|| PatchError::Mismatch {
+                hunk_idx,
+                old_lines: hunk.old_start..hunk.old_start + hunk.old_len,
+            })?;
+
+        shift = line_offset as isize - hunk.old_start as isize;
+        min_offset = line_offset + hunk.old_len;
+        matches.push(line_offset);
+    }
+
+    // Then apply them starting from the last one, so that the line offsets
+    // of the previous ones stay valid.
+
+    for (hunk, &line_offset) in hunks.iter().zip(&matches).rev() {
+        apply_hunk(rope, hunk, line_offset);
+    }
+
+    Ok(())
+}
+
+/// Replaces every run of deleted and inserted lines of the hunk, leaving the
+/// context lines untouched.
+#[inline]
+fn apply_hunk(rope: &mut Rope, hunk: &PatchHunk<'_>, line_offset: usize) {
+    // The runs of changed lines, as the range of old lines they replace and
+    // the text that replaces them.
+    let mut runs = Vec::<(Range<usize>, String)>::new();
+
+    let mut old_line = line_offset;
+
+    let mut in_run = false;
+
+    for line in &hunk.lines {
+        if line.kind == LineKind::Context {
+            old_line += 1;
+            in_run = false;
+            continue;
+        }
+
+        if !in_run {
+            runs.push((old_line..old_line, String::new()));
+            in_run = true;
+        }
+
+        let (range, text) = runs.last_mut().unwrap();
+
+        if line.kind == LineKind::Delete {
+            old_line += 1;
+            range.end = old_line;
+        } else {
+            let (line_text, line_break) = line.split();
+            text.push_str(line_text);
+            text.push_str(line_break);
+        }
+    }
+
+    for (lines, text) in runs.into_iter().rev() {
+        let start = rope.byte_of_line(lines.start);
+        let end = rope.byte_of_line(lines.end);
+        rope.replace(start..end, text);
+    }
+}
@@ -0,0 +1,114 @@
+use core::ops::Range;
+
+/// A pattern that can be searched for in `Rope`s and `RopeSlice`s.
+///
+/// This is implemented for [`char`]s, string slices and closures of the form
+/// `FnMut(char) -> bool`, and it's used by the `find`, `rfind`, `find_iter`
+/// and `rfind_iter` methods on [`Rope`](crate::Rope::find()) and
+/// [`RopeSlice`](crate::RopeSlice::find()).
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait Pattern: sealed::Sealed {
+    /// Returns the byte range of the first match in `haystack`.
+    #[doc(hidden)]
+    fn first_match(&mut self, haystack: &str) -> Option<Range<usize>>;
+
+    /// Returns the byte range of the last match in `haystack`.
+    #[doc(hidden)]
+    fn last_match(&mut self, haystack: &str) -> Option<Range<usize>>;
+
+    /// Returns the maximum byte length of a match that can span more than
+    /// one chunk.
+    ///
+    /// Patterns that only ever match a single char can return zero, since
+    /// chunks are always split on char boundaries.
+    #[doc(hidden)]
+    fn spanning_len(&self) -> usize;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for char {}
+
+    impl Sealed for &str {}
+
+    impl Sealed for &alloc::string::String {}
+
+    impl<F: FnMut(char) -> bool> Sealed for F {}
+}
+
+impl Pattern for char {
+    #[inline]
+    fn first_match(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let start = haystack.find(*self)?;
+        Some(start..start + self.len_utf8())
+    }
+
+    #[inline]
+    fn last_match(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let start = haystack.rfind(*self)?;
+        Some(start..start + self.len_utf8())
+    }
+
+    #[inline]
+    fn spanning_len(&self) -> usize {
+        0
+    }
+}
+
+impl Pattern for &str {
+    #[inline]
+    fn first_match(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let start = haystack.find(*self)?;
+        Some(start..start + self.len())
+    }
+
+    #[inline]
+    fn last_match(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let start = haystack.rfind(*self)?;
+        Some(start..start + self.len())
+    }
+
+    #[inline]
+    fn spanning_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl Pattern for &alloc::string::String {
+    #[inline]
+    fn first_match(&mut self, haystack: &str) -> Option<Range<usize>> {
+        self.as_str().first_match(haystack)
+    }
+
+    #[inline]
+    fn last_match(&mut self, haystack: &str) -> Option<Range<usize>> {
+        self.as_str().last_match(haystack)
+    }
+
+    #[inline]
+    fn spanning_len(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<F: FnMut(char) -> bool> Pattern for F {
+    #[inline]
+    fn first_match(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let (start, ch) = haystack.char_indices().find(|&(_, ch)| self(ch))?;
+        Some(start..start + ch.len_utf8())
+    }
+
+    #[inline]
+    fn last_match(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let (start, ch) =
+            haystack.char_indices().rev().find(|&(_, ch)| self(ch))?;
+        Some(start..start + ch.len_utf8())
+    }
+
+    #[inline]
+    fn spanning_len(&self) -> usize {
+        0
+    }
+}
@@ -0,0 +1,177 @@
+use std::io;
+
+use super::iterators::{chunks_in_range, Chunks};
+use super::RopeSlice;
+
+/// A reader over the contents of `Rope`s and `RopeSlice`s.
+///
+/// It implements [`Read`](io::Read), [`BufRead`](io::BufRead) and
+/// [`Seek`](io::Seek), where [`fill_buf()`](io::BufRead::fill_buf()) returns
+/// the rest of the current chunk without copying it, and seeking to a byte
+/// offset runs in logarithmic time.
+///
+/// This struct is created by the `reader` method on
+/// [`Rope`](crate::Rope::reader()) and
+/// [`RopeSlice`](crate::RopeSlice::reader()). See their documentation for
+/// more.
+#[derive(Clone)]
+pub struct RopeReader<'a> {
+    slice: RopeSlice<'a>,
+
+    /// The chunks following `chunk`.
+    chunks: Chunks<'a>,
+
+    /// The bytes of the current chunk that haven't been consumed yet.
+    chunk: &'a [u8],
+
+    /// The byte offset of the reader in the slice. This can be greater than
+    /// the byte length of the slice if we've seeked past its end.
+    position: usize,
+}
+
+impl<'a> From<RopeSlice<'a>> for RopeReader<'a> {
+    #[inline]
+    fn from(slice: RopeSlice<'a>) -> Self {
+        Self { slice, chunks: slice.chunks(), chunk: &[], position: 0 }
+    }
+}
+
+impl core::fmt::Debug for RopeReader<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RopeReader")
+            .field("position", &self.position)
+            .field("byte_len", &self.slice.byte_len())
+            .finish()
+    }
+}
+
+impl<'a> RopeReader<'a> {
+    /// Returns the byte offset of the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Read;
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello");
+    ///
+    /// let mut reader = r.reader();
+    /// reader.read_exact(&mut [0; 3]).unwrap();
+    ///
+    /// assert_eq!(reader.position(), 3);
+    /// ```
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves the reader to the given byte offset, which doesn't have to lie
+    /// on a char boundary.
+    #[inline]
+    fn seek_to(&mut self, byte_offset: usize) {
+        self.position = byte_offset;
+
+        if byte_offset >= self.slice.byte_len() {
+            self.chunks = self.slice.byte_slice(0..0).chunks();
+            self.chunk = &[];
+            return;
+        }
+
+        let (_, first, first_idx, _, _) =
+            chunks_in_range(self.slice, byte_offset, self.slice.byte_len());
+
+        // The end of a chunk is always a char boundary, so we can slice the
+        // rest of the text from there.
+        let first_end = byte_offset - first_idx + first.len();
+
+        self.chunks = self.slice.byte_slice(first_end..).chunks();
+        self.chunk = &first.as_bytes()[first_idx..];
+    }
+}
+
+impl io::Read for RopeReader<'_> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut read = 0;
+
+        while read < buf.len() {
+            let chunk = io::BufRead::fill_buf(self)?;
+
+            if chunk.is_empty() {
+                break;
+            }
+
+            let len = core::cmp::min(chunk.len(), buf.len() - read);
+            buf[read..read + len].copy_from_slice(&chunk[..len]);
+            io::BufRead::consume(self, len);
+            read += len;
+        }
+
+        Ok(read)
+    }
+}
+
+impl io::BufRead for RopeReader<'_> {
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        while self.chunk.is_empty() {
+            match self.chunks.next() {
+                Some(chunk) => self.chunk = chunk.as_bytes(),
+                None => break,
+            }
+        }
+
+        Ok(self.chunk)
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        let amt = core::cmp::min(amt, self.chunk.len());
+        self.chunk = &self.chunk[amt..];
+        self.position += amt;
+    }
+}
+
+impl io::Seek for RopeReader<'_> {
+    #[inline]
+    fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+        let new_position = match pos {
+            io::SeekFrom::Start(offset) => usize::try_from(offset).ok(),
+            io::SeekFrom::End(offset) => {
+                offset_by(self.slice.byte_len(), offset)
+            },
+            io::SeekFrom::Current(offset) => offset_by(self.position, offset),
+        };
+
+        match new_position {
+            Some(position) => {
+                self.seek_to(position);
+                Ok(position as u64)
+            },
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    #[inline]
+    fn stream_position(&mut self) -> io::Result<u64> {
+        Ok(self.position as u64)
+    }
+}
+
+/// Adds a signed offset to a byte offset, returning `None` if the result
+/// would be negative or overflow.
+#[inline]
+fn offset_by(byte_offset: usize, offset: i64) -> Option<usize> {
+    let abs = usize::try_from(offset.unsigned_abs()).ok()?;
+
+    if offset >= 0 {
+        byte_offset.checked_add(abs)
+    } else {
+        byte_offset.checked_sub(abs)
+    }
+}
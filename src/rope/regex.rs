@@ -0,0 +1,470 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::ops::Range;
+
+use regex_automata::util::captures::Captures;
+use regex_automata::util::start;
+use regex_automata::{hybrid, meta, Anchored, Input};
+
+use super::RopeSlice;
+
+/// A compiled regular expression that can be used to search `Rope`s and
+/// `RopeSlice`s without first collecting them into a string.
+///
+/// This is used by the `find_regex`, `find_regex_iter` and `regex_captures`
+/// methods on [`Rope`](crate::Rope::find_regex()) and
+/// [`RopeSlice`](crate::RopeSlice::find_regex()), and by
+/// [`Rope::replace_all_regex()`](crate::Rope::replace_all_regex()).
+///
+/// The syntax is the same as the one of the
+/// [`regex`](https://docs.rs/regex) crate.
+///
+/// Searches run a lazy DFA over the chunks of the text. Regexes containing a
+/// Unicode word boundary (`\b` or `\B`, unless Unicode is disabled like in
+/// `(?-u:\b)`) are the exception: the DFA can't handle them on non-ASCII
+/// text, so once it finds a non-ASCII byte the whole text is copied into a
+/// `String` and searched in one go, which takes linear time and memory.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{regex::Regex, Rope};
+/// #
+/// let r = Rope::from("Hello Earth 🌎!");
+///
+/// let re = Regex::new(r"[A-Z]\w+").unwrap();
+///
+/// assert_eq!(r.find_regex(&re), Some(0..5));
+/// ```
+#[derive(Debug)]
+pub struct Regex {
+    /// Used to find the start and end of every match by walking the chunks
+    /// of the text.
+    hybrid: Option<hybrid::regex::Regex>,
+
+    /// Used to resolve capture groups, and as a fallback when the lazy DFA
+    /// can't handle the text.
+    meta: meta::Regex,
+}
+
+impl Regex {
+    /// Compiles a new `Regex` from the given pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pattern is not a valid regular expression or
+    /// if the compiled regex would exceed the default size limits.
+    #[allow(clippy::result_large_err)]
+    #[inline]
+    pub fn new(pattern: &str) -> Result<Self, meta::BuildError> {
+        let meta = meta::Regex::new(pattern)?;
+
+        // Unicode word boundaries can't be handled by a DFA, so we ask it to
+        // quit on non-ASCII bytes instead of failing to build. When that
+        // happens we fall back to the meta regex.
+        //
+        // Big patterns, like bounded repetitions of Unicode classes, don't
+        // fit in the default cache capacity, so we let the DFA use the
+        // minimum capacity it needs instead of falling back for those too.
+        let dfa_config = hybrid::dfa::Config::new()
+            .unicode_word_boundary(true)
+            .skip_cache_capacity_check(true);
+
+        let hybrid =
+            hybrid::regex::Builder::new().dfa(dfa_config).build(pattern).ok();
+
+        Ok(Self { hybrid, meta })
+    }
+
+    /// Returns the byte range of the first match in the slice starting at or
+    /// after `start`.
+    #[inline]
+    fn find_at(
+        &self,
+        slice: RopeSlice<'_>,
+        start: usize,
+        searcher: &mut Searcher,
+    ) -> Option<Range<usize>> {
+        if let Searcher::Chunked(cache) = searcher {
+            // Chunked searchers are only created if the lazy DFA was built.
+            let hybrid = self.hybrid.as_ref().expect("lazy DFA was built");
+
+            match find_chunked(hybrid, cache, slice, start) {
+                Ok(Some(range)) if !slice.is_char_boundary(range.start) => {
+                    // An empty match splitting a code point, which we skip
+                    // like the `regex` crate does.
+                    let next = next_char_boundary(slice, range.start);
+                    return self.find_at(slice, next, searcher);
+                },
+                Ok(range) => return range,
+                Err(Unsupported) => {
+                    *searcher = Searcher::Flattened(slice.chunks().collect());
+                },
+            }
+        }
+
+        let Searcher::Flattened(text) = searcher else { unreachable!() };
+
+        self.meta
+            .find(Input::new(text.as_str()).range(start..))
+            .map(|m| m.range())
+    }
+
+    /// Resolves the capture groups of a match previously returned by
+    /// [`find_at()`](Self::find_at()), returning them together with the text
+    /// they refer to and the byte offset of that text in the slice.
+    #[inline]
+    fn captures_of(
+        &self,
+        slice: RopeSlice<'_>,
+        range: Range<usize>,
+    ) -> (Captures, String, usize) {
+        // Look-around assertions like `\b` or `$` look at most one char
+        // before and after the match, so that's all the context we need.
+        let context_start = slice
+            .byte_slice(..range.start)
+            .chars()
+            .next_back()
+            .map_or(range.start, |ch| range.start - ch.len_utf8());
+
+        let context_end = slice
+            .byte_slice(range.end..)
+            .chars()
+            .next()
+            .map_or(range.end, |ch| range.end + ch.len_utf8());
+
+        let text = slice
+            .byte_slice(context_start..context_end)
+            .chunks()
+            .collect::<String>();
+
+        let input = Input::new(text.as_str())
+            .span(range.start - context_start..range.end - context_start)
+            .anchored(Anchored::Yes);
+
+        let mut captures = self.meta.create_captures();
+
+        self.meta.search_captures(&input, &mut captures);
+
+        debug_assert!(captures.is_match());
+
+        (captures, text, context_start)
+    }
+
+    /// Returns the byte range of the first match in the slice.
+    #[inline]
+    pub(super) fn find(&self, slice: RopeSlice<'_>) -> Option<Range<usize>> {
+        self.find_at(slice, 0, &mut self.searcher(slice))
+    }
+
+    /// Returns the capture groups of the given match.
+    #[inline]
+    pub(super) fn captures(
+        &self,
+        slice: RopeSlice<'_>,
+        range: Range<usize>,
+    ) -> RegexCaptures {
+        let (captures, _, offset) = self.captures_of(slice, range);
+        RegexCaptures { captures, offset }
+    }
+
+    /// Appends the replacement for the given match to `dst`, expanding any
+    /// `$name` or `${name}` reference to the text of the corresponding
+    /// capture group.
+    #[inline]
+    pub(super) fn expand(
+        &self,
+        slice: RopeSlice<'_>,
+        range: Range<usize>,
+        replacement: &str,
+        dst: &mut String,
+    ) {
+        let (captures, text, _) = self.captures_of(slice, range);
+        captures.interpolate_string_into(&text, replacement, dst);
+    }
+
+    #[inline]
+    fn searcher(&self, slice: RopeSlice<'_>) -> Searcher {
+        match &self.hybrid {
+            Some(hybrid) => {
+                Searcher::Chunked(Box::new(hybrid.create_cache()))
+            },
+            None => Searcher::Flattened(slice.chunks().collect()),
+        }
+    }
+}
+
+/// The capture groups of a regex match in a `Rope` or `RopeSlice`.
+///
+/// This struct is created by the `regex_captures` method on
+/// [`Rope`](crate::Rope::regex_captures()) and
+/// [`RopeSlice`](crate::RopeSlice::regex_captures()). See their documentation
+/// for more.
+#[derive(Clone, Debug)]
+pub struct RegexCaptures {
+    captures: Captures,
+
+    /// The byte offset of the text the captures were resolved on.
+    offset: usize,
+}
+
+impl RegexCaptures {
+    /// Returns the byte range of the capture group at the given index, or
+    /// `None` if the group didn't participate in the match or if the index
+    /// is out of bounds.
+    ///
+    /// The group at index 0 always corresponds to the whole match.
+    #[inline]
+    pub fn get(&self, group_index: usize) -> Option<Range<usize>> {
+        self.captures
+            .get_group(group_index)
+            .map(|span| span.start + self.offset..span.end + self.offset)
+    }
+
+    /// Returns the total number of capture groups in the regex, including
+    /// the implicit one matching the whole match.
+    #[inline]
+    pub fn group_len(&self) -> usize {
+        self.captures.group_len()
+    }
+
+    /// Returns the byte range of the capture group with the given name, or
+    /// `None` if there's no group with that name or if it didn't participate
+    /// in the match.
+    #[inline]
+    pub fn name(&self, name: &str) -> Option<Range<usize>> {
+        self.captures
+            .get_group_by_name(name)
+            .map(|span| span.start + self.offset..span.end + self.offset)
+    }
+}
+
+/// An iterator over the byte ranges of the non-overlapping matches of a
+/// [`Regex`] in `Rope`s and `RopeSlice`s.
+///
+/// This struct is created by the `find_regex_iter` method on
+/// [`Rope`](crate::Rope::find_regex_iter()) and
+/// [`RopeSlice`](crate::RopeSlice::find_regex_iter()). See their
+/// documentation for more.
+#[derive(Clone, Debug)]
+pub struct RegexMatches<'a, 'r> {
+    slice: RopeSlice<'a>,
+    regex: &'r Regex,
+    searcher: Searcher,
+
+    /// The byte offset to start the next search at.
+    start: usize,
+
+    /// The end of the last match, used to avoid yielding an empty match
+    /// right after a non-empty one.
+    last_end: Option<usize>,
+
+    finished: bool,
+}
+
+impl<'a, 'r> RegexMatches<'a, 'r> {
+    #[inline]
+    pub(super) fn new(slice: RopeSlice<'a>, regex: &'r Regex) -> Self {
+        Self {
+            slice,
+            regex,
+            searcher: regex.searcher(slice),
+            start: 0,
+            last_end: None,
+            finished: false,
+        }
+    }
+}
+
+impl Iterator for RegexMatches<'_, '_> {
+    type Item = Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        loop {
+            let Some(range) =
+                self.regex.find_at(self.slice, self.start, &mut self.searcher)
+            else {
+                self.finished = true;
+                return None;
+            };
+
+            if range.is_empty() && self.last_end == Some(range.end) {
+                if range.end == self.slice.byte_len() {
+                    self.finished = true;
+                    return None;
+                }
+                self.start = next_char_boundary(self.slice, range.end);
+                continue;
+            }
+
+            self.start = range.end;
+            self.last_end = Some(range.end);
+            return Some(range);
+        }
+    }
+}
+
+impl core::iter::FusedIterator for RegexMatches<'_, '_> {}
+
+/// How a [`Regex`] searches a `RopeSlice`.
+#[derive(Clone, Debug)]
+enum Searcher {
+    /// Using the lazy DFA, chunk by chunk.
+    Chunked(Box<hybrid::regex::Cache>),
+
+    /// Using the meta regex on the whole text collected into a `String`.
+    Flattened(String),
+}
+
+/// Returned when the lazy DFA can't search the text, either because it
+/// contains a non-ASCII byte and the regex has a Unicode word boundary, or
+/// because the DFA gave up.
+struct Unsupported;
+
+/// Finds the first match in the slice starting at or after `start` by
+/// walking its chunks forward to find the end of the match and then backward
+/// to find its start, just like [`hybrid::regex::Regex::find()`] does on a
+/// contiguous haystack.
+#[inline]
+fn find_chunked(
+    regex: &hybrid::regex::Regex,
+    cache: &mut hybrid::regex::Cache,
+    slice: RopeSlice<'_>,
+    start: usize,
+) -> Result<Option<Range<usize>>, Unsupported> {
+    let (forward_cache, reverse_cache) = cache.as_parts_mut();
+
+    let Some(end) = find_end(regex.forward(), forward_cache, slice, start)?
+    else {
+        return Ok(None);
+    };
+
+    let start =
+        find_start(regex.reverse(), reverse_cache, slice, start, end)?;
+
+    Ok(Some(start..end))
+}
+
+/// Returns the end of the first (leftmost-first) match starting at or after
+/// `start`.
+#[inline]
+fn find_end(
+    dfa: &hybrid::dfa::DFA,
+    cache: &mut hybrid::dfa::Cache,
+    slice: RopeSlice<'_>,
+    start: usize,
+) -> Result<Option<usize>, Unsupported> {
+    let config = start::Config::new()
+        .anchored(Anchored::No)
+        .look_behind((start > 0).then(|| slice.byte(start - 1)));
+
+    let mut state =
+        dfa.start_state(cache, &config).map_err(|_| Unsupported)?;
+
+    let mut end = None;
+
+    let mut offset = start;
+
+    for chunk in slice.byte_slice(start..).chunks() {
+        for &byte in chunk.as_bytes() {
+            state =
+                dfa.next_state(cache, state, byte).map_err(|_| Unsupported)?;
+
+            if state.is_tagged() {
+                if state.is_match() {
+                    // Matches are delayed by one byte, so the match ends
+                    // right before the current byte.
+                    end = Some(offset);
+                } else if state.is_dead() {
+                    return Ok(end);
+                } else if state.is_quit() {
+                    return Err(Unsupported);
+                }
+            }
+
+            offset += 1;
+        }
+    }
+
+    state = dfa.next_eoi_state(cache, state).map_err(|_| Unsupported)?;
+
+    if state.is_match() {
+        end = Some(slice.byte_len());
+    }
+
+    Ok(end)
+}
+
+/// Returns the start of the match ending at `end`, which is known not to
+/// start before `lower_bound`.
+#[inline]
+fn find_start(
+    dfa: &hybrid::dfa::DFA,
+    cache: &mut hybrid::dfa::Cache,
+    slice: RopeSlice<'_>,
+    lower_bound: usize,
+    end: usize,
+) -> Result<usize, Unsupported> {
+    let config = start::Config::new()
+        .anchored(Anchored::Yes)
+        .look_behind((end < slice.byte_len()).then(|| slice.byte(end)));
+
+    let mut state =
+        dfa.start_state(cache, &config).map_err(|_| Unsupported)?;
+
+    let mut start = None;
+
+    let mut offset = end;
+
+    for chunk in slice.byte_slice(lower_bound..end).chunks().rev() {
+        for &byte in chunk.as_bytes().iter().rev() {
+            offset -= 1;
+
+            state =
+                dfa.next_state(cache, state, byte).map_err(|_| Unsupported)?;
+
+            if state.is_tagged() {
+                if state.is_match() {
+                    start = Some(offset + 1);
+                } else if state.is_dead() {
+                    return start.ok_or(Unsupported);
+                } else if state.is_quit() {
+                    return Err(Unsupported);
+                }
+            }
+        }
+    }
+
+    state = if lower_bound > 0 {
+        let byte = slice.byte(lower_bound - 1);
+        dfa.next_state(cache, state, byte).map_err(|_| Unsupported)?
+    } else {
+        dfa.next_eoi_state(cache, state).map_err(|_| Unsupported)?
+    };
+
+    if state.is_match() {
+        start = Some(lower_bound);
+    } else if state.is_quit() {
+        return Err(Unsupported);
+    }
+
+    // The forward search found a match ending at `end`, so the reverse
+    // search is guaranteed to find its start.
+    start.ok_or(Unsupported)
+}
+
+#[inline]
+fn next_char_boundary(slice: RopeSlice<'_>, byte_offset: usize) -> usize {
+    let mut offset = byte_offset + 1;
+
+    while !slice.is_char_boundary(offset) {
+        offset += 1;
+    }
+
+    offset
+}
@@ -1,10 +1,14 @@
 use core::ops::RangeBounds;
 
 use super::gap_buffer::GapBuffer;
-use super::iterators::{Bytes, Chars, Chunks, Lines, RawLines};
+use super::iterators::{
+    Bytes, BytesAt, Chars, CharsAt, Chunks, ChunksAt, FindIter, Lines,
+    LinesAt, RFindIter, RawLines,
+};
+use super::lsp::Encoding;
 use super::metrics::{ByteMetric, RawLineMetric};
 use super::utils::{panic_messages as panic, *};
-use super::RopeSlice;
+use super::{Cursor, Pattern, RopeSlice};
 use crate::range_bounds_to_start_end;
 use crate::tree::Tree;
 
@@ -34,6 +38,31 @@ pub struct Rope {
 }
 
 impl Rope {
+    /// Appends `other` to the end of the `Rope`.
+    ///
+    /// This runs in logarithmic time and doesn't copy the text of either
+    /// `Rope`: the two share their underlying chunks, and only the nodes
+    /// along the seam between them are rebalanced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello ");
+    ///
+    /// r.append(Rope::from("world!"));
+    /// assert_eq!(r, "Hello world!");
+    /// ```
+    #[inline]
+    pub fn append(&mut self, other: Rope) {
+        if !other.is_empty() {
+            self.has_trailing_newline = other.has_trailing_newline;
+        }
+
+        self.tree.append(other.tree);
+    }
+
     #[doc(hidden)]
     pub fn assert_invariants(&self) {
         self.tree.assert_invariants();
@@ -111,6 +140,42 @@ impl Rope {
         self.tree.summary().bytes()
     }
 
+    /// Returns the byte offset corresponding to the given [`char`] offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the char offset is out of bounds (i.e. greater than
+    /// [`char_len()`](Self::char_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // The "ƒ" and "ä" characters are both encoded using two bytes in
+    /// // UTF-8.
+    /// let r = Rope::from("ƒoo bär");
+    ///
+    /// assert_eq!(r.byte_of_char(0), 0);
+    /// assert_eq!(r.byte_of_char(1), "ƒ".len());
+    /// assert_eq!(r.byte_of_char(6), "ƒoo bä".len());
+    /// assert_eq!(r.byte_of_char(r.char_len()), r.byte_len());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_char(&self, char_offset: usize) -> usize {
+        if char_offset > self.char_len() {
+            panic::char_offset_out_of_bounds(char_offset, self.char_len())
+        }
+
+        let ByteMetric(byte_offset) =
+            self.tree.convert_measure(super::metrics::CharMetric(char_offset));
+
+        byte_offset
+    }
+
     /// Returns the byte offset of the start of the given line.
     ///
     /// # Panics
@@ -147,6 +212,49 @@ impl Rope {
         byte_offset
     }
 
+    /// Returns the byte offset of the position at the given line and column,
+    /// where the column is measured in the given [`Encoding`].
+    ///
+    /// Like in the Language Server Protocol, columns past the end of the
+    /// line are clamped to its end (before its line break), and lines past
+    /// the last one are clamped to the end of the `Rope`. A column in the
+    /// middle of a code point is rounded down to its start. Only `\n` and
+    /// `\r\n` are considered line breaks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{lsp::Encoding, Rope};
+    /// #
+    /// let r = Rope::from("foo\r\n𐐀bar");
+    ///
+    /// // The "𐐀" character is encoded using two code units in UTF-16 and
+    /// // four bytes in UTF-8.
+    /// assert_eq!(r.byte_of_position(1, 2, Encoding::Utf16), 9);
+    /// assert_eq!(r.byte_of_position(1, 1, Encoding::Utf32), 9);
+    /// assert_eq!(r.byte_of_position(1, 4, Encoding::Utf8), 9);
+    ///
+    /// // Columns inside a code point are rounded down, out of range columns
+    /// // and lines are clamped.
+    /// assert_eq!(r.byte_of_position(1, 1, Encoding::Utf16), 5);
+    /// assert_eq!(r.byte_of_position(0, 10, Encoding::Utf16), 3);
+    /// assert_eq!(r.byte_of_position(5, 0, Encoding::Utf16), r.byte_len());
+    /// ```
+    #[inline]
+    pub fn byte_of_position(
+        &self,
+        line_offset: usize,
+        column: usize,
+        encoding: Encoding,
+    ) -> usize {
+        super::lsp::byte_of_position(
+            self.byte_slice(..),
+            line_offset,
+            column,
+            encoding,
+        )
+    }
+
     /// Returns the byte offset corresponding to the given UTF-16 code unit
     /// offset.
     ///
@@ -243,6 +351,147 @@ impl Rope {
         Bytes::from(self)
     }
 
+    /// Returns an iterator over the bytes of this `Rope` starting at the
+    /// given byte offset.
+    ///
+    /// Calling [`next()`](Iterator::next()) on the returned iterator yields
+    /// the bytes after its position, and calling
+    /// [`next_back()`](DoubleEndedIterator::next_back()) yields the bytes
+    /// before it, so it can walk over the whole `Rope` in both directions. See
+    /// [`BytesAt`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo");
+    ///
+    /// let mut bytes = r.bytes_at(1);
+    ///
+    /// assert_eq!(Some(b'o'), bytes.next());
+    /// assert_eq!(Some(b'o'), bytes.next());
+    /// assert_eq!(None, bytes.next());
+    ///
+    /// assert_eq!(Some(b'o'), bytes.next_back());
+    /// assert_eq!(Some(b'o'), bytes.next_back());
+    /// assert_eq!(Some(b'f'), bytes.next_back());
+    /// assert_eq!(None, bytes.next_back());
+    ///
+    /// let before = r.bytes_at(1).rev().collect::<Vec<_>>();
+    /// assert_eq!(before, b"f");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn bytes_at(&self, byte_offset: usize) -> BytesAt<'_> {
+        BytesAt::new(self.byte_slice(..), byte_offset)
+    }
+
+    /// Returns the number of [`char`]s in the `Rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // Even though it's encoded using four bytes, the "🐸" emoji is a
+    /// // single char.
+    /// let r = Rope::from("abc🐸");
+    /// assert_eq!(r.char_len(), 4);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[inline]
+    pub fn char_len(&self) -> usize {
+        self.tree.summary().chars()
+    }
+
+    /// Returns the [`char`] offset corresponding to the given byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// // The "ƒ" and "ä" characters are both encoded using two bytes in
+    /// // UTF-8.
+    /// let r = Rope::from("ƒoo bär");
+    ///
+    /// assert_eq!(r.char_of_byte(0), 0);
+    /// assert_eq!(r.char_of_byte("ƒ".len()), 1);
+    /// assert_eq!(r.char_of_byte("ƒoo bä".len()), 6);
+    /// assert_eq!(r.char_of_byte(r.byte_len()), r.char_len());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn char_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let super::metrics::CharMetric(char_offset) =
+            self.tree.convert_measure(ByteMetric(byte_offset));
+
+        char_offset
+    }
+
+    /// Returns an immutable slice of the `Rope` in the specified [`char`]
+    /// range, where the start and end of the range are interpreted as
+    /// offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`char_len()`](Self::char_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🗻∈🌏");
+    ///
+    /// assert_eq!(r.char_slice(..1), "🗻");
+    /// assert_eq!(r.char_slice(1..2), "∈");
+    /// assert_eq!(r.char_slice(2..), "🌏");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn char_slice<R>(&self, char_range: R) -> RopeSlice<'_>
+    where
+        R: RangeBounds<usize>,
+    {
+        use super::metrics::CharMetric;
+
+        let (start, end) =
+            range_bounds_to_start_end(char_range, 0, self.char_len());
+
+        if start > end {
+            panic::char_start_after_end(start, end);
+        }
+
+        if end > self.char_len() {
+            panic::char_offset_out_of_bounds(end, self.char_len());
+        }
+
+        self.tree.slice(CharMetric(start)..CharMetric(end)).into()
+    }
+
     /// Returns an iterator over the [`char`]s of this `Rope`.
     ///
     /// # Examples
@@ -265,12 +514,102 @@ impl Rope {
         Chars::from(self)
     }
 
+    /// Returns an iterator over the [`char`]s of this `Rope` starting at
+    /// the given byte offset.
+    ///
+    /// Calling [`next()`](Iterator::next()) on the returned iterator yields
+    /// the chars after its position, and calling
+    /// [`next_back()`](DoubleEndedIterator::next_back()) yields the chars
+    /// before it, so it can walk over the whole `Rope` in both directions. See
+    /// [`CharsAt`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🐸🐻‍❄️");
+    ///
+    /// let mut chars = r.chars_at(4);
+    ///
+    /// assert_eq!(Some('🐻'), chars.next());
+    /// assert_eq!(Some('🐻'), chars.next_back());
+    /// assert_eq!(Some('🐸'), chars.next_back());
+    /// assert_eq!(None, chars.next_back());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn chars_at(&self, byte_offset: usize) -> CharsAt<'_> {
+        CharsAt::new(self.byte_slice(..), byte_offset)
+    }
+
     /// Returns an iterator over the chunks of this [`Rope`].
     #[inline]
     pub fn chunks(&self) -> Chunks<'_> {
         Chunks::from(self)
     }
 
+    /// Returns an iterator over the chunks of this `Rope` starting at the
+    /// given byte offset.
+    ///
+    /// Calling [`next()`](Iterator::next()) on the returned iterator yields
+    /// the chunks after its position, and calling
+    /// [`next_back()`](DoubleEndedIterator::next_back()) yields the chunks
+    /// before it, so it can walk over the whole `Rope` in both directions. The
+    /// chunk containing `byte_offset` is split in two, so the first chunk
+    /// yielded in either direction might be shorter than the ones yielded by
+    /// [`chunks()`](Self::chunks()). See [`ChunksAt`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\nbaz");
+    ///
+    /// let after = r.chunks_at(4).collect::<String>();
+    /// assert_eq!(after, "bar\nbaz");
+    ///
+    /// let mut before = r.chunks_at(4).rev().collect::<Vec<_>>();
+    /// before.reverse();
+    /// assert_eq!(before.concat(), "foo\n");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn chunks_at(&self, byte_offset: usize) -> ChunksAt<'_> {
+        ChunksAt::new(self.byte_slice(..), byte_offset)
+    }
+
+    /// Returns a [`Cursor`] positioned at the start of this `Rope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// let mut cursor = r.cursor();
+    /// cursor.seek_to_line(1);
+    /// assert_eq!(cursor.next_char(), Some('b'));
+    /// ```
+    #[inline]
+    pub fn cursor(&self) -> Cursor<'_> {
+        Cursor::from(self)
+    }
+
     /// Deletes the contents of the `Rope` within the specified byte range,
     /// where the start and end of the range are interpreted as offsets.
     ///
@@ -299,10 +638,335 @@ impl Rope {
         self.replace(byte_range, "");
     }
 
+    /// Returns the ranges of text that changed between an older snapshot of
+    /// this `Rope` and this `Rope`, sorted by their position.
+    ///
+    /// Every [`DiffRange`](crate::DiffRange) contains a byte range of `older`
+    /// and the byte range of `self` that replaced it, together with the lines
+    /// they touch. The byte ranges are minimal, i.e. they don't start or end
+    /// with text shared by both sides. An empty byte range at the end of a
+    /// text ending with a line break touches no lines, and its line range is
+    /// `line_len..line_len`.
+    ///
+    /// Cloning a `Rope` is cheap because the clone shares all of its chunks
+    /// with the original, and editing either of them only copies the parts of
+    /// the tree that are affected by the edit. This method takes advantage of
+    /// that by skipping over the parts the two `Rope`s still share, so its
+    /// cost is proportional to the size of the changes rather than to the size
+    /// of the `Rope`s. On the flip side, two `Rope`s with the same text that
+    /// don't share any chunk (for example because they were created
+    /// independently) are compared in linear time.
+    ///
+    /// Note that edits falling within the same chunk are reported as a single
+    /// range spanning all of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let saved = Rope::from("Hello Earth 🌎!\n".repeat(1000));
+    ///
+    /// let mut r = saved.clone();
+    /// r.replace(6..11, "Mars");
+    /// r.insert(r.byte_len(), "Goodbye!\n");
+    ///
+    /// let ranges = r.diff_ranges(&saved);
+    ///
+    /// assert_eq!(ranges.len(), 2);
+    ///
+    /// assert_eq!(ranges[0].old_bytes, 6..11);
+    /// assert_eq!(ranges[0].new_bytes, 6..10);
+    /// assert_eq!(ranges[0].old_lines, 0..1);
+    /// assert_eq!(ranges[0].new_lines, 0..1);
+    ///
+    /// assert_eq!(ranges[1].old_bytes, 18000..18000);
+    /// assert_eq!(ranges[1].new_bytes, 17999..18008);
+    /// // The insertion is past the last line of `saved`.
+    /// assert_eq!(ranges[1].old_lines, 1000..1000);
+    /// assert_eq!(ranges[1].new_lines, 1000..1001);
+    /// ```
+    #[inline]
+    pub fn diff_ranges(&self, older: &Rope) -> Vec<crate::DiffRange> {
+        super::diff_ranges::diff_ranges(older, self)
+    }
+
+    /// Applies a batch of changes sent by a Language Server Protocol client
+    /// in a `textDocument/didChange` notification to this `Rope`.
+    ///
+    /// The changes are applied one after the other, so the range of every
+    /// change refers to the document as left by the previous ones. Its
+    /// positions are converted to byte offsets via
+    /// [`byte_of_position()`](Self::byte_of_position()) using
+    /// [`Encoding::Utf16`], so characters past the end of a line are clamped
+    /// to its end and lines past the last one to the end of the document. A
+    /// change without a range replaces the whole document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the start of the range of one of the changes comes
+    /// after its end, in which case the `Rope` is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::lsp::{ContentChange, ContentChangeError};
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("let 🦀 = 1;\n");
+    ///
+    /// // The crab takes two UTF-16 code units.
+    /// let rename = ContentChange {
+    ///     range: Some((0, 4)..(0, 6)),
+    ///     text: "crab".into(),
+    /// };
+    ///
+    /// let newline = ContentChange {
+    ///     range: Some((0, 13)..(0, 13)),
+    ///     text: "\n".into(),
+    /// };
+    ///
+    /// r.apply_lsp_changes(&[rename, newline]).unwrap();
+    /// assert_eq!(r, "let crab = 1;\n\n");
+    ///
+    /// let backwards = ContentChange {
+    ///     range: Some((1, 0)..(0, 0)),
+    ///     text: String::new(),
+    /// };
+    ///
+    /// assert_eq!(
+    ///     r.apply_lsp_changes(&[backwards]),
+    ///     Err(ContentChangeError::StartAfterEnd {
+    ///         change_idx: 0,
+    ///         range: (1, 0)..(0, 0),
+    ///     })
+    /// );
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "utf16-metric")))]
+    #[cfg(feature = "utf16-metric")]
+    #[inline]
+    pub fn apply_lsp_changes(
+        &mut self,
+        changes: &[crate::lsp::ContentChange],
+    ) -> Result<(), crate::lsp::ContentChangeError> {
+        super::lsp::apply_changes(self, changes)
+    }
+
+    /// Applies a patch in the unified diff format to this `Rope`.
+    ///
+    /// Every hunk of the patch is matched against the lines of the `Rope` by
+    /// comparing its context and deleted lines, including their line breaks,
+    /// with the corresponding lines of the `Rope`. If a hunk doesn't match at
+    /// the position given by its `@@` header, it's searched further up and
+    /// down the `Rope`, like `patch(1)` does. Once all the hunks have been
+    /// matched their changes are applied via
+    /// [`replace()`](Self::replace()); the inserted lines keep the line
+    /// breaks they have in the patch, except for a last line followed by a
+    /// `\ No newline at end of file` marker.
+    ///
+    /// The patch is expected to target a single file: any line outside of a
+    /// hunk, like the `---` and `+++` headers, is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the patch is malformed or if one of its hunks
+    /// can't be matched, in which case the `Rope` is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{PatchError, Rope};
+    /// #
+    /// let mut r = Rope::from("Hello\nWorld\n");
+    ///
+    /// r.apply_patch("@@ -2 +2,2 @@\n-World\n+there\n+World\n").unwrap();
+    /// assert_eq!(r, "Hello\nthere\nWorld\n");
+    ///
+    /// assert_eq!(
+    ///     r.apply_patch("@@ -1 +1 @@\n-Goodbye\n+Hi\n"),
+    ///     Err(PatchError::Mismatch { hunk_idx: 0, old_lines: 0..1 })
+    /// );
+    /// ```
+    #[inline]
+    pub fn apply_patch(
+        &mut self,
+        patch: &str,
+    ) -> Result<(), crate::PatchError> {
+        super::patch::apply_patch(self, patch)
+    }
+
+    /// Returns the byte offset of the first match of the pattern in this
+    /// `Rope`, or `None` if there isn't one.
+    ///
+    /// The pattern can be a [`char`], a string slice or a closure of the form
+    /// `FnMut(char) -> bool`. Matches spanning chunk boundaries are found too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// assert_eq!(r.find('o'), Some(4));
+    /// assert_eq!(r.find("Earth"), Some(6));
+    /// assert_eq!(r.find(char::is_whitespace), Some(5));
+    /// assert_eq!(r.find("Mars"), None);
+    /// ```
+    #[inline]
+    pub fn find<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        self.find_iter(pattern).next()
+    }
+
+    /// Returns an iterator over the byte offsets of the non-overlapping
+    /// matches of the pattern in this `Rope`, from front to back.
+    ///
+    /// This behaves like [`str::match_indices()`], except it only yields the
+    /// offsets of the matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("abcXXXabcYYYabc");
+    ///
+    /// let matches = r.find_iter("abc").collect::<Vec<_>>();
+    /// assert_eq!(matches, [0, 6, 12]);
+    /// ```
+    #[inline]
+    pub fn find_iter<P: Pattern>(&self, pattern: P) -> FindIter<'_, P> {
+        FindIter::new(self.byte_slice(..), pattern)
+    }
+
+    /// Returns the byte range of the first match of the regex in this
+    /// `Rope`, or `None` if there isn't one.
+    ///
+    /// The search runs directly on the chunks of the `Rope`, without
+    /// collecting its contents into a string. The exception are regexes
+    /// containing a Unicode word boundary (`\b` or `\B`, unless Unicode is
+    /// disabled like in `(?-u:\b)`): if the text contains non-ASCII bytes
+    /// it's copied into a `String` before searching it, which takes linear
+    /// time and memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{regex::Regex, Rope};
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// let re = Regex::new(r"E\w+").unwrap();
+    ///
+    /// let range = r.find_regex(&re).unwrap();
+    /// assert_eq!(r.byte_slice(range), "Earth");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn find_regex(
+        &self,
+        regex: &crate::regex::Regex,
+    ) -> Option<core::ops::Range<usize>> {
+        regex.find(self.byte_slice(..))
+    }
+
+    /// Returns an iterator over the byte ranges of the non-overlapping
+    /// matches of the regex in this `Rope`, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{regex::Regex, Rope};
+    /// #
+    /// let r = Rope::from("foo 42 bar 1337");
+    ///
+    /// let re = Regex::new(r"\d+").unwrap();
+    ///
+    /// let matches = r.find_regex_iter(&re).collect::<Vec<_>>();
+    /// assert_eq!(matches, [4..6, 11..15]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn find_regex_iter<'r>(
+        &self,
+        regex: &'r crate::regex::Regex,
+    ) -> crate::regex::RegexMatches<'_, 'r> {
+        crate::regex::RegexMatches::new(self.byte_slice(..), regex)
+    }
+
     pub(super) const fn arity() -> usize {
         ARITY
     }
 
+    /// Creates a new `Rope` from the contents of a reader, returning an error
+    /// if reading fails or if the contents are not valid UTF-8.
+    ///
+    /// The contents are validated as they're read, without first collecting
+    /// them into a `String`. Invalid UTF-8 is reported as a
+    /// [`FromReaderError::Utf8`](crate::FromReaderError::Utf8), which holds
+    /// the byte offset of the first invalid sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{FromReaderError, Rope};
+    /// #
+    /// let r = Rope::from_reader(&b"Hello \xF0\x9F\x8C\x8E!"[..]).unwrap();
+    /// assert_eq!(r, "Hello 🌎!");
+    ///
+    /// let err = Rope::from_reader(&b"Hello \xF0\x9F!"[..]).unwrap_err();
+    ///
+    /// let FromReaderError::Utf8(utf8_err) = err else { unreachable!() };
+    /// assert_eq!(utf8_err.byte_offset(), 6);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_reader<R: std::io::Read>(
+        reader: R,
+    ) -> Result<Self, crate::FromReaderError> {
+        let mut builder = super::RopeBuilder::new();
+
+        read_into(reader, |bytes| {
+            builder
+                .append_bytes(bytes)
+                .map(|_| ())
+                .map_err(crate::FromReaderError::Utf8)
+        })?;
+
+        Ok(builder.try_build()?)
+    }
+
+    /// Like [`from_reader()`](Self::from_reader()), except invalid UTF-8
+    /// sequences are replaced by U+FFFD REPLACEMENT CHARACTER instead of
+    /// returning an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from_reader_lossy(&b"Hello \xF0\x9F!"[..]).unwrap();
+    /// assert_eq!(r, "Hello \u{FFFD}!");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn from_reader_lossy<R: std::io::Read>(
+        reader: R,
+    ) -> std::io::Result<Self> {
+        let mut builder = super::RopeBuilder::new();
+
+        read_into(reader, |bytes| {
+            builder.append_bytes_lossy(bytes);
+            Ok::<_, std::io::Error>(())
+        })?;
+
+        Ok(builder.build())
+    }
+
     /// Returns an iterator over the extended grapheme clusters of this
     /// `Rope`.
     ///
@@ -345,16 +1009,47 @@ impl Rope {
     /// #
     /// let mut r = Rope::from("Hello Earth!");
     ///
-    /// r.insert(11, " 🌎");
+    /// r.insert(11, " 🌎");
+    /// assert_eq!(r, "Hello Earth 🌎!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn insert<T>(&mut self, byte_offset: usize, text: T)
+    where
+        T: AsRef<str>,
+    {
+        self.replace(byte_offset..byte_offset, text)
+    }
+
+    /// Inserts the contents of a [`RopeSlice`] in the `Rope` at the given byte
+    /// offset.
+    ///
+    /// Unlike [`insert()`](Self::insert()) this doesn't need the text of the
+    /// slice to be materialized into a string first: see
+    /// [`replace_with_slice()`](Self::replace_with_slice()) for more details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset doesn't lie on a code point boundary or if
+    /// it's out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let planets = Rope::from("Earth 🌎 and Saturn 🪐");
+    ///
+    /// let mut r = Rope::from("Hello !");
+    ///
+    /// r.insert_slice(6, planets.byte_slice(..10));
     /// assert_eq!(r, "Hello Earth 🌎!");
     /// ```
     #[track_caller]
     #[inline]
-    pub fn insert<T>(&mut self, byte_offset: usize, text: T)
-    where
-        T: AsRef<str>,
-    {
-        self.replace(byte_offset..byte_offset, text)
+    pub fn insert_slice(&mut self, byte_offset: usize, slice: RopeSlice<'_>) {
+        self.replace_with_slice(byte_offset..byte_offset, slice)
     }
 
     /// Returns `true` if the given byte offset lies on a [`char`] boundary.
@@ -616,6 +1311,77 @@ impl Rope {
         Lines::from(self)
     }
 
+    /// Returns an iterator over the lines of this `Rope`, not including the
+    /// line terminators, starting at the given line offset.
+    ///
+    /// Calling [`next()`](Iterator::next()) on the returned iterator yields
+    /// the lines starting from the `line_offset`-th one, and calling
+    /// [`next_back()`](DoubleEndedIterator::next_back()) yields the lines
+    /// before it, so it can walk over the whole `Rope` in both directions. See
+    /// [`LinesAt`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    ///
+    /// let mut lines = r.lines_at(1);
+    ///
+    /// assert_eq!("bar", lines.next().unwrap());
+    /// assert_eq!("baz", lines.next().unwrap());
+    /// assert_eq!(None, lines.next());
+    ///
+    /// assert_eq!("baz", lines.next_back().unwrap());
+    /// assert_eq!("bar", lines.next_back().unwrap());
+    /// assert_eq!("foo", lines.next_back().unwrap());
+    /// assert_eq!(None, lines.next_back());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn lines_at(&self, line_offset: usize) -> LinesAt<'_> {
+        LinesAt::new(self.byte_slice(..), line_offset)
+    }
+
+    /// Returns the line and column of the given byte offset, where the column
+    /// is measured in the given [`Encoding`].
+    ///
+    /// It's the inverse of [`byte_of_position()`](Self::byte_of_position()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{lsp::Encoding, Rope};
+    /// #
+    /// let r = Rope::from("foo\r\n𐐀bar");
+    ///
+    /// assert_eq!(r.position_of_byte(9, Encoding::Utf8), (1, 4));
+    /// assert_eq!(r.position_of_byte(9, Encoding::Utf16), (1, 2));
+    /// assert_eq!(r.position_of_byte(9, Encoding::Utf32), (1, 1));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn position_of_byte(
+        &self,
+        byte_offset: usize,
+        encoding: Encoding,
+    ) -> (usize, usize) {
+        let slice = self.byte_slice(..);
+        super::lsp::position_of_byte(slice, byte_offset, encoding)
+    }
+
     /// Returns an iterator over the lines of this `Rope`, including the
     /// line terminators.
     ///
@@ -659,6 +1425,66 @@ impl Rope {
         Self::default()
     }
 
+    /// Returns a reader over the contents of this `Rope`, implementing the
+    /// [`Read`](std::io::Read), [`BufRead`](std::io::BufRead) and
+    /// [`Seek`](std::io::Seek) traits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{BufRead, Read, Seek, SeekFrom};
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\nbaz");
+    ///
+    /// let mut reader = r.reader();
+    ///
+    /// let mut line = String::new();
+    /// reader.read_line(&mut line).unwrap();
+    /// assert_eq!(line, "foo\n");
+    ///
+    /// reader.seek(SeekFrom::End(-3)).unwrap();
+    ///
+    /// let mut rest = String::new();
+    /// reader.read_to_string(&mut rest).unwrap();
+    /// assert_eq!(rest, "baz");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn reader(&self) -> crate::RopeReader<'_> {
+        crate::RopeReader::from(self.byte_slice(..))
+    }
+
+    /// Returns the capture groups of the first match of the regex in this
+    /// `Rope`, or `None` if there isn't one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{regex::Regex, Rope};
+    /// #
+    /// let r = Rope::from("name: crop, version: 0.4.2");
+    ///
+    /// let re = Regex::new(r"version: (?<major>\d+)\.(\d+)").unwrap();
+    ///
+    /// let captures = r.regex_captures(&re).unwrap();
+    ///
+    /// assert_eq!(captures.get(0), Some(12..24));
+    /// assert_eq!(r.byte_slice(captures.name("major").unwrap()), "0");
+    /// assert_eq!(r.byte_slice(captures.get(2).unwrap()), "4");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn regex_captures(
+        &self,
+        regex: &crate::regex::Regex,
+    ) -> Option<crate::regex::RegexCaptures> {
+        let range = regex.find(self.byte_slice(..))?;
+        Some(regex.captures(self.byte_slice(..), range))
+    }
+
     /// Replaces the contents of the `Rope` within the specified byte range
     /// with the given string, where the start and end of the range are
     /// interpreted as byte offsets.
@@ -719,6 +1545,232 @@ impl Rope {
         }
     }
 
+    /// Replaces all the non-overlapping matches of the regex in the `Rope`
+    /// with the given replacement, returning the number of replaced matches.
+    ///
+    /// Like in the `regex` crate, `$name` and `${name}` in the replacement are
+    /// expanded to the text of the corresponding capture group, where `name`
+    /// is either the index or the name of the group. Use `$$` to insert a
+    /// literal dollar sign.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{regex::Regex, Rope};
+    /// #
+    /// let mut r = Rope::from("2024-01-31, 1999-12-25");
+    ///
+    /// let re = Regex::new(r"(?<y>\d{4})-(?<m>\d{2})-(?<d>\d{2})").unwrap();
+    ///
+    /// assert_eq!(r.replace_all_regex(&re, "$d/$m/$y"), 2);
+    /// assert_eq!(r, "31/01/2024, 25/12/1999");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn replace_all_regex(
+        &mut self,
+        regex: &crate::regex::Regex,
+        replacement: &str,
+    ) -> usize {
+        let slice = self.byte_slice(..);
+
+        let mut replacements = Vec::new();
+
+        for range in slice.find_regex_iter(regex) {
+            let mut text = String::new();
+
+            if replacement.contains('$') {
+                regex.expand(slice, range.clone(), replacement, &mut text);
+            } else {
+                text.push_str(replacement);
+            }
+
+            replacements.push((range, text));
+        }
+
+        let num_replaced = replacements.len();
+
+        // Replacing back to front keeps the offsets of the remaining matches
+        // valid.
+        for (range, text) in replacements.into_iter().rev() {
+            self.replace(range, text);
+        }
+
+        num_replaced
+    }
+
+    /// Replaces the contents of the `Rope` within the specified byte range
+    /// with the contents of a [`RopeSlice`], where the start and end of the
+    /// range are interpreted as offsets.
+    ///
+    /// The internal nodes of the slice are shared with the `Rope` it was
+    /// taken from instead of being copied, so only the chunks at the
+    /// boundaries of the slice and of the replaced range are copied and
+    /// rebalanced. This runs in logarithmic time regardless of the length of
+    /// the slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start or the end of the byte range don't lie on a code
+    /// point boundary, if the start is greater than the end or if the end is
+    /// out of bounds (i.e. greater than [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let planets = Rope::from("Earth 🌎 and Saturn 🪐");
+    ///
+    /// let mut r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// r.replace_with_slice(6..16, planets.byte_slice(15..));
+    /// assert_eq!(r, "Hello Saturn 🪐!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn replace_with_slice<R>(
+        &mut self,
+        byte_range: R,
+        slice: RopeSlice<'_>,
+    ) where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) =
+            range_bounds_to_start_end(byte_range, 0, self.byte_len());
+
+        if start > end {
+            panic::byte_start_after_end(start, end);
+        }
+
+        if end > self.byte_len() {
+            panic::byte_offset_out_of_bounds(end, self.byte_len());
+        }
+
+        let mut rope = Rope::from(self.byte_slice(..start));
+
+        rope.append(Rope::from(slice));
+
+        rope.append(Rope::from(self.byte_slice(end..)));
+
+        *self = rope;
+    }
+
+    /// Returns the byte offset of the last match of the pattern in this
+    /// `Rope`, or `None` if there isn't one.
+    ///
+    /// See [`find()`](Self::find()) for the kinds of patterns that are
+    /// accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    ///
+    /// assert_eq!(r.rfind('o'), Some(4));
+    /// assert_eq!(r.rfind("🌎"), Some(12));
+    /// assert_eq!(r.rfind(char::is_whitespace), Some(11));
+    /// assert_eq!(r.rfind("Mars"), None);
+    /// ```
+    #[inline]
+    pub fn rfind<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        self.rfind_iter(pattern).next()
+    }
+
+    /// Returns an iterator over the byte offsets of the non-overlapping
+    /// matches of the pattern in this `Rope`, from back to front.
+    ///
+    /// This behaves like [`str::rmatch_indices()`], except it only yields the
+    /// offsets of the matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("abcXXXabcYYYabc");
+    ///
+    /// let matches = r.rfind_iter("abc").collect::<Vec<_>>();
+    /// assert_eq!(matches, [12, 6, 0]);
+    /// ```
+    #[inline]
+    pub fn rfind_iter<P: Pattern>(&self, pattern: P) -> RFindIter<'_, P> {
+        RFindIter::new(self.byte_slice(..), pattern)
+    }
+
+    /// Splits the `Rope` in two at the given byte offset, returning the text
+    /// before and after it, respectively.
+    ///
+    /// Just like [`split_off()`](Self::split_off()), this runs in logarithmic
+    /// time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar");
+    ///
+    /// let (left, right) = r.split_at(4);
+    ///
+    /// assert_eq!(left, "foo\n");
+    /// assert_eq!(right, "bar");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn split_at(mut self, byte_offset: usize) -> (Rope, Rope) {
+        let right = self.split_off(byte_offset);
+        (self, right)
+    }
+
+    /// Splits the `Rope` in two at the given byte offset, returning the text
+    /// after it and keeping the text before it in `self`.
+    ///
+    /// This runs in logarithmic time and doesn't copy the text of the `Rope`:
+    /// the two halves keep sharing the chunks that don't contain the byte
+    /// offset, and only the nodes along the cut are rebalanced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let mut r = Rope::from("Hello world!");
+    ///
+    /// let world = r.split_off(6);
+    ///
+    /// assert_eq!(r, "Hello ");
+    /// assert_eq!(world, "world!");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn split_off(&mut self, byte_offset: usize) -> Rope {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let right = Rope::from(self.byte_slice(byte_offset..));
+
+        *self = Rope::from(self.byte_slice(..byte_offset));
+
+        right
+    }
+
     /// Returns the number of UTF-16 code units the `Rope` would have if it
     /// stored its text as UTF-16 instead of UTF-8.
     ///
@@ -889,7 +1941,7 @@ impl core::cmp::PartialEq<Rope> for Rope {
     fn eq(&self, rhs: &Rope) -> bool {
         (self.byte_len() == rhs.byte_len())
             && (self.line_len() == rhs.line_len())
-            && chunks_eq_chunks(self.chunks(), rhs.chunks())
+            && subtrees_eq_subtrees(self.tree.subtrees(), rhs.tree.subtrees())
     }
 }
 
@@ -898,7 +1950,10 @@ impl core::cmp::PartialEq<RopeSlice<'_>> for Rope {
     fn eq(&self, rhs: &RopeSlice<'_>) -> bool {
         (self.byte_len() == rhs.byte_len())
             && (self.line_len() == rhs.line_len())
-            && chunks_eq_chunks(self.chunks(), rhs.chunks())
+            && subtrees_eq_subtrees(
+                self.tree.subtrees(),
+                rhs.tree_slice.subtrees(),
+            )
     }
 }
 
@@ -959,3 +2014,112 @@ impl core::cmp::PartialEq<Rope> for alloc::borrow::Cow<'_, str> {
 }
 
 impl core::cmp::Eq for Rope {}
+
+impl core::hash::Hash for Rope {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        hash_chunks(self.chunks(), state)
+    }
+}
+
+impl core::cmp::Ord for Rope {
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        chunks_cmp_chunks(self.chunks(), rhs.chunks())
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), rhs.chunks()))
+    }
+}
+
+impl core::cmp::PartialOrd<str> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &str) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_str(self.chunks(), rhs))
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for str {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl core::cmp::PartialOrd<&str> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &&str) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(*rhs)
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for &str {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl core::cmp::PartialOrd<String> for Rope {
+    #[inline]
+    fn partial_cmp(&self, rhs: &String) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&**rhs)
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for String {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl core::cmp::PartialOrd<alloc::borrow::Cow<'_, str>> for Rope {
+    #[inline]
+    fn partial_cmp(
+        &self,
+        rhs: &alloc::borrow::Cow<'_, str>,
+    ) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&**rhs)
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for alloc::borrow::Cow<'_, str> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+/// Reads the whole contents of the reader, passing them to `append` in
+/// pieces.
+#[cfg(feature = "std")]
+#[inline]
+fn read_into<R, F, E>(mut reader: R, mut append: F) -> Result<(), E>
+where
+    R: std::io::Read,
+    F: FnMut(&[u8]) -> Result<(), E>,
+    E: From<std::io::Error>,
+{
+    let mut buffer = [0; 8 * 1024];
+
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(read) => append(&buffer[..read])?,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {},
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
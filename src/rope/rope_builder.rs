@@ -12,6 +12,119 @@ pub struct RopeBuilder {
     buffer: RopeChunk,
     buffer_len_left: usize,
     rope_has_trailing_newline: bool,
+
+    /// The start of a multi-byte sequence at the end of the last slice passed
+    /// to [`append_bytes()`](Self::append_bytes()), which will hopefully be
+    /// completed by the next one.
+    incomplete: [u8; 3],
+    incomplete_len: usize,
+
+    /// The number of bytes appended so far, used to report the offset of
+    /// invalid UTF-8 sequences.
+    bytes_appended: usize,
+}
+
+/// The error returned when the bytes appended to a
+/// [`RopeBuilder`](crate::RopeBuilder) are not valid UTF-8.
+///
+/// This is returned by
+/// [`RopeBuilder::append_bytes()`](crate::RopeBuilder::append_bytes()) and
+/// [`RopeBuilder::try_build()`](crate::RopeBuilder::try_build()), and it's
+/// wrapped in a [`FromReaderError`](crate::FromReaderError) by
+/// [`Rope::from_reader()`](crate::Rope::from_reader()).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Utf8Error {
+    byte_offset: usize,
+    error_len: Option<usize>,
+}
+
+impl Utf8Error {
+    /// Returns the byte offset of the first invalid sequence, counting all
+    /// the bytes appended to the builder.
+    #[inline]
+    pub fn byte_offset(&self) -> usize {
+        self.byte_offset
+    }
+
+    /// Returns the byte length of the invalid sequence, or `None` if the
+    /// input ended in the middle of a multi-byte sequence.
+    ///
+    /// This has the same meaning as [`core::str::Utf8Error::error_len()`].
+    #[inline]
+    pub fn error_len(&self) -> Option<usize> {
+        self.error_len
+    }
+}
+
+impl core::fmt::Display for Utf8Error {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.error_len {
+            Some(len) => write!(
+                f,
+                "invalid UTF-8 sequence of {} bytes at byte offset {}",
+                len, self.byte_offset
+            ),
+            None => write!(
+                f,
+                "incomplete UTF-8 sequence at byte offset {}",
+                self.byte_offset
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Utf8Error {}
+
+/// The error returned by [`Rope::from_reader()`](crate::Rope::from_reader()).
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum FromReaderError {
+    /// Reading from the reader failed.
+    Io(std::io::Error),
+
+    /// The contents of the reader are not valid UTF-8.
+    Utf8(Utf8Error),
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for FromReaderError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::Utf8(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FromReaderError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Utf8(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for FromReaderError {
+    #[inline]
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Utf8Error> for FromReaderError {
+    #[inline]
+    fn from(err: Utf8Error) -> Self {
+        Self::Utf8(err)
+    }
 }
 
 /// Pushes as mush of the slice as possible onto the left chunk of the gap
@@ -50,30 +163,82 @@ fn gap_buffer_push_with_remainder<'a, const MAX_BYTES: usize>(
 
 impl RopeBuilder {
     /// Appends `text` to the end of the `Rope` being built.
+    ///
+    /// If the last call to [`append_bytes()`](Self::append_bytes()) ended in
+    /// the middle of a multi-byte sequence, that sequence is replaced by
+    /// U+FFFD REPLACEMENT CHARACTER.
     #[inline]
     pub fn append<T>(&mut self, text: T) -> &mut Self
     where
         T: AsRef<str>,
     {
-        let mut text = text.as_ref();
-
-        while let Some(rest) = gap_buffer_push_with_remainder(
-            &mut self.buffer,
-            &mut self.buffer_len_left,
-            text,
-        ) {
-            self.buffer.left_summary =
-                ChunkSummary::from(self.buffer_left_chunk());
-
-            self.tree_builder.append(core::mem::take(&mut self.buffer));
-
-            self.buffer_len_left = 0;
-
-            text = rest;
-        }
+        self.flush_incomplete();
+        self.push_str(text.as_ref());
+        self
+    }
 
-        self.rope_has_trailing_newline = self.buffer.has_trailing_newline();
+    /// Appends a slice of bytes to the end of the `Rope` being built,
+    /// returning an error if they're not valid UTF-8.
+    ///
+    /// The bytes don't have to end on a char boundary: an incomplete
+    /// multi-byte sequence at the end of the slice is held back until the
+    /// next call completes it, so a stream of bytes can be appended in
+    /// arbitrary pieces.
+    ///
+    /// If an invalid sequence is found, all the bytes before it are still
+    /// appended, while the sequence and the rest of the slice are dropped.
+    /// The dropped bytes still count towards the byte offsets of the errors
+    /// returned by later calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::RopeBuilder;
+    /// #
+    /// let mut builder = RopeBuilder::new();
+    ///
+    /// // "ä" is encoded as [0xC3, 0xA4].
+    /// builder.append_bytes(b"foo \xC3").unwrap();
+    /// builder.append_bytes(b"\xA4 bar").unwrap();
+    ///
+    /// let err = builder.append_bytes(b" \xFF").err().unwrap();
+    /// assert_eq!(err.byte_offset(), 11);
+    /// assert_eq!(err.error_len(), Some(1));
+    ///
+    /// let err = builder.append_bytes(b"baz\xFF").err().unwrap();
+    /// assert_eq!(err.byte_offset(), 15);
+    ///
+    /// assert_eq!(builder.build(), "foo ä bar baz");
+    /// ```
+    #[inline]
+    pub fn append_bytes(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<&mut Self, Utf8Error> {
+        self.push_bytes(bytes, false)?;
+        Ok(self)
+    }
 
+    /// Like [`append_bytes()`](Self::append_bytes()), except invalid UTF-8
+    /// sequences are replaced by U+FFFD REPLACEMENT CHARACTER instead of
+    /// returning an error, just like [`String::from_utf8_lossy()`] does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::RopeBuilder;
+    /// #
+    /// let mut builder = RopeBuilder::new();
+    ///
+    /// builder
+    ///     .append_bytes_lossy(b"foo \xFF")
+    ///     .append_bytes_lossy(b"bar \xC3");
+    ///
+    /// assert_eq!(builder.build(), "foo \u{FFFD}bar \u{FFFD}");
+    /// ```
+    #[inline]
+    pub fn append_bytes_lossy(&mut self, bytes: &[u8]) -> &mut Self {
+        let _ = self.push_bytes(bytes, true);
         self
     }
 
@@ -91,6 +256,11 @@ impl RopeBuilder {
     /// Completes the build, consuming the `RopeBuilder` and returning the
     /// `Rope`.
     ///
+    /// If the last call to [`append_bytes()`](Self::append_bytes()) ended in
+    /// the middle of a multi-byte sequence, that sequence is replaced by
+    /// U+FFFD REPLACEMENT CHARACTER. Use [`try_build()`](Self::try_build())
+    /// to get an error instead.
+    ///
     /// # Examples
     ///
     /// ```
@@ -106,6 +276,8 @@ impl RopeBuilder {
     /// ```
     #[inline]
     pub fn build(mut self) -> Rope {
+        self.flush_incomplete();
+
         if self.buffer_len_left > 0 {
             self.buffer.left_summary =
                 ChunkSummary::from(self.buffer_left_chunk());
@@ -122,9 +294,215 @@ impl RopeBuilder {
         }
     }
 
+    /// Replaces the incomplete multi-byte sequence left by the last call to
+    /// [`append_bytes()`](Self::append_bytes()) (if any) with U+FFFD
+    /// REPLACEMENT CHARACTER.
+    #[inline]
+    fn flush_incomplete(&mut self) {
+        if self.incomplete_len > 0 {
+            self.incomplete_len = 0;
+            self.push_str_uncounted("\u{FFFD}");
+        }
+    }
+
     /// Creates a new `RopeBuilder`.
     #[inline]
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Appends the bytes, replacing invalid sequences with U+FFFD if `lossy`
+    /// is true or returning an error otherwise.
+    #[inline]
+    fn push_bytes(
+        &mut self,
+        mut bytes: &[u8],
+        lossy: bool,
+    ) -> Result<(), Utf8Error> {
+        if self.incomplete_len > 0 {
+            bytes = self.complete_incomplete(bytes, lossy)?;
+        }
+
+        loop {
+            match core::str::from_utf8(bytes) {
+                Ok(valid) => {
+                    self.push_str(valid);
+                    return Ok(());
+                },
+
+                Err(err) => {
+                    let (valid, rest) = bytes.split_at(err.valid_up_to());
+
+                    // SAFETY: `valid_up_to()` is the length of the longest
+                    // valid UTF-8 prefix.
+                    self.push_str(unsafe {
+                        core::str::from_utf8_unchecked(valid)
+                    });
+
+                    let Some(error_len) = err.error_len() else {
+                        // The slice ends in the middle of a multi-byte
+                        // sequence, which could be completed by the next
+                        // one.
+                        self.incomplete[..rest.len()].copy_from_slice(rest);
+                        self.incomplete_len = rest.len();
+                        self.bytes_appended += rest.len();
+                        return Ok(());
+                    };
+
+                    if !lossy {
+                        let byte_offset = self.bytes_appended;
+                        self.bytes_appended += rest.len();
+                        return Err(Utf8Error {
+                            byte_offset,
+                            error_len: Some(error_len),
+                        });
+                    }
+
+                    self.bytes_appended += error_len;
+                    self.push_str_uncounted("\u{FFFD}");
+                    bytes = &rest[error_len..];
+                },
+            }
+        }
+    }
+
+    /// Tries to complete the incomplete multi-byte sequence left by the last
+    /// call to [`append_bytes()`](Self::append_bytes()) using the start of
+    /// `bytes`, returning the rest of them.
+    #[inline]
+    fn complete_incomplete<'a>(
+        &mut self,
+        bytes: &'a [u8],
+        lossy: bool,
+    ) -> Result<&'a [u8], Utf8Error> {
+        let mut sequence = [0; 4];
+
+        let incomplete_len = self.incomplete_len;
+
+        sequence[..incomplete_len]
+            .copy_from_slice(&self.incomplete[..incomplete_len]);
+
+        // A char is at most 4 bytes long, so we never need more than that.
+        let taken = core::cmp::min(4 - incomplete_len, bytes.len());
+
+        sequence[incomplete_len..incomplete_len + taken]
+            .copy_from_slice(&bytes[..taken]);
+
+        let sequence = &sequence[..incomplete_len + taken];
+
+        let sequence_offset = self.bytes_appended - incomplete_len;
+
+        let err = match core::str::from_utf8(sequence) {
+            Ok(valid) => {
+                // All the bytes formed valid chars, and since the first
+                // byte was the start of a multi-byte sequence they can't be
+                // more than one char.
+                self.incomplete_len = 0;
+                self.bytes_appended += taken;
+                self.push_str_uncounted(valid);
+                return Ok(&bytes[taken..]);
+            },
+            Err(err) => err,
+        };
+
+        if err.valid_up_to() > 0 {
+            // The sequence was completed and followed by something else.
+            let consumed = err.valid_up_to() - incomplete_len;
+            self.incomplete_len = 0;
+            self.bytes_appended += consumed;
+            // SAFETY: `valid_up_to()` is the length of the longest valid
+            // UTF-8 prefix.
+            self.push_str_uncounted(unsafe {
+                core::str::from_utf8_unchecked(&sequence[..err.valid_up_to()])
+            });
+            return Ok(&bytes[consumed..]);
+        }
+
+        match err.error_len() {
+            // Still incomplete, which means we ran out of bytes.
+            None => {
+                debug_assert_eq!(taken, bytes.len());
+                self.incomplete[..sequence.len()].copy_from_slice(sequence);
+                self.incomplete_len = sequence.len();
+                self.bytes_appended += taken;
+                Ok(&[])
+            },
+
+            Some(error_len) if lossy => {
+                // The invalid sequence always includes all the bytes we
+                // held back, since they were a valid prefix.
+                let consumed = error_len - incomplete_len;
+                self.incomplete_len = 0;
+                self.bytes_appended += consumed;
+                self.push_str_uncounted("\u{FFFD}");
+                Ok(&bytes[consumed..])
+            },
+
+            Some(error_len) => {
+                self.incomplete_len = 0;
+                self.bytes_appended += bytes.len();
+                Err(Utf8Error {
+                    byte_offset: sequence_offset,
+                    error_len: Some(error_len),
+                })
+            },
+        }
+    }
+
+    /// Appends the string and adds its length to the number of bytes
+    /// appended.
+    #[inline]
+    fn push_str(&mut self, text: &str) {
+        self.bytes_appended += text.len();
+        self.push_str_uncounted(text);
+    }
+
+    #[inline]
+    fn push_str_uncounted(&mut self, mut text: &str) {
+        while let Some(rest) = gap_buffer_push_with_remainder(
+            &mut self.buffer,
+            &mut self.buffer_len_left,
+            text,
+        ) {
+            self.buffer.left_summary =
+                ChunkSummary::from(self.buffer_left_chunk());
+
+            self.tree_builder.append(core::mem::take(&mut self.buffer));
+
+            self.buffer_len_left = 0;
+
+            text = rest;
+        }
+
+        self.rope_has_trailing_newline = self.buffer.has_trailing_newline();
+    }
+
+    /// Like [`build()`](Self::build()), except it returns an error if the
+    /// last call to [`append_bytes()`](Self::append_bytes()) ended in the
+    /// middle of a multi-byte sequence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::RopeBuilder;
+    /// #
+    /// let mut builder = RopeBuilder::new();
+    ///
+    /// builder.append_bytes(b"foo \xE2\x82").unwrap();
+    ///
+    /// let err = builder.try_build().unwrap_err();
+    /// assert_eq!(err.byte_offset(), 4);
+    /// assert_eq!(err.error_len(), None);
+    /// ```
+    #[inline]
+    pub fn try_build(self) -> Result<Rope, Utf8Error> {
+        if self.incomplete_len > 0 {
+            return Err(Utf8Error {
+                byte_offset: self.bytes_appended - self.incomplete_len,
+                error_len: None,
+            });
+        }
+
+        Ok(self.build())
+    }
 }
@@ -1,10 +1,14 @@
 use core::ops::RangeBounds;
 
-use super::iterators::{Bytes, Chars, Chunks, Lines, RawLines};
+use super::iterators::{
+    Bytes, BytesAt, Chars, CharsAt, Chunks, ChunksAt, FindIter, Lines,
+    LinesAt, RFindIter, RawLines,
+};
+use super::lsp::Encoding;
 use super::metrics::{ByteMetric, RawLineMetric};
 use super::rope::RopeChunk;
 use super::utils::{panic_messages as panic, *};
-use super::Rope;
+use super::{Cursor, Pattern, Rope};
 use crate::range_bounds_to_start_end;
 use crate::tree::TreeSlice;
 
@@ -81,6 +85,41 @@ impl<'a> RopeSlice<'a> {
         self.tree_slice.summary().bytes()
     }
 
+    /// Returns the byte offset corresponding to the given [`char`] offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the char offset is out of bounds (i.e. greater than
+    /// [`char_len()`](Self::char_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("ƒoo bär");
+    /// let s = r.byte_slice("ƒ".len()..);
+    ///
+    /// assert_eq!(s.byte_of_char(0), 0);
+    /// assert_eq!(s.byte_of_char(4), "oo b".len());
+    /// assert_eq!(s.byte_of_char(5), "oo bä".len());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn byte_of_char(&self, char_offset: usize) -> usize {
+        if char_offset > self.char_len() {
+            panic::char_offset_out_of_bounds(char_offset, self.char_len())
+        }
+
+        let ByteMetric(byte_offset) = self
+            .tree_slice
+            .convert_measure(super::metrics::CharMetric(char_offset));
+
+        byte_offset
+    }
+
     /// Returns the byte offset of the start of the given line.
     ///
     /// # Panics
@@ -120,6 +159,45 @@ impl<'a> RopeSlice<'a> {
         byte_offset
     }
 
+    /// Returns the byte offset of the position at the given line and column,
+    /// where the column is measured in the given [`Encoding`].
+    ///
+    /// Like in the Language Server Protocol, columns past the end of the
+    /// line are clamped to its end (before its line break), and lines past
+    /// the last one are clamped to the end of the `RopeSlice`. A column in the
+    /// middle of a code point is rounded down to its start. Only `\n` and
+    /// `\r\n` are considered line breaks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{lsp::Encoding, Rope};
+    /// #
+    /// let r = Rope::from("ƒoo\nfoo\r\n𐐀bar");
+    /// let s = r.byte_slice("ƒoo\n".len()..);
+    ///
+    /// // The "𐐀" character is encoded using two code units in UTF-16 and
+    /// // four bytes in UTF-8.
+    /// assert_eq!(s.byte_of_position(1, 2, Encoding::Utf16), 9);
+    /// assert_eq!(s.byte_of_position(1, 1, Encoding::Utf32), 9);
+    /// assert_eq!(s.byte_of_position(1, 4, Encoding::Utf8), 9);
+    ///
+    /// // Columns inside a code point are rounded down, out of range columns
+    /// // and lines are clamped.
+    /// assert_eq!(s.byte_of_position(1, 1, Encoding::Utf16), 5);
+    /// assert_eq!(s.byte_of_position(0, 10, Encoding::Utf16), 3);
+    /// assert_eq!(s.byte_of_position(5, 0, Encoding::Utf16), s.byte_len());
+    /// ```
+    #[inline]
+    pub fn byte_of_position(
+        &self,
+        line_offset: usize,
+        column: usize,
+        encoding: Encoding,
+    ) -> usize {
+        super::lsp::byte_of_position(*self, line_offset, column, encoding)
+    }
+
     /// Returns the byte offset corresponding to the given UTF-16 code unit
     /// offset.
     ///
@@ -219,6 +297,146 @@ impl<'a> RopeSlice<'a> {
         Bytes::from(self)
     }
 
+    /// Returns an iterator over the bytes of this `RopeSlice` starting at the
+    /// given byte offset.
+    ///
+    /// Calling [`next()`](Iterator::next()) on the returned iterator yields
+    /// the bytes after its position, and calling
+    /// [`next_back()`](DoubleEndedIterator::next_back()) yields the bytes
+    /// before it, so it can walk over the whole `RopeSlice` in both
+    /// directions. See [`BytesAt`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut bytes = s.bytes_at(1);
+    ///
+    /// assert_eq!(Some(b'o'), bytes.next());
+    /// assert_eq!(Some(b'o'), bytes.next());
+    /// assert_eq!(None, bytes.next());
+    ///
+    /// assert_eq!(Some(b'o'), bytes.next_back());
+    /// assert_eq!(Some(b'o'), bytes.next_back());
+    /// assert_eq!(Some(b'f'), bytes.next_back());
+    /// assert_eq!(None, bytes.next_back());
+    ///
+    /// let before = s.bytes_at(1).rev().collect::<Vec<_>>();
+    /// assert_eq!(before, b"f");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn bytes_at(&self, byte_offset: usize) -> BytesAt<'a> {
+        BytesAt::new(*self, byte_offset)
+    }
+
+    /// Returns the number of [`char`]s in the `RopeSlice`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🗻∈🌏");
+    /// let s = r.byte_slice(4..);
+    /// assert_eq!(s.char_len(), 2);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[inline]
+    pub fn char_len(&self) -> usize {
+        self.tree_slice.summary().chars()
+    }
+
+    /// Returns the [`char`] offset corresponding to the given byte offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("ƒoo bär");
+    /// let s = r.byte_slice("ƒ".len()..);
+    ///
+    /// assert_eq!(s.char_of_byte(0), 0);
+    /// assert_eq!(s.char_of_byte("oo bä".len()), 5);
+    /// assert_eq!(s.char_of_byte(s.byte_len()), s.char_len());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn char_of_byte(&self, byte_offset: usize) -> usize {
+        if byte_offset > self.byte_len() {
+            panic::byte_offset_out_of_bounds(byte_offset, self.byte_len());
+        }
+
+        let super::metrics::CharMetric(char_offset) =
+            self.tree_slice.convert_measure(ByteMetric(byte_offset));
+
+        char_offset
+    }
+
+    /// Returns a sub-slice of this `RopeSlice` in the specified [`char`]
+    /// range, where the start and end of the range are interpreted as
+    /// offsets.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start is greater than the end or if the end is out of
+    /// bounds (i.e. greater than [`char_len()`](Self::char_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🗻∈🌏 and 🗻∈🌏");
+    /// let s = r.char_slice(4..);
+    ///
+    /// assert_eq!(s.char_slice(..3), "and");
+    /// assert_eq!(s.char_slice(4..5), "🗻");
+    /// assert_eq!(s.char_slice(5..), "∈🌏");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "char-metric")))]
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[inline]
+    pub fn char_slice<R>(self, char_range: R) -> RopeSlice<'a>
+    where
+        R: RangeBounds<usize>,
+    {
+        use super::metrics::CharMetric;
+
+        let (start, end) =
+            range_bounds_to_start_end(char_range, 0, self.char_len());
+
+        if start > end {
+            panic::char_start_after_end(start, end);
+        }
+
+        if end > self.char_len() {
+            panic::char_offset_out_of_bounds(end, self.char_len());
+        }
+
+        self.tree_slice.slice(CharMetric(start)..CharMetric(end)).into()
+    }
+
     /// Returns an iterator over the [`char`]s of this `RopeSlice`.
     ///
     /// # Examples
@@ -241,12 +459,211 @@ impl<'a> RopeSlice<'a> {
         Chars::from(self)
     }
 
+    /// Returns an iterator over the [`char`]s of this `RopeSlice` starting at
+    /// the given byte offset.
+    ///
+    /// Calling [`next()`](Iterator::next()) on the returned iterator yields
+    /// the chars after its position, and calling
+    /// [`next_back()`](DoubleEndedIterator::next_back()) yields the chars
+    /// before it, so it can walk over the whole `RopeSlice` in both
+    /// directions. See [`CharsAt`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("🐸🐻‍❄️");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut chars = s.chars_at(4);
+    ///
+    /// assert_eq!(Some('🐻'), chars.next());
+    /// assert_eq!(Some('🐻'), chars.next_back());
+    /// assert_eq!(Some('🐸'), chars.next_back());
+    /// assert_eq!(None, chars.next_back());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn chars_at(&self, byte_offset: usize) -> CharsAt<'a> {
+        CharsAt::new(*self, byte_offset)
+    }
+
     /// Returns an iterator over the chunks of this `RopeSlice`.
     #[inline]
     pub fn chunks(&self) -> Chunks<'a> {
         Chunks::from(self)
     }
 
+    /// Returns an iterator over the chunks of this `RopeSlice` starting at the
+    /// given byte offset.
+    ///
+    /// Calling [`next()`](Iterator::next()) on the returned iterator yields
+    /// the chunks after its position, and calling
+    /// [`next_back()`](DoubleEndedIterator::next_back()) yields the chunks
+    /// before it, so it can walk over the whole `RopeSlice` in both
+    /// directions. The chunk containing `byte_offset` is split in two, so the
+    /// first chunk yielded in either direction might be shorter than the ones
+    /// yielded by [`chunks()`](Self::chunks()). See [`ChunksAt`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\nbaz");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let after = s.chunks_at(4).collect::<String>();
+    /// assert_eq!(after, "bar\nbaz");
+    ///
+    /// let mut before = s.chunks_at(4).rev().collect::<Vec<_>>();
+    /// before.reverse();
+    /// assert_eq!(before.concat(), "foo\n");
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn chunks_at(&self, byte_offset: usize) -> ChunksAt<'a> {
+        ChunksAt::new(*self, byte_offset)
+    }
+
+    /// Returns a [`Cursor`] positioned at the start of this `RopeSlice`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\nbaz");
+    ///
+    /// let s = r.byte_slice(4..);
+    /// let mut cursor = s.cursor();
+    /// cursor.seek_to_line(1);
+    /// assert_eq!(cursor.byte_offset(), 4);
+    /// assert_eq!(cursor.next_char(), Some('b'));
+    /// ```
+    #[inline]
+    pub fn cursor(&self) -> Cursor<'a> {
+        Cursor::from(self)
+    }
+
+    /// Returns the byte offset of the first match of the pattern in this
+    /// `RopeSlice`, or `None` if there isn't one.
+    ///
+    /// The pattern can be a [`char`], a string slice or a closure of the form
+    /// `FnMut(char) -> bool`. Matches spanning chunk boundaries are found too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.find('o'), Some(4));
+    /// assert_eq!(s.find("Earth"), Some(6));
+    /// assert_eq!(s.find(char::is_whitespace), Some(5));
+    /// assert_eq!(s.find("Mars"), None);
+    /// ```
+    #[inline]
+    pub fn find<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        self.find_iter(pattern).next()
+    }
+
+    /// Returns an iterator over the byte offsets of the non-overlapping
+    /// matches of the pattern in this `RopeSlice`, from front to back.
+    ///
+    /// This behaves like [`str::match_indices()`], except it only yields the
+    /// offsets of the matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("abcXXXabcYYYabc");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let matches = s.find_iter("abc").collect::<Vec<_>>();
+    /// assert_eq!(matches, [0, 6, 12]);
+    /// ```
+    #[inline]
+    pub fn find_iter<P: Pattern>(&self, pattern: P) -> FindIter<'a, P> {
+        FindIter::new(*self, pattern)
+    }
+
+    /// Returns the byte range of the first match of the regex in this
+    /// `RopeSlice`, or `None` if there isn't one.
+    ///
+    /// The search runs directly on the chunks of the `RopeSlice`, without
+    /// collecting its contents into a string. The exception are regexes
+    /// containing a Unicode word boundary (`\b` or `\B`, unless Unicode is
+    /// disabled like in `(?-u:\b)`): if the text contains non-ASCII bytes
+    /// it's copied into a `String` before searching it, which takes linear
+    /// time and memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{regex::Regex, Rope};
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    /// let s = r.byte_slice(6..);
+    ///
+    /// let re = Regex::new(r"E\w+").unwrap();
+    ///
+    /// let range = s.find_regex(&re).unwrap();
+    /// assert_eq!(s.byte_slice(range), "Earth");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn find_regex(
+        &self,
+        regex: &crate::regex::Regex,
+    ) -> Option<core::ops::Range<usize>> {
+        regex.find(*self)
+    }
+
+    /// Returns an iterator over the byte ranges of the non-overlapping
+    /// matches of the regex in this `RopeSlice`, from front to back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{regex::Regex, Rope};
+    /// #
+    /// let r = Rope::from("foo 42 bar 1337");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let re = Regex::new(r"\d+").unwrap();
+    ///
+    /// let matches = s.find_regex_iter(&re).collect::<Vec<_>>();
+    /// assert_eq!(matches, [4..6, 11..15]);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn find_regex_iter<'r>(
+        &self,
+        regex: &'r crate::regex::Regex,
+    ) -> crate::regex::RegexMatches<'a, 'r> {
+        crate::regex::RegexMatches::new(*self, regex)
+    }
+
     /// Returns an iterator over the extended grapheme clusters of this
     /// `RopeSlice`.
     ///
@@ -536,6 +953,78 @@ impl<'a> RopeSlice<'a> {
         Lines::from(self)
     }
 
+    /// Returns an iterator over the lines of this `RopeSlice`, not including
+    /// the line terminators, starting at the given line offset.
+    ///
+    /// Calling [`next()`](Iterator::next()) on the returned iterator yields
+    /// the lines starting from the `line_offset`-th one, and calling
+    /// [`next_back()`](DoubleEndedIterator::next_back()) yields the lines
+    /// before it, so it can walk over the whole `RopeSlice` in both
+    /// directions. See [`LinesAt`] for more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line offset is out of bounds (i.e. greater than
+    /// [`line_len()`](Self::line_len())).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\r\nbaz\n");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut lines = s.lines_at(1);
+    ///
+    /// assert_eq!("bar", lines.next().unwrap());
+    /// assert_eq!("baz", lines.next().unwrap());
+    /// assert_eq!(None, lines.next());
+    ///
+    /// assert_eq!("baz", lines.next_back().unwrap());
+    /// assert_eq!("bar", lines.next_back().unwrap());
+    /// assert_eq!("foo", lines.next_back().unwrap());
+    /// assert_eq!(None, lines.next_back());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn lines_at(&self, line_offset: usize) -> LinesAt<'a> {
+        LinesAt::new(*self, line_offset)
+    }
+
+    /// Returns the line and column of the given byte offset, where the column
+    /// is measured in the given [`Encoding`].
+    ///
+    /// It's the inverse of [`byte_of_position()`](Self::byte_of_position()).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the byte offset is out of bounds (i.e. greater than
+    /// [`byte_len()`](Self::byte_len())) or if it doesn't lie on a code point
+    /// boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{lsp::Encoding, Rope};
+    /// #
+    /// let r = Rope::from("ƒoo\nfoo\r\n𐐀bar");
+    /// let s = r.byte_slice("ƒoo\n".len()..);
+    ///
+    /// assert_eq!(s.position_of_byte(9, Encoding::Utf8), (1, 4));
+    /// assert_eq!(s.position_of_byte(9, Encoding::Utf16), (1, 2));
+    /// assert_eq!(s.position_of_byte(9, Encoding::Utf32), (1, 1));
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn position_of_byte(
+        &self,
+        byte_offset: usize,
+        encoding: Encoding,
+    ) -> (usize, usize) {
+        super::lsp::position_of_byte(*self, byte_offset, encoding)
+    }
+
     /// Returns an iterator over the lines of this `RopeSlice`, including the
     /// line terminators.
     ///
@@ -574,6 +1063,114 @@ impl<'a> RopeSlice<'a> {
         RawLines::from(self)
     }
 
+    /// Returns a reader over the contents of this `RopeSlice`, implementing the
+    /// [`Read`](std::io::Read), [`BufRead`](std::io::BufRead) and
+    /// [`Seek`](std::io::Seek) traits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{BufRead, Read, Seek, SeekFrom};
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("foo\nbar\nbaz");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let mut reader = s.reader();
+    ///
+    /// let mut line = String::new();
+    /// reader.read_line(&mut line).unwrap();
+    /// assert_eq!(line, "foo\n");
+    ///
+    /// reader.seek(SeekFrom::End(-3)).unwrap();
+    ///
+    /// let mut rest = String::new();
+    /// reader.read_to_string(&mut rest).unwrap();
+    /// assert_eq!(rest, "baz");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn reader(&self) -> crate::RopeReader<'a> {
+        crate::RopeReader::from(*self)
+    }
+
+    /// Returns the capture groups of the first match of the regex in this
+    /// `RopeSlice`, or `None` if there isn't one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::{regex::Regex, Rope};
+    /// #
+    /// let r = Rope::from("name: crop, version: 0.4.2");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let re = Regex::new(r"version: (?<major>\d+)\.(\d+)").unwrap();
+    ///
+    /// let captures = s.regex_captures(&re).unwrap();
+    ///
+    /// assert_eq!(captures.get(0), Some(12..24));
+    /// assert_eq!(s.byte_slice(captures.name("major").unwrap()), "0");
+    /// assert_eq!(s.byte_slice(captures.get(2).unwrap()), "4");
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+    #[cfg(feature = "regex")]
+    #[inline]
+    pub fn regex_captures(
+        &self,
+        regex: &crate::regex::Regex,
+    ) -> Option<crate::regex::RegexCaptures> {
+        let range = regex.find(*self)?;
+        Some(regex.captures(*self, range))
+    }
+
+    /// Returns the byte offset of the last match of the pattern in this
+    /// `RopeSlice`, or `None` if there isn't one.
+    ///
+    /// See [`find()`](Self::find()) for the kinds of patterns that are
+    /// accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("Hello Earth 🌎!");
+    /// let s = r.byte_slice(..);
+    ///
+    /// assert_eq!(s.rfind('o'), Some(4));
+    /// assert_eq!(s.rfind("🌎"), Some(12));
+    /// assert_eq!(s.rfind(char::is_whitespace), Some(11));
+    /// assert_eq!(s.rfind("Mars"), None);
+    /// ```
+    #[inline]
+    pub fn rfind<P: Pattern>(&self, pattern: P) -> Option<usize> {
+        self.rfind_iter(pattern).next()
+    }
+
+    /// Returns an iterator over the byte offsets of the non-overlapping
+    /// matches of the pattern in this `RopeSlice`, from back to front.
+    ///
+    /// This behaves like [`str::rmatch_indices()`], except it only yields the
+    /// offsets of the matches.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use crop::Rope;
+    /// #
+    /// let r = Rope::from("abcXXXabcYYYabc");
+    /// let s = r.byte_slice(..);
+    ///
+    /// let matches = s.rfind_iter("abc").collect::<Vec<_>>();
+    /// assert_eq!(matches, [12, 6, 0]);
+    /// ```
+    #[inline]
+    pub fn rfind_iter<P: Pattern>(&self, pattern: P) -> RFindIter<'a, P> {
+        RFindIter::new(*self, pattern)
+    }
+
     /// Removes the last char from the range spanned by this slice.
     ///
     /// # Panics
@@ -745,6 +1342,13 @@ impl<'a> From<TreeSlice<'a, { Rope::arity() }, RopeChunk>> for RopeSlice<'a> {
     }
 }
 
+impl<'a> From<&'a Rope> for RopeSlice<'a> {
+    #[inline]
+    fn from(rope: &'a Rope) -> Self {
+        rope.byte_slice(..)
+    }
+}
+
 impl core::fmt::Debug for RopeSlice<'_> {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
@@ -769,7 +1373,10 @@ impl core::cmp::PartialEq<RopeSlice<'_>> for RopeSlice<'_> {
     fn eq(&self, rhs: &RopeSlice<'_>) -> bool {
         (self.byte_len() == rhs.byte_len())
             && (self.line_len() == rhs.line_len())
-            && chunks_eq_chunks(self.chunks(), rhs.chunks())
+            && subtrees_eq_subtrees(
+                self.tree_slice.subtrees(),
+                rhs.tree_slice.subtrees(),
+            )
     }
 }
 
@@ -837,3 +1444,90 @@ impl core::cmp::PartialEq<RopeSlice<'_>> for alloc::borrow::Cow<'_, str> {
 }
 
 impl core::cmp::Eq for RopeSlice<'_> {}
+
+impl core::hash::Hash for RopeSlice<'_> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        hash_chunks(self.chunks(), state)
+    }
+}
+
+impl core::cmp::Ord for RopeSlice<'_> {
+    #[inline]
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        chunks_cmp_chunks(self.chunks(), rhs.chunks())
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), rhs.chunks()))
+    }
+}
+
+impl core::cmp::PartialOrd<Rope> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &Rope) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_chunks(self.chunks(), rhs.chunks()))
+    }
+}
+
+impl core::cmp::PartialOrd<str> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &str) -> Option<core::cmp::Ordering> {
+        Some(chunks_cmp_str(self.chunks(), rhs))
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for str {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl core::cmp::PartialOrd<&str> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &&str) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(*rhs)
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for &str {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl core::cmp::PartialOrd<String> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &String) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&**rhs)
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for String {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
+
+impl core::cmp::PartialOrd<alloc::borrow::Cow<'_, str>> for RopeSlice<'_> {
+    #[inline]
+    fn partial_cmp(
+        &self,
+        rhs: &alloc::borrow::Cow<'_, str>,
+    ) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&**rhs)
+    }
+}
+
+impl core::cmp::PartialOrd<RopeSlice<'_>> for alloc::borrow::Cow<'_, str> {
+    #[inline]
+    fn partial_cmp(&self, rhs: &RopeSlice<'_>) -> Option<core::cmp::Ordering> {
+        rhs.partial_cmp(self).map(core::cmp::Ordering::reverse)
+    }
+}
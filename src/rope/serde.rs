@@ -0,0 +1,101 @@
+use core::fmt;
+
+use serde::de::{self, DeserializeSeed, Deserializer, SeqAccess, Visitor};
+use serde::ser::{SerializeSeq, Serializer};
+use serde::{Deserialize, Serialize};
+
+use super::{Rope, RopeBuilder, RopeSlice};
+
+/// `Rope`s and `RopeSlice`s are serialized as a sequence of strings, one for
+/// every chunk, so that we never have to collect them into a contiguous
+/// buffer.
+impl Serialize for RopeSlice<'_> {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        // Some formats need to know the length of the sequence upfront, and
+        // counting the chunks is cheap compared to serializing them.
+        let mut seq = serializer.serialize_seq(Some(self.chunks().count()))?;
+
+        for chunk in self.chunks() {
+            seq.serialize_element(chunk)?;
+        }
+
+        seq.end()
+    }
+}
+
+impl Serialize for Rope {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.byte_slice(..).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rope {
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(RopeVisitor)
+    }
+}
+
+struct RopeVisitor;
+
+impl<'de> Visitor<'de> for RopeVisitor {
+    type Value = Rope;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence of strings")
+    }
+
+    #[inline]
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Rope, A::Error> {
+        let mut builder = RopeBuilder::new();
+
+        while seq.next_element_seed(AppendChunk(&mut builder))?.is_some() {}
+
+        Ok(builder.build())
+    }
+}
+
+/// Deserializes a single chunk, appending it to the builder without
+/// allocating a `String` for it (if the deserializer allows it).
+struct AppendChunk<'a>(&'a mut RopeBuilder);
+
+impl<'de> DeserializeSeed<'de> for AppendChunk<'_> {
+    type Value = ();
+
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for AppendChunk<'_> {
+    type Value = ();
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a string")
+    }
+
+    #[inline]
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<(), E> {
+        self.0.append(s);
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use core::ops::RangeBounds;
+
+use tree_sitter::{InputEdit, Point};
+
+use super::utils::panic_messages as panic;
+use super::{Rope, RopeSlice};
+use crate::range_bounds_to_start_end;
+
+/// Returns a callback which can be passed to tree-sitter's
+/// [`Parser::parse_with()`](tree_sitter::Parser::parse_with) to parse a
+/// `Rope` or `RopeSlice` without first collecting it into a string.
+///
+/// Every call returns the bytes going from the requested offset to the end of
+/// the chunk containing it, or an empty slice once the end of the text is
+/// reached. The chunks are read through a [`Cursor`](crate::Cursor), so
+/// reading the text front to back only takes amortized constant time per
+/// chunk, while jumping to an arbitrary offset takes logarithmic time.
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{tree_sitter::chunk_callback, Rope};
+/// # use tree_sitter::Parser;
+/// #
+/// let r = Rope::from("fn main() {}\n");
+///
+/// let mut parser = Parser::new();
+/// parser.set_language(tree_sitter_rust::language()).unwrap();
+///
+/// let tree = parser.parse_with(&mut chunk_callback(&r), None).unwrap();
+/// assert_eq!(tree.root_node().to_sexp(), "(source_file (function_item \
+///     name: (identifier) parameters: (parameters) body: (block)))");
+/// ```
+#[inline]
+pub fn chunk_callback<'a, S>(text: S) -> impl FnMut(usize, Point) -> &'a [u8]
+where
+    S: Into<RopeSlice<'a>>,
+{
+    let slice = text.into();
+
+    let mut cursor = slice.cursor();
+
+    move |byte_offset, _| {
+        if byte_offset >= slice.byte_len() {
+            return &[];
+        }
+
+        let chunk_start = cursor.chunk_byte_offset();
+
+        if byte_offset < chunk_start
+            || byte_offset >= chunk_start + cursor.chunk().len()
+        {
+            // Chunks always end on code point boundaries, so seeking to the
+            // start of the code point still lands in the right chunk.
+            let mut char_offset = byte_offset;
+
+            while !slice.is_char_boundary(char_offset) {
+                char_offset -= 1;
+            }
+
+            cursor.seek_to_byte(char_offset);
+        }
+
+        let chunk = cursor.chunk().as_bytes();
+
+        &chunk[byte_offset - cursor.chunk_byte_offset()..]
+    }
+}
+
+/// Returns the [`InputEdit`] describing the replacement of the given byte
+/// range of the `Rope` with `text`, which can be passed to
+/// [`Tree::edit()`](tree_sitter::Tree::edit) before reparsing the `Rope`.
+///
+/// The positions of the start and of the old end of the range are computed
+/// from the current contents of the `Rope`, so this has to be called
+/// *before* the edit is applied via [`Rope::replace()`]. Like in tree-sitter,
+/// the rows of the positions are line offsets and their columns are byte
+/// offsets from the start of the line.
+///
+/// # Panics
+///
+/// Panics if the start is greater than the end or if the end is out of
+/// bounds (i.e. greater than [`byte_len()`](Rope::byte_len())).
+///
+/// # Examples
+///
+/// ```
+/// # use crop::{tree_sitter::input_edit, Rope};
+/// # use tree_sitter::Point;
+/// #
+/// let mut r = Rope::from("fn main() {\n    foo();\n}\n");
+///
+/// let edit = input_edit(&r, 16..19, "bar(\n        baz");
+/// r.replace(16..19, "bar(\n        baz");
+///
+/// assert_eq!(r, "fn main() {\n    bar(\n        baz();\n}\n");
+/// assert_eq!(edit.start_position, Point::new(1, 4));
+/// assert_eq!(edit.old_end_position, Point::new(1, 7));
+/// assert_eq!(edit.new_end_position, Point::new(2, 11));
+/// ```
+#[track_caller]
+#[inline]
+pub fn input_edit<R>(rope: &Rope, byte_range: R, text: &str) -> InputEdit
+where
+    R: RangeBounds<usize>,
+{
+    let (start, end) =
+        range_bounds_to_start_end(byte_range, 0, rope.byte_len());
+
+    if start > end {
+        panic::byte_start_after_end(start, end);
+    }
+
+    if end > rope.byte_len() {
+        panic::byte_offset_out_of_bounds(end, rope.byte_len());
+    }
+
+    let start_position = point_of_byte(rope, start);
+
+    let new_end_position = match text.rfind('\n') {
+        Some(last_newline) => Point::new(
+            start_position.row + str_indices::lines_lf::count_breaks(text),
+            text.len() - last_newline - 1,
+        ),
+
+        None => Point::new(
+            start_position.row,
+            start_position.column + text.len(),
+        ),
+    };
+
+    InputEdit {
+        start_byte: start,
+        old_end_byte: end,
+        new_end_byte: start + text.len(),
+        start_position,
+        old_end_position: point_of_byte(rope, end),
+        new_end_position,
+    }
+}
+
+/// Returns the tree-sitter position of the given byte offset.
+#[inline]
+fn point_of_byte(rope: &Rope, byte_offset: usize) -> Point {
+    let row = rope.line_of_byte(byte_offset);
+    Point::new(row, byte_offset - rope.byte_of_line(row))
+}
@@ -1,7 +1,11 @@
 //! This module contains utility functions on strings and code to be shared
 //! between `Rope`s and `RopeSlice`s, `RopeChunk`s and `ChunkSlice`s.
 
+use super::gap_slice::GapSlice;
 use super::iterators::Chunks;
+use super::rope::RopeChunk;
+use super::Rope;
+use crate::tree::{Subtree, Subtrees};
 
 /// Adjusts the candidate byte offset to make sure it's a char boundary for
 /// `s`. Offsets past the end of the string will be clipped to the length of
@@ -37,57 +41,127 @@ pub(super) fn adjust_split_point<const WITH_RIGHT_BIAS: bool>(
     offset
 }
 
-/// Checks equality between the chunks yielded by iterating over two
-/// [`Chunks`].
+/// Checks equality between the text of two [`Subtrees`].
 ///
 /// This is used in the `PartialEq` implementation between `Rope`s and
-/// `RopeSlice`s. It's assumed that if we get this far both chunks yield the
-/// same number of bytes.
+/// `RopeSlice`s. Every time both sides are at the same byte offset we first
+/// check if their next subtrees are the same node, in which case we can skip
+/// them without looking at their bytes. This makes comparing two snapshots of
+/// the same `Rope` proportional to the size of the edits between them rather
+/// than to the size of the `Rope`. It's assumed that if we get this far both
+/// subtrees contain the same number of bytes.
 #[inline]
-pub(super) fn chunks_eq_chunks(
-    mut lhs: Chunks<'_>,
-    mut rhs: Chunks<'_>,
+pub(super) fn subtrees_eq_subtrees(
+    mut lhs: Subtrees<'_, { Rope::arity() }, RopeChunk>,
+    mut rhs: Subtrees<'_, { Rope::arity() }, RopeChunk>,
 ) -> bool {
-    let mut left_chunk = lhs.next().unwrap_or("").as_bytes();
-    let mut right_chunk = rhs.next().unwrap_or("").as_bytes();
+    // The bytes of the leaves we're currently comparing, split in the two
+    // segments of a gap buffer. The first segment is only empty if the second
+    // one is as well.
+    let mut left_bytes: (&[u8], &[u8]) = (&[], &[]);
+    let mut right_bytes: (&[u8], &[u8]) = (&[], &[]);
+
+    let mut left_next = lhs.next();
+    let mut right_next = rhs.next();
 
     loop {
-        if left_chunk.len() < right_chunk.len() {
-            if left_chunk != &right_chunk[..left_chunk.len()] {
-                return false;
-            } else {
-                right_chunk = &right_chunk[left_chunk.len()..];
-                left_chunk = &[];
+        if left_bytes.0.is_empty() && right_bytes.0.is_empty() {
+            let (Some(left), Some(right)) = (left_next, right_next) else {
+                // Both sides have the same number of bytes, so if one of them
+                // is done then so is the other.
+                return true;
+            };
+
+            if left.ptr_eq(&right) {
+                left_next = lhs.next();
+                right_next = rhs.next();
+                continue;
             }
-        } else if &left_chunk[..right_chunk.len()] != right_chunk {
-            return false;
-        } else {
-            left_chunk = &left_chunk[right_chunk.len()..];
-            right_chunk = &[];
-        }
 
-        if left_chunk.is_empty() {
-            match lhs.next() {
-                Some(chunk) => left_chunk = chunk.as_bytes(),
+            // Descend into the bigger of the two nodes, hoping to find a
+            // shared subtree in its children.
+            if left.is_internal()
+                && (!right.is_internal()
+                    || left.base_measure() >= right.base_measure())
+            {
+                lhs.descend(left);
+                left_next = lhs.next();
+                continue;
+            } else if right.is_internal() {
+                rhs.descend(right);
+                right_next = rhs.next();
+                continue;
+            }
+        }
 
-                // This works because both chunks are assumed to yield the same
-                // number of bytes, so if one iterator is done then so is the
-                // other.
+        if left_bytes.0.is_empty() {
+            match next_leaf(&mut lhs, &mut left_next) {
+                Some(leaf) => left_bytes = leaf_bytes(leaf),
                 _ => return true,
             }
+            continue;
         }
 
-        if right_chunk.is_empty() {
-            match rhs.next() {
-                Some(chunk) => right_chunk = chunk.as_bytes(),
-
-                // Same as above.
+        if right_bytes.0.is_empty() {
+            match next_leaf(&mut rhs, &mut right_next) {
+                Some(leaf) => right_bytes = leaf_bytes(leaf),
                 _ => return true,
             }
+            continue;
+        }
+
+        let len = left_bytes.0.len().min(right_bytes.0.len());
+
+        if left_bytes.0[..len] != right_bytes.0[..len] {
+            return false;
+        }
+
+        left_bytes.0 = &left_bytes.0[len..];
+        if left_bytes.0.is_empty() {
+            left_bytes = (left_bytes.1, &[]);
+        }
+
+        right_bytes.0 = &right_bytes.0[len..];
+        if right_bytes.0.is_empty() {
+            right_bytes = (right_bytes.1, &[]);
+        }
+    }
+}
+
+/// Returns the next leaf slice of the subtrees, descending into `next` if it's
+/// an internal node.
+#[inline]
+fn next_leaf<'a>(
+    subtrees: &mut Subtrees<'a, { Rope::arity() }, RopeChunk>,
+    next: &mut Option<Subtree<'a, { Rope::arity() }, RopeChunk>>,
+) -> Option<GapSlice<'a>> {
+    loop {
+        let subtree = (*next)?;
+
+        if subtree.is_internal() {
+            subtrees.descend(subtree);
+            *next = subtrees.next();
+        } else {
+            *next = subtrees.next();
+            return subtree.leaf();
         }
     }
 }
 
+/// Returns the bytes of the two segments of a leaf slice, making sure the
+/// first one is only empty if the second one is as well.
+#[inline]
+fn leaf_bytes(leaf: GapSlice<'_>) -> (&[u8], &[u8]) {
+    let (left, right) =
+        (leaf.left_chunk().as_bytes(), leaf.right_chunk().as_bytes());
+
+    if left.is_empty() {
+        (right, &[])
+    } else {
+        (left, right)
+    }
+}
+
 /// Checks equality between the chunks yielded by iterating over a [`Chunks`]
 /// and a string slice.
 ///
@@ -107,6 +181,145 @@ pub(super) fn chunks_eq_str(chunks: Chunks<'_>, s: &str) -> bool {
     true
 }
 
+/// Lexicographically compares the bytes yielded by iterating over two
+/// [`Chunks`].
+///
+/// This is used in the `Ord` and `PartialOrd` implementations between `Rope`s
+/// and `RopeSlice`s. The two iterators can yield a different number of bytes.
+#[inline]
+pub(super) fn chunks_cmp_chunks(
+    mut lhs: Chunks<'_>,
+    mut rhs: Chunks<'_>,
+) -> core::cmp::Ordering {
+    let mut left_chunk: &[u8] = &[];
+    let mut right_chunk: &[u8] = &[];
+
+    loop {
+        if left_chunk.is_empty() {
+            match lhs.next() {
+                Some(chunk) => {
+                    left_chunk = chunk.as_bytes();
+                    continue;
+                },
+
+                None => {
+                    // The left side is a prefix of the right side, so it's
+                    // smaller unless the right side is also done.
+                    return if right_chunk.is_empty()
+                        && rhs.all(|chunk| chunk.is_empty())
+                    {
+                        core::cmp::Ordering::Equal
+                    } else {
+                        core::cmp::Ordering::Less
+                    };
+                },
+            }
+        }
+
+        if right_chunk.is_empty() {
+            match rhs.next() {
+                Some(chunk) => {
+                    right_chunk = chunk.as_bytes();
+                    continue;
+                },
+
+                // The left chunk isn't empty, so the left side is longer.
+                None => return core::cmp::Ordering::Greater,
+            }
+        }
+
+        let len = left_chunk.len().min(right_chunk.len());
+
+        match left_chunk[..len].cmp(&right_chunk[..len]) {
+            core::cmp::Ordering::Equal => {
+                left_chunk = &left_chunk[len..];
+                right_chunk = &right_chunk[len..];
+            },
+
+            ordering => return ordering,
+        }
+    }
+}
+
+/// Lexicographically compares the bytes yielded by iterating over a
+/// [`Chunks`] with the bytes of a string slice.
+///
+/// This is used in the `PartialOrd` implementation between
+/// `Rope`/`RopeSlice`s and strings.
+#[inline]
+pub(super) fn chunks_cmp_str(
+    chunks: Chunks<'_>,
+    s: &str,
+) -> core::cmp::Ordering {
+    let mut s = s.as_bytes();
+
+    for chunk in chunks {
+        let chunk = chunk.as_bytes();
+
+        let len = chunk.len().min(s.len());
+
+        match chunk[..len].cmp(&s[..len]) {
+            core::cmp::Ordering::Equal if len < chunk.len() => {
+                return core::cmp::Ordering::Greater
+            },
+
+            core::cmp::Ordering::Equal => s = &s[len..],
+
+            ordering => return ordering,
+        }
+    }
+
+    if s.is_empty() {
+        core::cmp::Ordering::Equal
+    } else {
+        core::cmp::Ordering::Less
+    }
+}
+
+/// Feeds the bytes yielded by iterating over a [`Chunks`] to a hasher.
+///
+/// The bytes are written in blocks of a fixed size, so the sequence of calls
+/// to [`Hasher::write`](core::hash::Hasher::write) only depends on the text
+/// and not on how it's split into chunks, and the text is then terminated
+/// with a `0xff` byte. This makes the hashes of `Rope`s and `RopeSlice`s
+/// consistent with each other, but not with the one of the equivalent `str`,
+/// which hashers are free to compute differently.
+#[inline]
+pub(super) fn hash_chunks<H: core::hash::Hasher>(
+    chunks: Chunks<'_>,
+    state: &mut H,
+) {
+    const BUFFER_LEN: usize = 256;
+
+    let mut buffer = [0u8; BUFFER_LEN];
+    let mut buffered = 0;
+    let mut flushed = false;
+
+    for chunk in chunks {
+        let mut chunk = chunk.as_bytes();
+
+        while !chunk.is_empty() {
+            let len = chunk.len().min(BUFFER_LEN - buffered);
+            buffer[buffered..buffered + len].copy_from_slice(&chunk[..len]);
+            buffered += len;
+            chunk = &chunk[len..];
+
+            if buffered == BUFFER_LEN {
+                state.write(&buffer);
+                buffered = 0;
+                flushed = true;
+            }
+        }
+    }
+
+    // The empty string still writes an empty slice.
+    if buffered > 0 || !flushed {
+        state.write(&buffer[..buffered]);
+    }
+
+    state.write_u8(0xff);
+}
+
 /// Iterates over the string slices yielded by [`Chunks`], writing the debug
 /// output of each chunk to a formatter.
 #[inline]
@@ -284,6 +497,38 @@ pub mod panic_messages {
         );
     }
 
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn char_offset_out_of_bounds(
+        char_offset: usize,
+        char_len: usize,
+    ) -> ! {
+        debug_assert!(char_offset > char_len);
+
+        panic!(
+            "char offset out of bounds: the offset is {char_offset} but the \
+             length is {char_len}"
+        );
+    }
+
+    #[cfg(feature = "char-metric")]
+    #[track_caller]
+    #[cold]
+    #[inline(never)]
+    pub(crate) fn char_start_after_end(
+        char_start: usize,
+        char_end: usize,
+    ) -> ! {
+        debug_assert!(char_start > char_end);
+
+        panic!(
+            "char start after end: the start is {char_start} but the end is \
+             {char_end}"
+        );
+    }
+
     #[track_caller]
     #[cold]
     #[inline(never)]
@@ -0,0 +1,240 @@
+use super::leaves::{descend, next_bunch, previous_bunch};
+use super::{Arc, Inode, Leaf, Lnode, Metric, Node, TreeSlice};
+
+/// A cursor pointing to a single leaf of a `TreeSlice` which can be moved to
+/// the previous and next leaves or repositioned at any measure.
+///
+/// It walks the tree like [`Leaves`](super::Leaves) does, i.e. by keeping the
+/// path from the root down to the internal node containing the current leaf,
+/// so moving to a neighbouring leaf is amortized constant time. On top of that
+/// it keeps track of the offset of the current leaf, which lets it step in
+/// both directions and seek.
+pub struct LeafCursor<'a, const ARITY: usize, L: Leaf> {
+    /// The `TreeSlice` we're moving over.
+    slice: TreeSlice<'a, ARITY, L>,
+
+    /// The path from the root down to (but not including) the internal node
+    /// containing [`leaves`](Self::leaves), in the same format used by
+    /// `Leaves`.
+    path: Vec<(&'a Inode<ARITY, L>, usize)>,
+
+    /// The children of the internal node containing the current leaf, or
+    /// just the root if it's a leaf. All the nodes in the slice are
+    /// guaranteed to be leaf nodes.
+    leaves: &'a [Arc<Node<ARITY, L>>],
+
+    /// The index of the current leaf in [`leaves`](Self::leaves).
+    idx: usize,
+
+    /// The summary of all the leaves under the root preceding the current
+    /// one.
+    leaf_offset: L::Summary,
+
+    /// The index of the current leaf among all the leaves under the root.
+    leaf_idx: usize,
+
+    /// The index of the leaf containing the slice's
+    /// [`start_slice`](TreeSlice::start_slice).
+    first_idx: usize,
+}
+
+impl<const ARITY: usize, L: Leaf> Clone for LeafCursor<'_, ARITY, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            slice: self.slice.clone(),
+            path: self.path.clone(),
+            leaf_offset: self.leaf_offset.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> From<&TreeSlice<'a, ARITY, L>>
+    for LeafCursor<'a, ARITY, L>
+{
+    #[inline]
+    fn from(slice: &TreeSlice<'a, ARITY, L>) -> LeafCursor<'a, ARITY, L> {
+        let mut cursor = Self {
+            slice: slice.clone(),
+            path: Vec::with_capacity(slice.root().depth()),
+            leaves: &[],
+            idx: 0,
+            leaf_offset: L::Summary::default(),
+            leaf_idx: 0,
+            first_idx: 0,
+        };
+
+        cursor.seek_to_start();
+        cursor.first_idx = cursor.leaf_idx;
+        cursor
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> LeafCursor<'a, ARITY, L> {
+    /// Moves the cursor to the first leaf of the slice for which `contains`
+    /// returns `true` when called with the summary of the leaves preceding it
+    /// and its own summary, or to the last leaf if there isn't one.
+    #[inline]
+    fn descend<F>(&mut self, contains: F)
+    where
+        F: Fn(&L::Summary, &L::Summary) -> bool,
+    {
+        self.path.clear();
+
+        let root = self.slice.root();
+
+        let Node::Internal(inode) = &**root else {
+            self.leaves = core::slice::from_ref(root);
+            self.idx = 0;
+            self.leaf_offset = L::Summary::default();
+            self.leaf_idx = 0;
+            return;
+        };
+
+        let mut offset = L::Summary::default();
+        let mut leaf_idx = 0;
+
+        let (inode, idx) = descend(&mut self.path, inode, |child| {
+            if contains(&offset, child.summary()) {
+                true
+            } else {
+                offset += child.summary();
+                leaf_idx += child.leaf_count();
+                false
+            }
+        });
+
+        self.leaves = inode.children();
+        self.idx = idx;
+        self.leaf_offset = offset;
+        self.leaf_idx = leaf_idx;
+    }
+
+    /// Returns whether the current leaf is the first one in the slice.
+    #[inline]
+    fn is_first(&self) -> bool {
+        self.leaf_idx == self.first_idx
+    }
+
+    /// Returns whether the current leaf is the last one in the slice.
+    #[inline]
+    fn is_last(&self) -> bool {
+        self.leaf_idx + 1 >= self.first_idx + self.slice.leaf_count()
+    }
+
+    /// Returns the current leaf together with its summary. The first and last
+    /// leaves are clipped to the range of the slice.
+    #[inline]
+    pub fn leaf(&self) -> (L::Slice<'a>, L::Summary) {
+        self.slice.clip_leaf(self.leaf_idx - self.first_idx, self.lnode())
+    }
+
+    /// Returns the current leaf node.
+    #[inline]
+    fn lnode(&self) -> &'a Lnode<L> {
+        self.leaves[self.idx].get_leaf()
+    }
+
+    /// Moves the cursor to the next leaf, returning `false` without moving if
+    /// the current leaf is the last one in the slice.
+    #[inline]
+    pub fn next_leaf(&mut self) -> bool {
+        if self.is_last() {
+            return false;
+        }
+
+        self.leaf_offset += self.lnode().summary();
+        self.leaf_idx += 1;
+
+        if self.idx + 1 < self.leaves.len() {
+            self.idx += 1;
+        } else {
+            self.leaves = next_bunch(&mut self.path).children();
+            self.idx = 0;
+        }
+
+        true
+    }
+
+    /// Returns the summary of the slice up to the start of the current leaf.
+    #[inline]
+    pub fn offset(&self) -> L::Summary {
+        if self.is_first() {
+            L::Summary::default()
+        } else {
+            self.leaf_offset.clone() - &self.slice.offset
+        }
+    }
+
+    /// Moves the cursor to the previous leaf, returning `false` without moving
+    /// if the current leaf is the first one in the slice.
+    #[inline]
+    pub fn previous_leaf(&mut self) -> bool {
+        if self.is_first() {
+            return false;
+        }
+
+        if self.idx > 0 {
+            self.idx -= 1;
+        } else {
+            self.leaves = previous_bunch(&mut self.path).children();
+            self.idx = self.leaves.len() - 1;
+        }
+
+        self.leaf_offset -= self.lnode().summary();
+        self.leaf_idx -= 1;
+
+        true
+    }
+
+    /// Moves the cursor to the leaf containing the given `M`-offset (measured
+    /// from the start of the slice).
+    ///
+    /// If the offset falls on the boundary between two leaves the cursor is
+    /// moved to the first one, unless the offset is zero, in which case it's
+    /// moved to the first leaf of the slice.
+    ///
+    /// It's the caller's responsibility to check that the offset is within
+    /// the bounds of the slice.
+    #[inline]
+    pub fn seek<M>(&mut self, measure: M)
+    where
+        M: Metric<L::Summary>,
+    {
+        if measure == M::zero() {
+            return self.seek_to_start();
+        }
+
+        let target = M::measure(&self.slice.offset) + measure;
+
+        let contains = |offset: &L::Summary, summary: &L::Summary| {
+            M::measure(offset) + M::measure(summary) >= target
+        };
+
+        // There's nothing to do if the target is in the current leaf.
+        if contains(&self.leaf_offset, self.lnode().summary())
+            && !contains(&self.leaf_offset, &L::Summary::default())
+        {
+            return;
+        }
+
+        self.descend(contains);
+
+        debug_assert!(self.first_idx <= self.leaf_idx);
+        debug_assert!(
+            self.leaf_idx < self.first_idx + self.slice.leaf_count()
+        );
+    }
+
+    /// Moves the cursor to the first leaf of the slice.
+    #[inline]
+    pub fn seek_to_start(&mut self) {
+        let start = L::BaseMetric::measure(&self.slice.offset);
+
+        self.descend(|offset, summary| {
+            L::BaseMetric::measure(offset) + L::BaseMetric::measure(summary)
+                > start
+        });
+    }
+}
@@ -73,6 +73,17 @@ impl<'a, const ARITY: usize, L: Leaf> Iterator for Leaves<'a, ARITY, L> {
         }
     }
 
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.leaves_yielded = self.leaves_total;
+            None
+        } else {
+            self.leaves_yielded += n + 1;
+            self.forward.nth(n)
+        }
+    }
+
     #[inline]
     fn size_hint(&self) -> (usize, Option<usize>) {
         let exact = self.len();
@@ -92,6 +103,17 @@ impl<const ARITY: usize, L: Leaf> DoubleEndedIterator
             self.backward.previous()
         }
     }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.leaves_yielded = self.leaves_total;
+            None
+        } else {
+            self.leaves_yielded += n + 1;
+            self.backward.nth_previous(n)
+        }
+    }
 }
 
 impl<const ARITY: usize, L: Leaf> ExactSizeIterator for Leaves<'_, ARITY, L> {
@@ -206,7 +228,7 @@ impl<'a, const N: usize, L: Leaf> LeavesForward<'a, N, L> {
 
         self.is_initialized = true;
 
-        let mut inode = match self.root {
+        let inode = match self.root {
             Node::Internal(inode) => inode,
 
             Node::Leaf(leaf) => {
@@ -216,108 +238,41 @@ impl<'a, const N: usize, L: Leaf> LeavesForward<'a, N, L> {
             },
         };
 
-        let mut offset = L::BaseMetric::zero();
+        let base_offset = self.base_offset;
 
-        'outer: loop {
-            match &**inode.first() {
-                Node::Internal(_) => {
-                    for (idx, i) in inode
-                        .children()
-                        .iter()
-                        .map(|n| {
-                            // The first child is an internal node, so all its
-                            // siblings are internal nodes as well.
-                            n.get_internal()
-                        })
-                        .enumerate()
-                    {
-                        let this = i.base_measure();
-
-                        if offset + this > self.base_offset {
-                            self.path.push((inode, idx));
-                            inode = i;
-                            continue 'outer;
-                        } else {
-                            offset += this;
-                        }
-                    }
+        let mut offset = L::BaseMetric::zero();
 
-                    unreachable!();
-                },
+        let (inode, idx) = descend(&mut self.path, inode, |child| {
+            let this = child.base_measure();
 
-                Node::Leaf(_) => {
-                    for (idx, leaf) in inode
-                        .children()
-                        .iter()
-                        .map(|n| {
-                            // The first child is a leaf node, so all its
-                            // siblings are leaf nodes as well.
-                            n.get_leaf()
-                        })
-                        .enumerate()
-                    {
-                        offset += leaf.base_measure();
+            if offset + this > base_offset {
+                true
+            } else {
+                offset += this;
+                false
+            }
+        });
 
-                        if offset > self.base_offset {
-                            let first = self
-                                .first_slice
-                                .take()
-                                .unwrap_or(leaf.as_slice());
+        let leaf = inode.child(idx).get_leaf();
 
-                            let n = core::cmp::min(
-                                inode.len() - idx - 1,
-                                self.whole_total - self.whole_yielded,
-                            );
+        let first = self.first_slice.take().unwrap_or(leaf.as_slice());
 
-                            return (
-                                first,
-                                &inode.children()[idx + 1..(idx + 1 + n)],
-                            );
-                        }
-                    }
+        let n = core::cmp::min(
+            inode.len() - idx - 1,
+            self.whole_total - self.whole_yielded,
+        );
 
-                    unreachable!();
-                },
-            }
-        }
+        (first, &inode.children()[idx + 1..(idx + 1 + n)])
     }
 
     #[inline]
     fn next_bunch(&mut self) -> &'a [Arc<Node<N, L>>] {
-        let mut inode = loop {
-            let &mut (inode, ref mut visited) = self.path.last_mut().unwrap();
-
-            *visited += 1;
-
-            if *visited == inode.len() {
-                self.path.pop();
-            } else {
-                let inode = inode.child(*visited);
+        let inode = next_bunch(&mut self.path);
 
-                // The last internal node in `path` is always *2* levels above
-                // a leaf, so all its children are internal nodes 1 level above
-                // a leaf.
-                break inode.get_internal();
-            }
-        };
-
-        loop {
-            match &**inode.first() {
-                Node::Internal(i) => {
-                    self.path.push((inode, 0));
-                    inode = i;
-                },
-
-                Node::Leaf(_) => {
-                    let n = core::cmp::min(
-                        inode.len(),
-                        self.whole_total - self.whole_yielded,
-                    );
+        let n =
+            core::cmp::min(inode.len(), self.whole_total - self.whole_yielded);
 
-                    return &inode.children()[..n];
-                },
-            }
-        }
+        &inode.children()[..n]
     }
 
     #[inline]
@@ -347,6 +302,100 @@ impl<'a, const N: usize, L: Leaf> LeavesForward<'a, N, L> {
             None
         }
     }
+
+    /// Skips the next `n` leaves and returns the one after them.
+    ///
+    /// Instead of yielding the leaves one by one this walks up the path until
+    /// it finds a node containing the target leaf, skipping whole subtrees
+    /// using their leaf counts.
+    #[inline]
+    fn nth(&mut self, mut n: usize) -> Option<L::Slice<'a>> {
+        if !self.is_initialized {
+            let first = self.next();
+
+            if n == 0 {
+                return first;
+            }
+
+            n -= 1;
+        }
+
+        let in_bunch = self.leaves.len() - self.next_leaf_idx;
+
+        if n < in_bunch {
+            self.next_leaf_idx += n;
+            self.whole_yielded += n;
+            return self.next();
+        }
+
+        n -= in_bunch;
+        self.next_leaf_idx = self.leaves.len();
+        self.whole_yielded += in_bunch;
+
+        let whole_remaining = self.whole_total - self.whole_yielded;
+
+        if n >= whole_remaining {
+            self.whole_yielded = self.whole_total;
+
+            if n > whole_remaining {
+                self.last_slice.take();
+            }
+
+            return self.next();
+        }
+
+        let mut inode = 'outer: loop {
+            let &mut (inode, ref mut visited) = self.path.last_mut().unwrap();
+
+            while *visited + 1 < inode.len() {
+                *visited += 1;
+
+                let child = inode.child(*visited);
+
+                if child.leaf_count() > n {
+                    // The last internal node in `path` is always *2* levels
+                    // above a leaf, so all its children are internal nodes.
+                    break 'outer child.get_internal();
+                }
+
+                n -= child.leaf_count();
+                self.whole_yielded += child.leaf_count();
+            }
+
+            self.path.pop();
+        };
+
+        'outer: loop {
+            match &**inode.first() {
+                Node::Internal(_) => {
+                    for (idx, child) in inode.children().iter().enumerate() {
+                        if child.leaf_count() > n {
+                            self.path.push((inode, idx));
+                            inode = child.get_internal();
+                            continue 'outer;
+                        }
+
+                        n -= child.leaf_count();
+                        self.whole_yielded += child.leaf_count();
+                    }
+
+                    unreachable!();
+                },
+
+                Node::Leaf(_) => {
+                    let len = core::cmp::min(
+                        inode.len(),
+                        self.whole_total - self.whole_yielded,
+                    );
+
+                    self.leaves = &inode.children()[..len];
+                    self.next_leaf_idx = n;
+                    self.whole_yielded += n;
+                    return self.next();
+                },
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -531,40 +580,12 @@ impl<'a, const N: usize, L: Leaf> LeavesBackward<'a, N, L> {
 
     #[inline]
     fn previous_bunch(&mut self) -> &'a [Arc<Node<N, L>>] {
-        let mut inode = loop {
-            let &mut (inode, ref mut visited) = self.path.last_mut().unwrap();
-
-            if *visited == 0 {
-                self.path.pop();
-            } else {
-                *visited -= 1;
-
-                let inode = inode.child(*visited);
-
-                // The last internal node in `path` is always *2* levels above
-                // a leaf, so all its children are internal nodes 1 level above
-                // a leaf.
-                break inode.get_internal();
-            }
-        };
+        let inode = previous_bunch(&mut self.path);
 
-        loop {
-            match &**inode.last() {
-                Node::Internal(i) => {
-                    self.path.push((inode, inode.len() - 1));
-                    inode = i;
-                },
+        let n =
+            core::cmp::min(inode.len(), self.whole_total - self.whole_yielded);
 
-                Node::Leaf(_) => {
-                    let n = core::cmp::min(
-                        inode.len(),
-                        self.whole_total - self.whole_yielded,
-                    );
-
-                    return &inode.children()[(inode.len() - n)..];
-                },
-            }
-        }
+        &inode.children()[(inode.len() - n)..]
     }
 
     #[inline]
@@ -595,6 +616,209 @@ impl<'a, const N: usize, L: Leaf> LeavesBackward<'a, N, L> {
             None
         }
     }
+
+    /// Skips the previous `n` leaves and returns the one before them.
+    ///
+    /// This is the mirror image of
+    /// [`LeavesForward::nth()`](LeavesForward::nth()).
+    #[inline]
+    fn nth_previous(&mut self, mut n: usize) -> Option<L::Slice<'a>> {
+        if !self.is_initialized {
+            let last = self.previous();
+
+            if n == 0 {
+                return last;
+            }
+
+            n -= 1;
+        }
+
+        let in_bunch = self.last_leaf_idx;
+
+        if n < in_bunch {
+            self.last_leaf_idx -= n;
+            self.whole_yielded += n;
+            return self.previous();
+        }
+
+        n -= in_bunch;
+        self.last_leaf_idx = 0;
+        self.whole_yielded += in_bunch;
+
+        let whole_remaining = self.whole_total - self.whole_yielded;
+
+        if n >= whole_remaining {
+            self.whole_yielded = self.whole_total;
+
+            if n > whole_remaining {
+                self.first_slice.take();
+            }
+
+            return self.previous();
+        }
+
+        let mut inode = 'outer: loop {
+            let &mut (inode, ref mut visited) = self.path.last_mut().unwrap();
+
+            while *visited > 0 {
+                *visited -= 1;
+
+                let child = inode.child(*visited);
+
+                if child.leaf_count() > n {
+                    // The last internal node in `path` is always *2* levels
+                    // above a leaf, so all its children are internal nodes.
+                    break 'outer child.get_internal();
+                }
+
+                n -= child.leaf_count();
+                self.whole_yielded += child.leaf_count();
+            }
+
+            self.path.pop();
+        };
+
+        'outer: loop {
+            match &**inode.last() {
+                Node::Internal(_) => {
+                    for (idx, child) in
+                        inode.children().iter().enumerate().rev()
+                    {
+                        if child.leaf_count() > n {
+                            self.path.push((inode, idx));
+                            inode = child.get_internal();
+                            continue 'outer;
+                        }
+
+                        n -= child.leaf_count();
+                        self.whole_yielded += child.leaf_count();
+                    }
+
+                    unreachable!();
+                },
+
+                Node::Leaf(_) => {
+                    let len = core::cmp::min(
+                        inode.len(),
+                        self.whole_total - self.whole_yielded,
+                    );
+
+                    self.leaves = &inode.children()[(inode.len() - len)..];
+                    self.last_leaf_idx = len - n;
+                    self.whole_yielded += n;
+                    return self.previous();
+                },
+            }
+        }
+    }
+}
+
+/// Descends from `inode` down to the internal node right above the leaves,
+/// pushing the internal nodes it goes through (but not the last one) onto
+/// `path`.
+///
+/// At every level it picks the first child for which `contains` returns
+/// `true`, or the last child if there isn't one. Returns the internal node
+/// right above the leaves and the index of the leaf it picked in it.
+#[inline]
+pub(super) fn descend<'a, const N: usize, L, F>(
+    path: &mut Vec<(&'a Inode<N, L>, usize)>,
+    mut inode: &'a Inode<N, L>,
+    mut contains: F,
+) -> (&'a Inode<N, L>, usize)
+where
+    L: Leaf,
+    F: FnMut(&'a Node<N, L>) -> bool,
+{
+    loop {
+        let last_idx = inode.len() - 1;
+
+        let idx = inode.children()[..last_idx]
+            .iter()
+            .position(|child| contains(child))
+            .unwrap_or(last_idx);
+
+        match &**inode.child(idx) {
+            Node::Internal(child) => {
+                path.push((inode, idx));
+                inode = child;
+            },
+
+            Node::Leaf(_) => return (inode, idx),
+        }
+    }
+}
+
+/// Moves `path` to the next internal node right above the leaves, and returns
+/// it.
+///
+/// Panics if the last node in `path` is the last one at its depth.
+#[inline]
+pub(super) fn next_bunch<'a, const N: usize, L: Leaf>(
+    path: &mut Vec<(&'a Inode<N, L>, usize)>,
+) -> &'a Inode<N, L> {
+    let mut inode = loop {
+        let &mut (inode, ref mut visited) = path.last_mut().unwrap();
+
+        *visited += 1;
+
+        if *visited == inode.len() {
+            path.pop();
+        } else {
+            let inode = inode.child(*visited);
+
+            // The last internal node in `path` is always *2* levels above a
+            // leaf, so all its children are internal nodes 1 level above a
+            // leaf.
+            break inode.get_internal();
+        }
+    };
+
+    loop {
+        match &**inode.first() {
+            Node::Internal(i) => {
+                path.push((inode, 0));
+                inode = i;
+            },
+
+            Node::Leaf(_) => return inode,
+        }
+    }
+}
+
+/// Moves `path` to the previous internal node right above the leaves, and
+/// returns it.
+///
+/// Panics if the last node in `path` is the first one at its depth.
+#[inline]
+pub(super) fn previous_bunch<'a, const N: usize, L: Leaf>(
+    path: &mut Vec<(&'a Inode<N, L>, usize)>,
+) -> &'a Inode<N, L> {
+    let mut inode = loop {
+        let &mut (inode, ref mut visited) = path.last_mut().unwrap();
+
+        if *visited == 0 {
+            path.pop();
+        } else {
+            *visited -= 1;
+
+            let inode = inode.child(*visited);
+
+            // Same as above.
+            break inode.get_internal();
+        }
+    };
+
+    loop {
+        match &**inode.last() {
+            Node::Internal(i) => {
+                path.push((inode, inode.len() - 1));
+                inode = i;
+            },
+
+            Node::Leaf(_) => return inode,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -657,4 +881,40 @@ mod tests {
             assert_eq!(None, leaves.next_back());
         }
     }
+
+    #[test]
+    fn leaves_nth() {
+        for n in 1..MAX {
+            let tree = Tree::<4, usize>::from_leaves(0..n);
+            for step in 0..n {
+                let mut leaves = tree.leaves();
+                let mut i = step;
+                while let Some(leaf) = leaves.nth(step) {
+                    assert_eq!(i, *leaf.0);
+                    assert_eq!(n - i - 1, leaves.len());
+                    i += step + 1;
+                }
+                assert_eq!(0, leaves.len());
+                assert_eq!(None, leaves.next());
+            }
+        }
+    }
+
+    #[test]
+    fn leaves_nth_back() {
+        for n in 1..MAX {
+            let tree = Tree::<4, usize>::from_leaves(0..n);
+            for step in 0..n {
+                let mut leaves = tree.leaves();
+                let mut i = n;
+                while let Some(leaf) = leaves.nth_back(step) {
+                    i -= step + 1;
+                    assert_eq!(i, *leaf.0);
+                    assert_eq!(i, leaves.len());
+                }
+                assert_eq!(0, leaves.len());
+                assert_eq!(None, leaves.next_back());
+            }
+        }
+    }
 }
@@ -1,7 +1,9 @@
+mod leaf_cursor;
 mod leaves;
 mod node;
 mod node_internal;
 mod node_leaf;
+mod subtrees;
 mod tiny_arc;
 mod traits;
 mod tree;
@@ -10,10 +12,12 @@ mod tree_slice;
 mod units;
 
 use iter_chain::ExactChain;
+pub use leaf_cursor::LeafCursor;
 pub use leaves::Leaves;
 use node::Node;
 use node_internal::Inode;
 use node_leaf::Lnode;
+pub use subtrees::{Subtree, Subtrees};
 use tiny_arc::Arc;
 pub use traits::*;
 pub use tree::Tree;
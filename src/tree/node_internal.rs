@@ -518,6 +518,12 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
             self.leaf_count -= child.leaf_count();
         }
 
+        if !L::INVERTIBLE_SUMMARY {
+            self.summary = Self::sum(
+                self.children[..start].iter().chain(&self.children[end..]),
+            );
+        }
+
         self.children.drain(start..end)
     }
 
@@ -638,6 +644,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         self.leaf_count += child.leaf_count();
         self.summary += child.summary();
         self.children.insert(child_offset, child);
+        self.resum();
     }
 
     /// Inserts a node shallower than this inode's children at the right depth
@@ -899,9 +906,30 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         let child = self.children.remove(child_idx);
         self.leaf_count -= child.leaf_count();
         self.summary -= child.summary();
+        self.resum();
         child
     }
 
+    /// Recomputes the summary of this inode from the ones of its children if
+    /// the summaries can't be subtracted from each other, which would've left
+    /// it out of date (see [`Summarize::INVERTIBLE_SUMMARY`]).
+    #[inline]
+    fn resum(&mut self) {
+        if !L::INVERTIBLE_SUMMARY {
+            self.summary = Self::sum(self.children.iter());
+        }
+    }
+
+    /// Returns the sum of the summaries of the given nodes, in order.
+    #[inline]
+    fn sum<'a, I>(nodes: I) -> L::Summary
+    where
+        I: Iterator<Item = &'a Arc<Node<N, L>>>,
+        L: 'a,
+    {
+        nodes.fold(L::Summary::default(), |sum, node| sum + node.summary())
+    }
+
     #[inline]
     pub(super) fn summary(&self) -> &L::Summary {
         &self.summary
@@ -929,6 +957,7 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         self.summary += new_child.summary();
         self.leaf_count += new_child.leaf_count();
         self.children[child_idx] = new_child;
+        self.resum();
     }
 
     /// Returns mutable references to the child nodes at `first_idx` and
@@ -975,6 +1004,8 @@ impl<const N: usize, L: Leaf> Inode<N, L> {
         self.summary += child.summary();
         self.leaf_count += child.leaf_count();
 
+        self.resum();
+
         ret
     }
 }
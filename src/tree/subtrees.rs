@@ -0,0 +1,260 @@
+use super::{Arc, Inode, Leaf, Metric, Node, Summarize, Tree, TreeSlice};
+
+/// A subtree yielded by the [`Subtrees`] iterator. It's either a whole node
+/// of the `Tree` or a sub-slice of a leaf.
+#[derive(Debug)]
+pub struct Subtree<'a, const ARITY: usize, L: Leaf> {
+    kind: SubtreeKind<'a, ARITY, L>,
+}
+
+#[derive(Debug)]
+enum SubtreeKind<'a, const N: usize, L: Leaf> {
+    Node(&'a Arc<Node<N, L>>),
+    Slice(L::Slice<'a>),
+}
+
+impl<const ARITY: usize, L: Leaf> Clone for Subtree<'_, ARITY, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const ARITY: usize, L: Leaf> Copy for Subtree<'_, ARITY, L> {}
+
+impl<const N: usize, L: Leaf> Clone for SubtreeKind<'_, N, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const N: usize, L: Leaf> Copy for SubtreeKind<'_, N, L> {}
+
+impl<'a, const ARITY: usize, L: Leaf> Subtree<'a, ARITY, L> {
+    #[inline]
+    pub fn base_measure(&self) -> L::BaseMetric {
+        match self.kind {
+            SubtreeKind::Node(node) => node.base_measure(),
+            SubtreeKind::Slice(slice) => {
+                L::BaseMetric::measure(&slice.summarize())
+            },
+        }
+    }
+
+    /// Returns `true` if this is an internal node, i.e. if it can be passed
+    /// to [`Subtrees::descend()`].
+    #[inline]
+    pub fn is_internal(&self) -> bool {
+        match self.kind {
+            SubtreeKind::Node(node) => node.is_internal(),
+            SubtreeKind::Slice(_) => false,
+        }
+    }
+
+    /// Returns the leaf slice of this subtree if it's either a leaf node or a
+    /// sub-slice of a leaf, or `None` if it's an internal node.
+    #[inline]
+    pub fn leaf(&self) -> Option<L::Slice<'a>> {
+        match self.kind {
+            SubtreeKind::Node(node) => match &**node {
+                Node::Leaf(leaf) => Some(leaf.as_slice()),
+                Node::Internal(_) => None,
+            },
+            SubtreeKind::Slice(slice) => Some(slice),
+        }
+    }
+
+    /// Returns the address of the node if this subtree is a whole node, or
+    /// `None` if it's a sub-slice of a leaf. Two subtrees have the same
+    /// address if and only if they're [`ptr_eq`](Self::ptr_eq()).
+    #[inline]
+    pub fn node_address(&self) -> Option<usize> {
+        match self.kind {
+            SubtreeKind::Node(node) => {
+                Some(&**node as *const Node<ARITY, L> as usize)
+            },
+            SubtreeKind::Slice(_) => None,
+        }
+    }
+
+    /// Returns `true` if both subtrees are the same node, i.e. if they point
+    /// to the same allocation. Two sub-slices of a leaf are never considered
+    /// the same.
+    #[inline]
+    pub fn ptr_eq(&self, other: &Subtree<'_, ARITY, L>) -> bool {
+        match (self.kind, other.kind) {
+            (SubtreeKind::Node(this), SubtreeKind::Node(other)) => {
+                Arc::ptr_eq(this, other)
+            },
+            _ => false,
+        }
+    }
+
+    /// Returns the summary of this subtree.
+    #[inline]
+    pub fn summary(&self) -> L::Summary {
+        match self.kind {
+            SubtreeKind::Node(node) => node.summary().clone(),
+            SubtreeKind::Slice(slice) => slice.summarize(),
+        }
+    }
+}
+
+/// An iterator over the subtrees of `Tree`s and `TreeSlice`s.
+///
+/// It yields the biggest nodes fully contained in the `Tree` or `TreeSlice`,
+/// from front to back, plus the sub-slices of the leaves at the start and at
+/// the end of a `TreeSlice`. The caller can then choose to
+/// [`descend`](Self::descend()) into an internal node to iterate over its
+/// children instead. This is useful to skip whole subtrees when comparing
+/// two `Tree`s sharing some of their nodes.
+#[derive(Debug)]
+pub struct Subtrees<'a, const ARITY: usize, L: Leaf> {
+    /// The sibling nodes we still have to yield, with the next node being the
+    /// first element of the last slice in the stack.
+    stack: Vec<&'a [Arc<Node<ARITY, L>>]>,
+
+    /// The first slice of a `TreeSlice`, which is yielded before any node.
+    first_slice: Option<L::Slice<'a>>,
+
+    /// The last slice of a `TreeSlice`, which is yielded after all the nodes.
+    last_slice: Option<L::Slice<'a>>,
+
+    /// The number of whole leaves left to be yielded, either by themselves or
+    /// as part of an internal node.
+    leaves_remaining: usize,
+}
+
+impl<'a, const ARITY: usize, L: Leaf> From<&'a Tree<ARITY, L>>
+    for Subtrees<'a, ARITY, L>
+{
+    #[inline]
+    fn from(tree: &'a Tree<ARITY, L>) -> Subtrees<'a, ARITY, L> {
+        Self {
+            stack: vec![core::slice::from_ref(tree.root())],
+            first_slice: None,
+            last_slice: None,
+            leaves_remaining: tree.leaf_count(),
+        }
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> From<&TreeSlice<'a, ARITY, L>>
+    for Subtrees<'a, ARITY, L>
+{
+    #[inline]
+    fn from(slice: &TreeSlice<'a, ARITY, L>) -> Subtrees<'a, ARITY, L> {
+        if slice.leaf_count() <= 1 {
+            return Self {
+                stack: Vec::new(),
+                first_slice: Some(slice.start_slice()),
+                last_slice: None,
+                leaves_remaining: 0,
+            };
+        }
+
+        let mut stack = Vec::with_capacity(slice.root().depth());
+
+        // Walk down to the leaf containing the first slice, pushing the
+        // siblings after every node we go through on the way.
+
+        let base_offset = L::BaseMetric::measure(&slice.offset);
+
+        let mut offset = L::BaseMetric::zero();
+
+        let mut inode = slice.root().get_internal();
+
+        'outer: loop {
+            for (idx, child) in inode.children().iter().enumerate() {
+                let this = child.base_measure();
+
+                if offset + this > base_offset {
+                    stack.push(&inode.children()[idx + 1..]);
+
+                    match &**child {
+                        Node::Internal(child) => {
+                            inode = child;
+                            continue 'outer;
+                        },
+                        Node::Leaf(_) => break 'outer,
+                    }
+                } else {
+                    offset += this;
+                }
+            }
+
+            unreachable!();
+        }
+
+        Self {
+            stack,
+            first_slice: Some(slice.start_slice()),
+            last_slice: Some(slice.end_slice()),
+            leaves_remaining: slice.leaf_count() - 2,
+        }
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> Subtrees<'a, ARITY, L> {
+    /// Makes the iterator yield the children of the given internal node next
+    /// instead of skipping over it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subtree is not an internal node. The subtree should be
+    /// the last one returned by [`next`](Self::next()).
+    #[inline]
+    pub fn descend(&mut self, subtree: Subtree<'a, ARITY, L>) {
+        let SubtreeKind::Node(node) = subtree.kind else {
+            panic!("can't descend into a leaf slice");
+        };
+
+        let inode: &'a Inode<ARITY, L> = node.get_internal();
+
+        self.leaves_remaining += inode.leaf_count();
+        self.stack.push(inode.children());
+    }
+}
+
+impl<'a, const ARITY: usize, L: Leaf> Iterator for Subtrees<'a, ARITY, L> {
+    type Item = Subtree<'a, ARITY, L>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(first) = self.first_slice.take() {
+            return Some(Subtree { kind: SubtreeKind::Slice(first) });
+        }
+
+        while self.leaves_remaining > 0 {
+            let siblings = self.stack.last_mut()?;
+
+            let Some((node, rest)) = siblings.split_first() else {
+                self.stack.pop();
+                continue;
+            };
+
+            *siblings = rest;
+
+            let leaf_count = node.leaf_count();
+
+            if leaf_count <= self.leaves_remaining {
+                self.leaves_remaining -= leaf_count;
+                return Some(Subtree { kind: SubtreeKind::Node(node) });
+            } else {
+                // The node contains the leaf of the last slice, so we have to
+                // yield its children instead.
+                self.stack.push(node.get_internal().children());
+            }
+        }
+
+        self.last_slice
+            .take()
+            .map(|last| Subtree { kind: SubtreeKind::Slice(last) })
+    }
+}
+
+impl<const ARITY: usize, L: Leaf> core::iter::FusedIterator
+    for Subtrees<'_, ARITY, L>
+{
+}
@@ -11,6 +11,14 @@ pub trait Summarize: Debug {
         + for<'a> SubAssign<&'a Self::Summary>
         + PartialEq<Self::Summary>;
 
+    /// Whether subtracting a summary from the sum of several summaries gives
+    /// the sum of the remaining ones, no matter the order they were added in.
+    ///
+    /// If it doesn't, internal nodes recompute their summary from the ones of
+    /// their children every time one of them is added, removed or modified,
+    /// instead of subtracting the old summary and adding the new one.
+    const INVERTIBLE_SUMMARY: bool = true;
+
     fn summarize(&self) -> Self::Summary;
 }
 
@@ -68,6 +68,67 @@ impl<const ARITY: usize, L: BalancedLeaf + Clone> From<TreeSlice<'_, ARITY, L>>
 }
 
 impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
+    /// Appends `other` to the end of this `Tree`.
+    ///
+    /// The subtrees of both trees are shared rather than copied, and only the
+    /// nodes along the seam between the two are rebalanced.
+    #[inline]
+    pub fn append(&mut self, mut other: Self)
+    where
+        L: BalancedLeaf + Clone,
+    {
+        if other.base_measure() == L::BaseMetric::zero() {
+            return;
+        }
+
+        if self.base_measure() == L::BaseMetric::zero() {
+            *self = other;
+            return;
+        }
+
+        let left_depth = self.root.depth();
+
+        let right_depth = other.root.depth();
+
+        if left_depth == right_depth {
+            Arc::make_mut(&mut self.root)
+                .balance(Arc::make_mut(&mut other.root));
+
+            if !other.root.is_empty() {
+                self.root = Arc::new(Node::Internal(Inode::from_children([
+                    Arc::clone(&self.root),
+                    other.root,
+                ])));
+            }
+        } else if left_depth > right_depth {
+            // The left root is deeper than a leaf so it has to be an inode.
+            let extra = Arc::make_mut(&mut self.root)
+                .get_internal_mut()
+                .append_at_depth(other.root);
+
+            if let Some(extra) = extra {
+                self.root = Arc::new(Node::Internal(Inode::from_children([
+                    Arc::clone(&self.root),
+                    Arc::new(Node::Internal(extra)),
+                ])));
+            }
+        } else {
+            let left = core::mem::replace(&mut self.root, other.root);
+
+            // The right root is deeper than a leaf so it has to be an inode.
+            let extra = Arc::make_mut(&mut self.root)
+                .get_internal_mut()
+                .prepend_at_depth(left);
+
+            if let Some(extra) = extra {
+                self.root = Arc::new(Node::Internal(Inode::from_children([
+                    Arc::new(Node::Internal(extra)),
+                    Arc::clone(&self.root),
+                ])));
+            }
+        }
+    }
+
     #[doc(hidden)]
     pub fn assert_invariants(&self) {
         match &*self.root {
@@ -217,6 +278,12 @@ impl<const ARITY: usize, L: Leaf> Tree<ARITY, L> {
         self.root.summary()
     }
 
+    /// Returns an iterator over the subtrees of this `Tree`.
+    #[inline]
+    pub fn subtrees(&self) -> Subtrees<'_, ARITY, L> {
+        Subtrees::from(self)
+    }
+
     /// Returns an iterator over the `M`-units of this `Tree`.
     #[inline]
     pub fn units<M>(&self) -> Units<'_, ARITY, L, M>
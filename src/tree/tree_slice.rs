@@ -123,6 +123,26 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
 
     /// Returns the `M2`-measure of all the leaves before `up_to` plus the
     /// `M2`-measure of the left sub-slice of the leaf at `up_to`.
+    /// Returns the `leaf_idx`-th leaf spanned by the slice together with its
+    /// summary, where `leaf` is the leaf node at that position. The first and
+    /// last leaves are clipped to the range of the slice.
+    #[inline]
+    pub(super) fn clip_leaf(
+        &self,
+        leaf_idx: usize,
+        leaf: &'a Lnode<L>,
+    ) -> (L::Slice<'a>, L::Summary) {
+        debug_assert!(leaf_idx < self.leaf_count);
+
+        if leaf_idx == 0 {
+            (self.start_slice, self.start_summary.clone())
+        } else if leaf_idx + 1 == self.leaf_count {
+            (self.end_slice, self.end_summary.clone())
+        } else {
+            (leaf.as_slice(), leaf.summary().clone())
+        }
+    }
+
     #[inline]
     pub fn convert_measure<M1, M2>(&self, up_to: M1) -> M2
     where
@@ -214,6 +234,11 @@ impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L> {
     pub fn summary(&self) -> &L::Summary {
         &self.summary
     }
+
+    #[inline]
+    pub fn subtrees(&self) -> Subtrees<'a, ARITY, L> {
+        Subtrees::from(self)
+    }
 }
 
 impl<'a, const ARITY: usize, L: Leaf> TreeSlice<'a, ARITY, L>
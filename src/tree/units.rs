@@ -134,6 +134,32 @@ impl<'a, const ARITY: usize, L: Leaf, M: UnitMetric<L>> Iterator
 
         Some((tree_slice, L::BaseMetric::measure(&advance)))
     }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n == 0 || self.remaining == L::BaseMetric::zero() {
+            return self.next();
+        }
+
+        let iter = &mut self.forward;
+
+        if !iter.is_initialized {
+            iter.initialize();
+        }
+
+        let n = measure_of_units::<L, M>(n);
+
+        if n > iter.units_total - iter.units_yielded {
+            iter.start_summary = L::Summary::default();
+            iter.units_yielded = iter.units_total;
+            iter.base_yielded = iter.base_total;
+            return None;
+        }
+
+        iter.skip_units(n);
+
+        self.next()
+    }
 }
 
 impl<const ARITY: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
@@ -183,6 +209,57 @@ impl<const ARITY: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
 
         Some((tree_slice, L::BaseMetric::measure(&advance)))
     }
+
+    #[inline]
+    fn nth_back(&mut self, mut n: usize) -> Option<Self::Item> {
+        if n == 0 || self.remaining == L::BaseMetric::zero() {
+            return self.next_back();
+        }
+
+        // The first call to `next_back()` may yield the remainder, which
+        // doesn't count as an `M`-unit, so we let it handle that.
+        if !self.backward.is_initialized {
+            self.next_back()?;
+            n -= 1;
+        }
+
+        let iter = &mut self.backward;
+
+        let n = measure_of_units::<L, M>(n);
+
+        if n >= iter.units_remaining {
+            iter.end_summary = L::Summary::default();
+            iter.units_remaining = M::zero();
+            iter.base_remaining = L::BaseMetric::zero();
+            return None;
+        }
+
+        iter.skip_units(n);
+
+        self.next_back()
+    }
+}
+
+/// Returns the `M`-measure of `n` `M`-units, i.e. `M::one()` added to itself
+/// `n` times, in `O(log n)` additions.
+#[inline]
+fn measure_of_units<L: Leaf, M: Metric<L::Summary>>(mut n: usize) -> M {
+    let mut measure = M::zero();
+    let mut power = M::one();
+
+    loop {
+        if n & 1 == 1 {
+            measure += power;
+        }
+
+        n >>= 1;
+
+        if n == 0 {
+            return measure;
+        }
+
+        power += power;
+    }
 }
 
 impl<const ARITY: usize, L: Leaf, M: UnitMetric<L>> core::iter::FusedIterator
@@ -703,6 +780,108 @@ impl<'a, const N: usize, L: Leaf, M: UnitMetric<L>> UnitsForward<'a, N, L, M> {
         )
     }
 
+    /// Skips the next `n` units, leaving `Self` in the same state it would be
+    /// in after calling [`next`](Self::next()) `n` times.
+    ///
+    /// Leaves which are entirely contained in the skipped range are jumped
+    /// over using their `M`-measure, so this only calls
+    /// [`first_unit`](UnitMetric::first_unit()) on the leaf where the
+    /// skipped range ends.
+    ///
+    /// NOTE: it assumes that `Self` is initialized and that there are at
+    /// least `n` units left to yield.
+    #[inline]
+    fn skip_units(&mut self, mut n: M) {
+        debug_assert!(self.is_initialized);
+        debug_assert!(n <= self.units_total - self.units_yielded);
+
+        self.units_yielded += n;
+
+        let in_leaf = M::measure(&self.start_summary);
+
+        if n > in_leaf {
+            n -= in_leaf;
+
+            let mut leaf_start = self.base_yielded
+                + L::BaseMetric::measure(&self.start_summary);
+
+            let mut node = 'outer: loop {
+                let (node, child_idx) = self.path.pop().unwrap();
+
+                // Every node in the path is an internal node.
+                let inode = node.get_internal();
+
+                for (idx, child) in
+                    inode.children()[child_idx + 1..].iter().enumerate()
+                {
+                    let child_measure = child.measure::<M>();
+
+                    if child_measure >= n {
+                        self.path.push((node, child_idx + 1 + idx));
+                        break 'outer child;
+                    } else {
+                        n -= child_measure;
+                        leaf_start += child.base_measure();
+                    }
+                }
+            };
+
+            'outer: loop {
+                match &**node {
+                    Node::Internal(inode) => {
+                        for (idx, child) in inode.children().iter().enumerate()
+                        {
+                            let child_measure = child.measure::<M>();
+
+                            if child_measure >= n {
+                                self.path.push((node, idx));
+                                node = child;
+                                continue 'outer;
+                            } else {
+                                n -= child_measure;
+                                leaf_start += child.base_measure();
+                            }
+                        }
+
+                        unreachable!();
+                    },
+
+                    Node::Leaf(leaf) => {
+                        self.leaf_node = node;
+
+                        let contains_last_slice = leaf.base_measure()
+                            > self.base_total - leaf_start;
+
+                        let (slice, summary) = if contains_last_slice {
+                            self.last_slice.take().unwrap()
+                        } else {
+                            (leaf.as_slice(), leaf.summary().clone())
+                        };
+
+                        self.yielded_in_leaf = L::Summary::default();
+                        self.start_slice = slice;
+                        self.start_summary = summary;
+                        self.base_yielded = leaf_start;
+
+                        break;
+                    },
+                }
+            }
+        }
+
+        while n > M::zero() {
+            let (_, _, advance, rest, rest_summary) =
+                M::first_unit(self.start_slice, &self.start_summary);
+
+            self.base_yielded += L::BaseMetric::measure(&advance);
+            self.yielded_in_leaf += &advance;
+            self.start_slice = rest;
+            self.start_summary = rest_summary;
+
+            n -= M::one();
+        }
+    }
+
     /// Very similar to [`next_leaf_with_measure`](1), except it doesn't
     /// mutate any state and instead of returning the next leaf node with a
     /// non-zero `M`-measure it returns the leaf node containing
@@ -1722,6 +1901,113 @@ impl<'a, const N: usize, L: Leaf, M: DoubleEndedUnitMetric<L>>
         )
     }
 
+    /// Skips the previous `n` units, leaving `Self` in the same state it
+    /// would be in after calling [`previous`](Self::previous()) `n` times.
+    ///
+    /// This is the mirror image of [`UnitsForward::skip_units()`].
+    ///
+    /// NOTE: it assumes that the remainder (if any) has already been yielded
+    /// and that there are more than `n` units left to yield.
+    #[inline]
+    fn skip_units(&mut self, mut n: M) {
+        debug_assert!(self.is_initialized);
+        debug_assert!(n < self.units_remaining);
+
+        self.units_remaining -= n;
+
+        let in_leaf = M::measure(&self.end_summary);
+
+        if n >= in_leaf {
+            // All the units in the current leaf are skipped, and the new end
+            // of the range is right after the `n`-th unit preceding it
+            // (counting backward from 1).
+            n = n - in_leaf + M::one();
+
+            let mut leaf_end = self.base_remaining
+                - L::BaseMetric::measure(&self.end_summary);
+
+            let mut node = 'outer: loop {
+                let (node, child_idx) = self.path.pop().unwrap();
+
+                // Every node in the path is an internal node.
+                let inode = node.get_internal();
+
+                for (idx, child) in
+                    inode.children()[..child_idx].iter().enumerate().rev()
+                {
+                    let child_measure = child.measure::<M>();
+
+                    if child_measure >= n {
+                        self.path.push((node, idx));
+                        break 'outer child;
+                    } else {
+                        n -= child_measure;
+                        leaf_end -= child.base_measure();
+                    }
+                }
+            };
+
+            let (slice, summary) = 'outer: loop {
+                match &**node {
+                    Node::Internal(inode) => {
+                        for (idx, child) in
+                            inode.children().iter().enumerate().rev()
+                        {
+                            let child_measure = child.measure::<M>();
+
+                            if child_measure >= n {
+                                self.path.push((node, idx));
+                                node = child;
+                                continue 'outer;
+                            } else {
+                                n -= child_measure;
+                                leaf_end -= child.base_measure();
+                            }
+                        }
+
+                        unreachable!();
+                    },
+
+                    Node::Leaf(leaf) => {
+                        self.leaf_node = node;
+
+                        let contains_first_slice =
+                            leaf.base_measure() > leaf_end;
+
+                        break 'outer if contains_first_slice {
+                            self.first_slice.take().unwrap()
+                        } else {
+                            (leaf.as_slice(), leaf.summary().clone())
+                        };
+                    },
+                }
+            };
+
+            let (rest, rest_summary, _, remainder_summary) =
+                M::remainder(slice, &summary);
+
+            self.yielded_in_leaf = remainder_summary;
+            self.end_slice = rest;
+            self.end_summary = rest_summary;
+            self.base_remaining = leaf_end
+                - L::BaseMetric::measure(&self.yielded_in_leaf);
+
+            n -= M::one();
+        }
+
+        while n > M::zero() {
+            let (rest, rest_summary, _, _, advance) =
+                M::last_unit(self.end_slice, &self.end_summary);
+
+            self.base_remaining -= L::BaseMetric::measure(&advance);
+            self.yielded_in_leaf += &advance;
+            self.end_slice = rest;
+            self.end_summary = rest_summary;
+
+            n -= M::one();
+        }
+    }
+
     /// Yields the remainder of the yielding range when dividing by `M`-units,
     /// i.e. the TreeSlice in the `units_total..base_total` range.
     ///
@@ -0,0 +1,150 @@
+use crop::anchors::{AnchorSet, AnchoredRope};
+use crop::changes::{Bias, ChangeSet};
+use crop::Rope;
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM};
+
+#[test]
+fn anchors_bias() {
+    let mut rope = AnchoredRope::new(Rope::from("foo bar"));
+
+    let left = rope.anchor(4, Bias::Left);
+    let right = rope.anchor(4, Bias::Right);
+    let end = rope.anchor(7, Bias::Left);
+
+    rope.insert(4, "baz ");
+    assert_eq!(rope.rope(), "foo baz bar");
+    assert_eq!(rope.offset(left), Some(4));
+    assert_eq!(rope.offset(right), Some(8));
+    assert_eq!(rope.offset(end), Some(11));
+
+    // Deleting the text between them collapses them together.
+    rope.delete(4..8);
+    assert_eq!(rope.offset(left), Some(4));
+    assert_eq!(rope.offset(right), Some(4));
+
+    // Anchors within a replaced range end up after the new text.
+    rope.replace(2..7, "ol");
+    assert_eq!(rope.rope(), "fool");
+    assert_eq!(rope.offset(left), Some(4));
+    assert_eq!(rope.offset(right), Some(4));
+    assert_eq!(rope.offset(end), Some(4));
+
+    rope.delete(..);
+    assert_eq!(rope.offset(end), Some(0));
+    assert_eq!(rope.anchors().len(), 3);
+}
+
+#[test]
+fn anchors_remove() {
+    let mut anchors = AnchorSet::new(10);
+
+    let a = anchors.create(3, Bias::Left);
+    let b = anchors.create(5, Bias::Right);
+    assert_eq!(anchors.len(), 2);
+
+    assert_eq!(anchors.remove(a), Some(3));
+    assert_eq!(anchors.remove(a), None);
+    assert_eq!(anchors.offset(a), None);
+    assert_eq!(anchors.offset(b), Some(5));
+
+    // The slot of the removed anchor is reused, but the old handle still
+    // doesn't resolve.
+    let c = anchors.create(8, Bias::Left);
+    assert_eq!(anchors.offset(a), None);
+    assert_eq!(anchors.offset(c), Some(8));
+
+    assert_eq!(anchors.remove(b), Some(5));
+    assert_eq!(anchors.remove(c), Some(8));
+    assert!(anchors.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn anchors_out_of_bounds() {
+    let mut anchors = AnchorSet::new(10);
+    anchors.create(11, Bias::Left);
+}
+
+#[test]
+#[should_panic]
+fn anchors_not_char_boundary() {
+    let mut rope = AnchoredRope::new(Rope::from("🦀"));
+    rope.anchor(1, Bias::Left);
+}
+
+/// Checks that anchors are mapped through random edits like offsets are
+/// mapped through the equivalent `ChangeSet`s.
+#[test]
+fn anchors_random() {
+    let mut rng = thread_rng();
+
+    for s in [MEDIUM, &CURSED_LIPSUM.repeat(8)] {
+        let mut rope = AnchoredRope::new(Rope::from(s));
+        let mut expected = Vec::new();
+
+        for _ in 0..500 {
+            let text = rope.rope().to_string();
+
+            let random_offset = |rng: &mut rand::rngs::ThreadRng| {
+                let mut offset = rng.gen_range(0..=text.len());
+                while !text.is_char_boundary(offset) {
+                    offset -= 1;
+                }
+                offset
+            };
+
+            match rng.gen_range(0..6) {
+                0 | 1 => {
+                    let offset = random_offset(&mut rng);
+                    let bias =
+                        if rng.gen() { Bias::Left } else { Bias::Right };
+                    let anchor = rope.anchor(offset, bias);
+                    expected.push((anchor, offset, bias));
+                },
+
+                2 if !expected.is_empty() => {
+                    let idx = rng.gen_range(0..expected.len());
+                    let (anchor, offset, _) = expected.swap_remove(idx);
+                    assert_eq!(rope.remove_anchor(anchor), Some(offset));
+                },
+
+                _ => {
+                    let start = random_offset(&mut rng);
+                    let mut end =
+                        (start + rng.gen_range(0..=16)).min(text.len());
+                    while !text.is_char_boundary(end) {
+                        end += 1;
+                    }
+
+                    let replacement = match rng.gen_range(0..3) {
+                        0 => "",
+                        1 => "\n",
+                        _ => "Ḽơᶉëᶆ",
+                    };
+
+                    let changes = ChangeSet::from_replacements(
+                        text.len(),
+                        [(start..end, replacement)],
+                    );
+
+                    for (_, offset, bias) in &mut expected {
+                        *offset = changes.map_offset(*offset, *bias);
+                    }
+
+                    rope.replace(start..end, replacement);
+                },
+            }
+
+            assert_eq!(rope.anchors().len(), expected.len());
+            assert_eq!(rope.anchors().byte_len(), rope.rope().byte_len());
+
+            for &(anchor, offset, _) in &expected {
+                assert_eq!(rope.offset(anchor), Some(offset));
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use crop::changes::{Bias, ChangeSet, Operation};
+use crop::Rope;
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM, SMALL};
+
+/// Returns a random `ChangeSet` over `s` together with the string it turns
+/// `s` into.
+fn random_changes(s: &str) -> (ChangeSet, String) {
+    let mut rng = thread_rng();
+
+    let mut changes = ChangeSet::new();
+    let mut edited = String::new();
+
+    let mut offset = 0;
+
+    while offset < s.len() {
+        let mut end = (offset + rng.gen_range(1..=32)).min(s.len());
+        while !s.is_char_boundary(end) {
+            end += 1;
+        }
+
+        match rng.gen_range(0..4) {
+            0 => {
+                changes.delete(end - offset);
+            },
+            1 => {
+                let text = if rng.gen() { "Ḽơᶉëᶆ" } else { "\r\n" };
+                changes.insert(text);
+                edited.push_str(text);
+                continue;
+            },
+            _ => {
+                changes.retain(end - offset);
+                edited.push_str(&s[offset..end]);
+            },
+        }
+
+        offset = end;
+    }
+
+    if rng.gen() {
+        changes.insert("ȋṕšᶙṁ");
+        edited.push_str("ȋṕšᶙṁ");
+    }
+
+    (changes, edited)
+}
+
+#[test]
+fn changes_canonical_form() {
+    let mut changes = ChangeSet::new();
+    changes.retain(1).retain(2).delete(3).insert("foo").delete(1).insert("!");
+
+    assert_eq!(
+        changes.operations(),
+        [
+            Operation::Retain(3),
+            Operation::Insert("foo!".into()),
+            Operation::Delete(4),
+        ]
+    );
+
+    assert_eq!(changes.byte_len(), 7);
+    assert_eq!(changes.byte_len_after(), 7);
+
+    let other = changes.operations().iter().cloned().collect::<ChangeSet>();
+    assert_eq!(other, changes);
+
+    assert!(!changes.is_empty());
+    let no_replacements: [(std::ops::Range<usize>, &str); 0] = [];
+    assert!(ChangeSet::from_replacements(10, no_replacements).is_empty());
+}
+
+#[test]
+fn changes_apply_and_invert() {
+    for s in [SMALL, MEDIUM, &CURSED_LIPSUM.repeat(8)] {
+        let original = Rope::from(s);
+
+        for _ in 0..10 {
+            let (changes, edited) = random_changes(s);
+            assert_eq!(changes.byte_len(), s.len());
+            assert_eq!(changes.byte_len_after(), edited.len());
+
+            let mut r = original.clone();
+            changes.apply(&mut r);
+            assert_eq!(r, edited);
+
+            let inverse = changes.invert(&original);
+            inverse.apply(&mut r);
+            assert_eq!(r, original);
+
+            assert_eq!(inverse.invert(&Rope::from(edited.as_str())), changes);
+        }
+    }
+}
+
+#[test]
+fn changes_compose() {
+    for s in [SMALL, MEDIUM, &CURSED_LIPSUM.repeat(8)] {
+        let original = Rope::from(s);
+
+        for _ in 0..10 {
+            let (first, edited) = random_changes(s);
+            let (second, expected) = random_changes(&edited);
+
+            let composed = first.clone().compose(second.clone());
+            assert_eq!(composed.byte_len(), s.len());
+            assert_eq!(composed.byte_len_after(), expected.len());
+
+            let mut r = original.clone();
+            composed.apply(&mut r);
+            assert_eq!(r, expected);
+
+            // Composing with the inverse gives back the original text.
+            let inverse = first.invert(&original);
+            let mut r = original.clone();
+            first.compose(inverse).apply(&mut r);
+            assert_eq!(r, original);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn changes_compose_wrong_len() {
+    let mut first = ChangeSet::new();
+    first.retain(3).insert("foo");
+
+    let mut second = ChangeSet::new();
+    second.retain(3);
+
+    let _ = first.compose(second);
+}
+
+#[test]
+fn changes_from_replacements() {
+    let s = "foo bar baz";
+
+    let changes = ChangeSet::from_replacements(
+        s.len(),
+        [(0..0, "> "), (4..7, "qux"), (8..8, "!"), (11..11, "\n")],
+    );
+
+    let mut r = Rope::from(s);
+    changes.apply(&mut r);
+    assert_eq!(r, "> foo qux !baz\n");
+}
+
+#[test]
+#[should_panic]
+fn changes_from_overlapping_replacements() {
+    let _ = ChangeSet::from_replacements(10, [(0..5, ""), (4..6, "")]);
+}
+
+#[test]
+fn changes_map_offset() {
+    let s = CURSED_LIPSUM.repeat(8);
+
+    for _ in 0..20 {
+        let (changes, edited) = random_changes(&s);
+
+        let mut prev = 0;
+
+        for (offset, _) in s.char_indices().chain([(s.len(), ' ')]) {
+            let left = changes.map_offset(offset, Bias::Left);
+            let right = changes.map_offset(offset, Bias::Right);
+
+            assert!(left <= right);
+            assert!(prev <= left);
+            assert!(right <= edited.len());
+            assert!(edited.is_char_boundary(left));
+            assert!(edited.is_char_boundary(right));
+
+            prev = right;
+        }
+
+        assert_eq!(changes.map_offset(s.len(), Bias::Right), edited.len());
+    }
+
+    // Offsets in retained text keep pointing to the same character.
+    let mut changes = ChangeSet::new();
+    changes.insert("Hello ").retain(5).delete(1).retain(3);
+
+    let s = "World???";
+    let edited = "Hello World??";
+
+    for offset in 0..5 {
+        let mapped = changes.map_offset(offset, Bias::Right);
+        let ch = s[offset..].chars().next();
+        assert_eq!(edited[mapped..].chars().next(), ch);
+    }
+
+    assert_eq!(changes.map_offset(0, Bias::Left), 0);
+    assert_eq!(changes.map_offset(5, Bias::Left), 11);
+    assert_eq!(changes.map_offset(6, Bias::Left), 11);
+}
@@ -0,0 +1,158 @@
+mod common;
+
+#[cfg(feature = "char-metric")]
+mod tests {
+    use crop::Rope;
+    use rand::Rng;
+
+    use crate::common::{CURSED_LIPSUM, LARGE, TEXT, TEXT_EMOJI};
+
+    #[test]
+    fn char_len_0() {
+        let r = Rope::from(TEXT);
+        assert_eq!(r.char_len(), 103);
+
+        let s = r.byte_slice(..);
+        assert_eq!(s.char_len(), 103);
+    }
+
+    #[test]
+    fn char_len_1() {
+        let r = Rope::from(TEXT_EMOJI);
+        assert_eq!(r.char_len(), 107);
+
+        let s = r.byte_slice(..);
+        assert_eq!(s.char_len(), 107);
+    }
+
+    #[test]
+    fn char_len_2() {
+        let r = Rope::new();
+        assert_eq!(r.char_len(), 0);
+    }
+
+    #[test]
+    fn char_len_3() {
+        let r = Rope::from(TEXT_EMOJI);
+        let s = r.byte_slice(16..39);
+        assert_eq!(s.char_len(), 20);
+    }
+
+    #[test]
+    fn byte_to_char_0() {
+        let r = Rope::new();
+        assert_eq!(r.char_of_byte(0), 0);
+
+        let s = r.byte_slice(..);
+        assert_eq!(s.char_of_byte(0), 0);
+    }
+
+    #[should_panic]
+    #[test]
+    fn byte_to_char_1() {
+        let r = Rope::new();
+        let _ = r.char_of_byte(1);
+    }
+
+    #[test]
+    fn byte_to_char_2() {
+        let r = Rope::from(TEXT_EMOJI);
+        let s = r.byte_slice(..);
+
+        assert_eq!(0, r.char_of_byte(0));
+        assert_eq!(12, r.char_of_byte(12));
+        assert_eq!(13, r.char_of_byte(16));
+        assert_eq!(107, r.char_of_byte(143));
+
+        assert_eq!(0, s.char_of_byte(0));
+        assert_eq!(12, s.char_of_byte(12));
+        assert_eq!(13, s.char_of_byte(16));
+        assert_eq!(107, s.char_of_byte(143));
+    }
+
+    #[test]
+    fn char_to_byte_0() {
+        let r = Rope::new();
+        assert_eq!(r.byte_of_char(0), 0);
+
+        let s = r.byte_slice(..);
+        assert_eq!(s.byte_of_char(0), 0);
+    }
+
+    #[should_panic]
+    #[test]
+    fn char_to_byte_1() {
+        let r = Rope::from(TEXT);
+        let _ = r.byte_of_char(104);
+    }
+
+    #[test]
+    fn char_to_byte_2() {
+        let r = Rope::from(TEXT_EMOJI);
+        let s = r.byte_slice(..);
+
+        assert_eq!(0, r.byte_of_char(0));
+        assert_eq!(12, r.byte_of_char(12));
+        assert_eq!(16, r.byte_of_char(13));
+        assert_eq!(143, r.byte_of_char(107));
+
+        assert_eq!(0, s.byte_of_char(0));
+        assert_eq!(12, s.byte_of_char(12));
+        assert_eq!(16, s.byte_of_char(13));
+        assert_eq!(143, s.byte_of_char(107));
+    }
+
+    #[test]
+    fn char_slice_0() {
+        let r = Rope::from(TEXT_EMOJI);
+
+        assert_eq!(r.char_slice(..), TEXT_EMOJI);
+        assert_eq!(r.char_slice(12..13), "🐸");
+        assert_eq!(r.char_slice(107..), "");
+
+        let s = r.char_slice(12..);
+        assert_eq!(s.char_slice(..1), "🐸");
+        assert_eq!(s.char_slice(1..3), "  ");
+    }
+
+    #[should_panic]
+    #[test]
+    fn char_slice_1() {
+        let r = Rope::from(TEXT);
+        #[allow(clippy::reversed_empty_ranges)]
+        let _ = r.char_slice(2..1);
+    }
+
+    #[should_panic]
+    #[test]
+    fn char_slice_2() {
+        let r = Rope::from(TEXT);
+        let _ = r.char_slice(..104);
+    }
+
+    #[test]
+    fn char_conversion_random() {
+        let mut rng = rand::thread_rng();
+
+        for s in [CURSED_LIPSUM, LARGE] {
+            let r = Rope::from(s);
+            let ropey = ropey::Rope::from(s);
+
+            assert_eq!(r.char_len(), ropey.len_chars());
+
+            for _ in 0..100 {
+                let char_offset = rng.gen_range(0..=r.char_len());
+                let byte_offset = ropey.char_to_byte(char_offset);
+
+                assert_eq!(r.byte_of_char(char_offset), byte_offset);
+                assert_eq!(r.char_of_byte(byte_offset), char_offset);
+
+                let end = rng.gen_range(char_offset..=r.char_len());
+                let slice = r.char_slice(char_offset..end);
+
+                assert_eq!(slice, &s[byte_offset..ropey.char_to_byte(end)]);
+                assert_eq!(slice.char_len(), end - char_offset);
+            }
+        }
+    }
+}
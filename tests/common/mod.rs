@@ -1,5 +1,8 @@
 #![allow(dead_code)]
 
+use crop::{Rope, RopeBuilder};
+use rand::{thread_rng, Rng};
+
 pub const TINY: &str = include_str!("tiny.txt");
 pub const SMALL: &str = include_str!("small.txt");
 pub const MEDIUM: &str = include_str!("medium.txt");
@@ -30,3 +33,46 @@ pub const TEXT_LINES: &str = "Hello there!  How're you doing?\nIt's a fine \
 pub const TEXT_EMOJI: &str = "Hello there!🐸  How're you doing?🐸  It's a \
                               fine day, isn't it?🐸  Aren't you glad we're \
                               alive?🐸  こんにちは、みんなさん！";
+
+/// Returns a random byte range of `s` whose start and end are both on char
+/// boundaries.
+pub fn random_range(s: &str) -> (usize, usize) {
+    random_range_at_most(s, s.len())
+}
+
+/// Like [`random_range()`], but the range is at most `max_len` bytes long,
+/// plus however many bytes it takes for its end to reach a char boundary.
+pub fn random_range_at_most(s: &str, max_len: usize) -> (usize, usize) {
+    let mut rng = thread_rng();
+
+    let mut start = rng.gen_range(0..=s.len());
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+
+    let mut end = rng.gen_range(start..=(start + max_len).min(s.len()));
+    while !s.is_char_boundary(end) {
+        end += 1;
+    }
+
+    (start, end)
+}
+
+/// Builds a rope by appending random pieces of `s`, so that its chunks are
+/// split at different offsets than the ones of `Rope::from(s)`.
+pub fn build_randomly(s: &str) -> Rope {
+    let mut rng = thread_rng();
+    let mut builder = RopeBuilder::new();
+    let mut start = 0;
+
+    while start < s.len() {
+        let mut end = (start + rng.gen_range(1..=64)).min(s.len());
+        while !s.is_char_boundary(end) {
+            end += 1;
+        }
+        builder.append(&s[start..end]);
+        start = end;
+    }
+
+    builder.build()
+}
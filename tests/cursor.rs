@@ -0,0 +1,217 @@
+use crop::{Rope, RopeSlice};
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+#[test]
+fn cursor_empty() {
+    let r = Rope::new();
+
+    let mut cursor = r.cursor();
+    assert_eq!(cursor.byte_offset(), 0);
+    assert_eq!(cursor.line_offset(), 0);
+    assert_eq!(cursor.chunk(), "");
+    assert_eq!(cursor.next_char(), None);
+    assert_eq!(cursor.previous_char(), None);
+    assert_eq!(cursor.next_chunk(), None);
+    assert_eq!(cursor.previous_chunk(), None);
+    assert!(!cursor.next_line());
+    assert!(!cursor.previous_line());
+
+    cursor.seek_to_byte(0);
+    cursor.seek_to_line(0);
+    assert_eq!(cursor.byte_offset(), 0);
+}
+
+#[should_panic]
+#[test]
+fn cursor_seek_out_of_bounds() {
+    let r = Rope::from("foo");
+    r.cursor().seek_to_byte(4);
+}
+
+#[should_panic]
+#[test]
+fn cursor_seek_not_char_boundary() {
+    let r = Rope::from("🐸");
+    r.cursor().seek_to_byte(2);
+}
+
+/// Tests that walking a `Cursor` char by char in both directions yields the
+/// same chars as the string.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cursor_chars() {
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+        let mut cursor = r.cursor();
+
+        let mut offset = 0;
+        for ch in s.chars() {
+            assert_eq!(cursor.byte_offset(), offset);
+            assert_eq!(cursor.next_char(), Some(ch));
+            offset += ch.len_utf8();
+        }
+        assert_eq!(cursor.next_char(), None);
+        assert_eq!(cursor.byte_offset(), s.len());
+
+        for ch in s.chars().rev() {
+            assert_eq!(cursor.previous_char(), Some(ch));
+            offset -= ch.len_utf8();
+            assert_eq!(cursor.byte_offset(), offset);
+        }
+        assert_eq!(cursor.previous_char(), None);
+    }
+}
+
+/// Tests that moving a `Cursor` chunk by chunk in both directions yields the
+/// same chunks as the `Chunks` iterator.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cursor_chunks() {
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        let chunks = r.chunks().collect::<Vec<_>>();
+
+        let mut cursor = r.cursor();
+        assert_eq!(cursor.chunk(), chunks[0]);
+
+        let mut offset = 0;
+        for window in chunks.windows(2) {
+            offset += window[0].len();
+            assert_eq!(cursor.next_chunk(), Some(window[1]));
+            assert_eq!(cursor.chunk_byte_offset(), offset);
+            assert_eq!(cursor.byte_offset(), offset);
+        }
+        assert_eq!(cursor.next_chunk(), None);
+
+        for window in chunks.windows(2).rev() {
+            offset -= window[0].len();
+            assert_eq!(cursor.previous_chunk(), Some(window[0]));
+            assert_eq!(cursor.byte_offset(), offset);
+        }
+        assert_eq!(cursor.previous_chunk(), None);
+    }
+}
+
+/// Tests that moving a `Cursor` line by line in both directions lands on the
+/// same byte offsets as `byte_of_line()`.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cursor_lines() {
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+        let line_breaks = s.matches('\n').count();
+
+        let mut cursor = r.cursor();
+        for line in 1..=line_breaks {
+            assert!(cursor.next_line());
+            assert_eq!(cursor.line_offset(), line);
+            assert_eq!(cursor.byte_offset(), r.byte_of_line(line));
+        }
+        assert!(!cursor.next_line());
+
+        for line in (0..line_breaks).rev() {
+            assert!(cursor.previous_line());
+            assert_eq!(cursor.line_offset(), line);
+            assert_eq!(cursor.byte_offset(), r.byte_of_line(line));
+        }
+        assert!(!cursor.previous_line());
+    }
+}
+
+fn check_random_seeks(slice: RopeSlice<'_>, s: &str) {
+    let mut rng = rand::thread_rng();
+
+    let mut cursor = slice.cursor();
+
+    for _ in 0..100 {
+        let mut byte_offset = rng.gen_range(0..=s.len());
+        while !s.is_char_boundary(byte_offset) {
+            byte_offset += 1;
+        }
+
+        cursor.seek_to_byte(byte_offset);
+        assert_eq!(cursor.byte_offset(), byte_offset);
+        assert_eq!(cursor.line_offset(), slice.line_of_byte(byte_offset));
+        assert_eq!(
+            cursor.clone().next_char(),
+            s[byte_offset..].chars().next()
+        );
+        assert_eq!(
+            cursor.clone().previous_char(),
+            s[..byte_offset].chars().next_back()
+        );
+
+        let chunk_offset = byte_offset - cursor.chunk_byte_offset();
+        assert!(chunk_offset <= cursor.chunk().len());
+        assert_eq!(
+            &cursor.chunk()[chunk_offset..],
+            &s[byte_offset..byte_offset + cursor.chunk().len() - chunk_offset]
+        );
+
+        let line_offset = rng.gen_range(0..=slice.line_len());
+        cursor.seek_to_line(line_offset);
+        assert_eq!(cursor.byte_offset(), slice.byte_of_line(line_offset));
+
+        #[cfg(feature = "utf16-metric")]
+        {
+            let utf16_offset = slice.utf16_code_unit_of_byte(byte_offset);
+            cursor.seek_to_utf16_code_unit(utf16_offset);
+            assert_eq!(cursor.byte_offset(), byte_offset);
+        }
+
+        #[cfg(feature = "char-metric")]
+        {
+            let char_offset = slice.char_of_byte(byte_offset);
+            cursor.seek_to_char(char_offset);
+            assert_eq!(cursor.byte_offset(), byte_offset);
+        }
+    }
+}
+
+/// Tests that seeking a `Cursor` to random offsets always lands on the same
+/// position as the equivalent string operations.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cursor_seek_random() {
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+        check_random_seeks(r.byte_slice(..), s);
+    }
+}
+
+/// Same as `cursor_seek_random` but on random `RopeSlice`s.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn cursor_slice_seek_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..10 {
+            let mut start = rng.gen_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.gen_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            check_random_seeks(slice, &s[start..end]);
+
+            let mut cursor = slice.cursor();
+            for ch in s[start..end].chars() {
+                assert_eq!(cursor.next_char(), Some(ch));
+            }
+            assert_eq!(cursor.next_char(), None);
+        }
+    }
+}
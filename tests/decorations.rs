@@ -0,0 +1,197 @@
+use std::ops::Range;
+
+use crop::changes::{Bias, ChangeSet};
+use crop::decorations::Decorations;
+use rand::{thread_rng, Rng};
+
+#[test]
+fn decorations_edits() {
+    let mut decorations = Decorations::new(11);
+
+    decorations.insert(0..5, 'a');
+    decorations.insert(6..11, 'b');
+    decorations.insert(6..6, 'c');
+
+    let all = |d: &Decorations<char>| {
+        d.overlapping(..).map(|(r, &c)| (r, c)).collect::<Vec<_>>()
+    };
+
+    assert_eq!(all(&decorations), [(0..5, 'a'), (6..11, 'b'), (6..6, 'c')]);
+
+    // Inserting at the start of a decoration moves it, inserting at its end
+    // doesn't grow it.
+    decorations.edit(5..5, 1);
+    assert_eq!(all(&decorations), [(0..5, 'a'), (7..12, 'b'), (7..7, 'c')]);
+
+    decorations.edit(7..7, 3);
+    assert_eq!(all(&decorations), [(0..5, 'a'), (10..15, 'b'), (10..10, 'c')]);
+
+    // Inserting strictly inside a decoration grows it.
+    decorations.edit(2..2, 2);
+    assert_eq!(all(&decorations), [(0..7, 'a'), (12..17, 'b'), (12..12, 'c')]);
+
+    // Deleting some of its text shrinks it.
+    decorations.edit(5..14, 0);
+    assert_eq!(all(&decorations), [(0..5, 'a'), (5..8, 'b'), (5..5, 'c')]);
+
+    decorations.edit(.., 0);
+    assert_eq!(all(&decorations), [(0..0, 'a'), (0..0, 'b'), (0..0, 'c')]);
+    assert_eq!(decorations.byte_len(), 0);
+    assert_eq!(decorations.len(), 3);
+
+    decorations.clear();
+    assert!(decorations.is_empty());
+}
+
+#[test]
+fn decorations_edit_inside_enclosing() {
+    let mut decorations = Decorations::new(1000);
+
+    decorations.insert(0..1000, 0);
+
+    for n in 1..100 {
+        decorations.insert(n * 10..n * 10 + 5, n);
+    }
+
+    // Only the enclosing decoration, the one being edited and the one after
+    // it change.
+    decorations.edit(502..508, 10);
+
+    let all = decorations.overlapping(..).collect::<Vec<_>>();
+
+    assert_eq!(all.len(), 100);
+    assert_eq!(all[0], (0..1004, &0));
+    assert_eq!(all[49], (490..495, &49));
+    assert_eq!(all[50], (500..512, &50));
+    assert_eq!(all[51], (514..519, &51));
+
+    for (n, (range, &value)) in all.into_iter().enumerate().skip(1) {
+        assert_eq!(value, n);
+        let start = if n <= 50 { n * 10 } else { n * 10 + 4 };
+        assert_eq!(range.start, start);
+    }
+}
+
+#[test]
+fn decorations_overlapping() {
+    let mut decorations = Decorations::new(20);
+
+    decorations.insert(0..5, 0);
+    decorations.insert(5..10, 1);
+    decorations.insert(7..7, 2);
+    decorations.insert(10..20, 3);
+
+    let overlapping = |d: &Decorations<i32>, range: Range<usize>| {
+        d.overlapping(range).map(|(_, &n)| n).collect::<Vec<_>>()
+    };
+
+    assert_eq!(overlapping(&decorations, 0..20), [0, 1, 2, 3]);
+    assert_eq!(overlapping(&decorations, 5..10), [1, 2]);
+    assert_eq!(overlapping(&decorations, 6..7), [1, 2]);
+    assert_eq!(overlapping(&decorations, 7..8), [1, 2]);
+    assert_eq!(overlapping(&decorations, 7..7), [1, 2]);
+    assert_eq!(overlapping(&decorations, 10..10), [1, 3]);
+    assert_eq!(overlapping(&decorations, 20..20), [3]);
+    assert_eq!(overlapping(&decorations, 0..5), [0]);
+
+    decorations.remove_overlapping(7..7);
+    assert_eq!(overlapping(&decorations, 0..20), [0, 3]);
+}
+
+#[test]
+#[should_panic]
+fn decorations_out_of_bounds() {
+    let mut decorations = Decorations::new(10);
+    decorations.insert(5..11, ());
+}
+
+/// Checks the decorations against a list of ranges updated by mapping their
+/// ends through the equivalent `ChangeSet`s.
+#[test]
+fn decorations_random() {
+    let mut rng = thread_rng();
+
+    for _ in 0..10 {
+        let mut byte_len = rng.gen_range(0..1000);
+        let mut decorations = Decorations::new(byte_len);
+        let mut expected: Vec<(Range<usize>, usize)> = Vec::new();
+
+        for value in 0..500 {
+            let start = rng.gen_range(0..=byte_len);
+            let end = (start + rng.gen_range(0..=50)).min(byte_len);
+
+            match rng.gen_range(0..8) {
+                0..=2 => {
+                    decorations.insert(start..end, value);
+
+                    let idx = expected
+                        .iter()
+                        .position(|(range, _)| range.start > start)
+                        .unwrap_or(expected.len());
+
+                    expected.insert(idx, (start..end, value));
+                },
+
+                3 => {
+                    decorations.remove_overlapping(start..end);
+
+                    expected.retain(|(range, _)| {
+                        !intersects(range.clone(), start..end)
+                    });
+                },
+
+                _ => {
+                    let text_len = rng.gen_range(0..=20);
+
+                    decorations.edit(start..end, text_len);
+
+                    let changes = ChangeSet::from_replacements(
+                        byte_len,
+                        [(start..end, "x".repeat(text_len))],
+                    );
+
+                    for (range, _) in &mut expected {
+                        let new_start =
+                            changes.map_offset(range.start, Bias::Right);
+                        let new_end = changes
+                            .map_offset(range.end, Bias::Left)
+                            .max(new_start);
+                        *range = new_start..new_end;
+                    }
+
+                    byte_len = byte_len - (end - start) + text_len;
+                },
+            }
+
+            assert_eq!(decorations.len(), expected.len());
+            assert_eq!(decorations.byte_len(), byte_len);
+
+            let start = rng.gen_range(0..=byte_len);
+            let end = (start + rng.gen_range(0..=100)).min(byte_len);
+
+            let actual = decorations
+                .overlapping(start..end)
+                .map(|(range, &value)| (range, value))
+                .collect::<Vec<_>>();
+
+            let wanted = expected
+                .iter()
+                .filter(|(range, _)| intersects(range.clone(), start..end))
+                .cloned()
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual, wanted);
+        }
+
+        let all = decorations.overlapping(..).collect::<Vec<_>>();
+        assert_eq!(all.len(), expected.len());
+    }
+}
+
+fn intersects(a: Range<usize>, b: Range<usize>) -> bool {
+    a.start <= b.end
+        && b.start <= a.end
+        && ((a.start < b.end && b.start < a.end)
+            || a.is_empty()
+            || b.is_empty())
+}
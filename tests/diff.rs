@@ -0,0 +1,256 @@
+use crop::diff::{Hunk, LineDiff};
+use crop::Rope;
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL};
+
+/// Returns the lines of the rope including their line breaks, which is what
+/// the diff compares.
+fn lines(r: &Rope) -> Vec<String> {
+    r.raw_lines().map(|line| line.to_string()).collect()
+}
+
+/// Checks that the hunks are sorted, separated by at least one unchanged
+/// line, and that they turn the lines of the old text into the ones of the
+/// new text.
+fn check(old: &Rope, new: &Rope) -> Vec<Hunk> {
+    let diff = LineDiff::new(old, new);
+
+    let hunks = diff.hunks().to_vec();
+
+    for window in hunks.windows(2) {
+        assert!(window[0].old_lines.end < window[1].old_lines.start);
+        assert!(window[0].new_lines.end < window[1].new_lines.start);
+    }
+
+    let old_lines = lines(old);
+    let new_lines = lines(new);
+
+    let mut patched = old_lines.clone();
+
+    for hunk in hunks.iter().rev() {
+        assert!(!hunk.old_lines.is_empty() || !hunk.new_lines.is_empty());
+
+        patched.splice(
+            hunk.old_lines.clone(),
+            new_lines[hunk.new_lines.clone()].iter().cloned(),
+        );
+    }
+
+    assert_eq!(patched, new_lines);
+    assert_eq!(diff.is_empty(), old_lines == new_lines);
+
+    hunks
+}
+
+#[test]
+fn diff_empty() {
+    let r = Rope::from("");
+    assert!(LineDiff::new(&r, &r).is_empty());
+
+    let new = Rope::from("foo\n");
+    let diff = LineDiff::new(&r, &new);
+    assert_eq!(diff.unified().to_string(), "@@ -0,0 +1 @@\n+foo\n");
+
+    let diff = LineDiff::new(&new, &r);
+    assert_eq!(diff.unified().to_string(), "@@ -1 +0,0 @@\n-foo\n");
+}
+
+#[test]
+fn diff_unified() {
+    let old = Rope::from("a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\n");
+    let new = Rope::from("a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\no\n");
+
+    let diff = LineDiff::new(&old, &new);
+
+    assert_eq!(
+        diff.hunks(),
+        [
+            Hunk { old_lines: 1..2, new_lines: 1..2 },
+            Hunk { old_lines: 14..14, new_lines: 14..15 },
+        ]
+    );
+
+    assert_eq!(
+        diff.unified().header("old", "new").to_string(),
+        "--- old\n\
+         +++ new\n\
+         @@ -1,5 +1,5 @@\n a\n-b\n+B\n c\n d\n e\n\
+         @@ -12,3 +12,4 @@\n l\n m\n n\n+o\n"
+    );
+
+    // With more context the two changes end up in the same hunk.
+    assert_eq!(
+        diff.unified().context_len(6).to_string(),
+        "@@ -1,14 +1,15 @@\n a\n-b\n+B\n c\n d\n e\n f\n g\n h\n i\n j\n \
+         k\n l\n m\n n\n+o\n"
+    );
+
+    assert_eq!(
+        diff.unified().context_len(0).to_string(),
+        "@@ -2 +2 @@\n-b\n+B\n@@ -14,0 +15 @@\n+o\n"
+    );
+}
+
+#[test]
+fn diff_missing_trailing_newline() {
+    let old = Rope::from("foo\nbar");
+    let new = Rope::from("foo\nbar\n");
+
+    let hunks = check(&old, &new);
+    assert_eq!(hunks, [Hunk { old_lines: 1..2, new_lines: 1..2 }]);
+
+    assert_eq!(
+        LineDiff::new(&old, &new).unified().to_string(),
+        "@@ -1,2 +1,2 @@\n foo\n-bar\n\\ No newline at end of file\n+bar\n"
+    );
+
+    check(&new, &old);
+}
+
+#[test]
+fn diff_crlf() {
+    let old = Rope::from("a\r\nb\r\n");
+    let new = Rope::from("a\r\nc\r\n");
+
+    check(&old, &new);
+
+    assert_eq!(
+        LineDiff::new(&old, &new).unified().to_string(),
+        "@@ -1,2 +1,2 @@\n a\r\n-b\r\n+c\r\n"
+    );
+
+    // Changing only the line breaks is a change.
+    let old = Rope::from("a\nb\n");
+    let new = Rope::from("a\r\nb\n");
+
+    let hunks = check(&old, &new);
+    assert_eq!(hunks, [Hunk { old_lines: 0..1, new_lines: 0..1 }]);
+
+    assert_eq!(
+        LineDiff::new(&old, &new).unified().to_string(),
+        "@@ -1,2 +1,2 @@\n-a\n+a\r\n b\n"
+    );
+
+    let old = Rope::from("a\r\nb");
+    let new = Rope::from("a\r\nc");
+
+    assert_eq!(
+        LineDiff::new(&old, &new).unified().to_string(),
+        "@@ -1,2 +1,2 @@\n a\r\n-b\n\\ No newline at end of file\n+c\n\\ \
+         No newline at end of file\n"
+    );
+}
+
+#[test]
+fn diff_slices() {
+    let r = Rope::from(MEDIUM);
+
+    let old = r.line_slice(10..40);
+    let new = r.line_slice(20..50);
+
+    let diff = LineDiff::new(old, new);
+    assert!(!diff.is_empty());
+    assert_eq!(diff.old(), old);
+    assert_eq!(diff.new_text(), new);
+}
+
+#[test]
+fn diff_small_edits_in_large_file() {
+    let old = Rope::from(LARGE);
+
+    let mut new = old.clone();
+    let start = new.byte_of_line(100);
+    new.insert(start, "Hello\nthere\n");
+    let start = new.byte_of_line(1000);
+    let end = new.byte_of_line(1003);
+    new.delete(start..end);
+
+    let hunks = check(&old, &new);
+
+    assert_eq!(
+        hunks,
+        [
+            Hunk { old_lines: 100..100, new_lines: 100..102 },
+            Hunk { old_lines: 998..1001, new_lines: 1000..1000 },
+        ]
+    );
+}
+
+#[test]
+fn diff_random_edits() {
+    let mut rng = thread_rng();
+
+    for s in [SMALL, MEDIUM, &CURSED_LIPSUM.repeat(16)] {
+        let old = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut new = old.clone();
+
+            for _ in 0..rng.gen_range(1..=8) {
+                let line_len = new.line_len();
+                let start = rng.gen_range(0..=line_len);
+                let end = (start + rng.gen_range(0..=4)).min(line_len);
+                let (start, end) =
+                    (new.byte_of_line(start), new.byte_of_line(end));
+
+                let replacement = match rng.gen_range(0..3) {
+                    0 => "",
+                    1 => "foo\n\nbar\n",
+                    _ => "Ḽơᶉëᶆ ȋṕšᶙṁ\n",
+                };
+
+                new.replace(start..end, replacement);
+            }
+
+            check(&old, &new);
+            check(&new, &old);
+        }
+    }
+}
+
+/// Checks that the diffs of short texts made of a few distinct lines delete
+/// and insert as few lines as possible, by comparing them with the length of
+/// the longest common subsequence of the two texts.
+#[test]
+fn diff_minimal() {
+    let mut rng = thread_rng();
+
+    let random_text = |rng: &mut rand::rngs::ThreadRng| {
+        (0..rng.gen_range(0..=12))
+            .map(|_| ["a\n", "b\n", "c\n"][rng.gen_range(0..3)])
+            .collect::<String>()
+    };
+
+    for _ in 0..1000 {
+        let old = Rope::from(random_text(&mut rng));
+        let new = Rope::from(random_text(&mut rng));
+
+        let hunks = check(&old, &new);
+
+        let edits = hunks
+            .iter()
+            .map(|hunk| hunk.old_lines.len() + hunk.new_lines.len())
+            .sum::<usize>();
+
+        let (old, new) = (lines(&old), lines(&new));
+
+        let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+
+        for i in 0..old.len() {
+            for j in 0..new.len() {
+                lcs[i + 1][j + 1] = if old[i] == new[j] {
+                    lcs[i][j] + 1
+                } else {
+                    lcs[i][j + 1].max(lcs[i + 1][j])
+                };
+            }
+        }
+
+        let lcs_len = lcs[old.len()][new.len()];
+
+        assert_eq!(edits, old.len() + new.len() - 2 * lcs_len);
+    }
+}
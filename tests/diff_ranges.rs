@@ -0,0 +1,191 @@
+use std::ops::Range;
+
+use crop::{DiffRange, Rope};
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{
+    random_range_at_most, CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY,
+};
+
+/// Checks that a line range is within the rope, and that it's only empty for
+/// an empty byte range past the last line.
+fn assert_lines(r: &Rope, bytes: &Range<usize>, lines: &Range<usize>) {
+    assert!(lines.end <= r.line_len());
+
+    if lines.is_empty() {
+        assert!(bytes.is_empty());
+        assert_eq!(lines.start, r.line_len());
+    }
+}
+
+/// Checks that the ranges returned by `diff_ranges()` are sorted, minimal,
+/// that their line ranges are correct and that they turn the old text into
+/// the new one.
+fn check(old: &Rope, new: &Rope) -> Vec<DiffRange> {
+    let ranges = new.diff_ranges(old);
+
+    let old_text = old.to_string();
+    let new_text = new.to_string();
+
+    for window in ranges.windows(2) {
+        assert!(window[0].old_bytes.end < window[1].old_bytes.start);
+        assert!(window[0].new_bytes.end < window[1].new_bytes.start);
+    }
+
+    let mut patched = old_text.clone();
+
+    for range in ranges.iter().rev() {
+        let old_bytes = &old_text[range.old_bytes.clone()];
+        let new_bytes = &new_text[range.new_bytes.clone()];
+
+        assert_ne!(old_bytes, new_bytes);
+        assert!(old_bytes.chars().next().is_none()
+            || old_bytes.chars().next() != new_bytes.chars().next());
+        assert!(old_bytes.chars().last().is_none()
+            || old_bytes.chars().last() != new_bytes.chars().last());
+
+        assert_eq!(
+            range.old_lines.start,
+            old.line_of_byte(range.old_bytes.start)
+        );
+        assert_eq!(
+            range.new_lines.start,
+            new.line_of_byte(range.new_bytes.start)
+        );
+        assert_lines(old, &range.old_bytes, &range.old_lines);
+        assert_lines(new, &range.new_bytes, &range.new_lines);
+
+        patched.replace_range(range.old_bytes.clone(), new_bytes);
+    }
+
+    assert_eq!(patched, new_text);
+
+    ranges
+}
+
+#[test]
+fn diff_ranges_unchanged() {
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+        assert!(r.diff_ranges(&r).is_empty());
+        assert!(r.clone().diff_ranges(&r).is_empty());
+
+        // These don't share any chunk.
+        assert!(Rope::from(s).diff_ranges(&r).is_empty());
+    }
+
+    assert!(Rope::new().diff_ranges(&Rope::new()).is_empty());
+}
+
+#[test]
+fn diff_ranges_single_edit() {
+    let old = Rope::from(LARGE);
+
+    let mut new = old.clone();
+    new.replace(1000..1010, "Hello\nthere");
+
+    let ranges = check(&old, &new);
+    assert_eq!(ranges.len(), 1);
+
+    let mut new = old.clone();
+    new.delete(..5000);
+    let ranges = check(&old, &new);
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(ranges[0].old_bytes, 0..5000);
+    assert_eq!(ranges[0].new_bytes, 0..0);
+
+    let mut new = old.clone();
+    new.insert(new.byte_len(), "foo");
+    let ranges = check(&old, &new);
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(ranges[0].old_bytes, LARGE.len()..LARGE.len());
+    assert_eq!(ranges[0].new_bytes, LARGE.len()..LARGE.len() + 3);
+}
+
+#[test]
+fn diff_ranges_far_apart_edits() {
+    let old = Rope::from(LARGE);
+
+    let mut new = old.clone();
+    new.insert(LARGE.len() - 100, "baz");
+    new.delete(50_000..50_010);
+    new.insert(100, "foo");
+
+    let ranges = check(&old, &new);
+    assert_eq!(ranges.len(), 3);
+    assert_eq!(ranges[0].old_bytes, 100..100);
+    assert_eq!(ranges[0].new_bytes, 100..103);
+}
+
+#[test]
+fn diff_ranges_random_edits() {
+    let mut rng = thread_rng();
+
+    for s in [SMALL, MEDIUM, LARGE, &CURSED_LIPSUM.repeat(64)] {
+        let old = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut new = old.clone();
+            let mut text = s.to_owned();
+
+            for _ in 0..rng.gen_range(1..=8) {
+                let (start, end) = random_range_at_most(&text, 64);
+                let replacement = match rng.gen_range(0..3) {
+                    0 => "",
+                    1 => "\r\n",
+                    _ => "Ḽơᶉëᶆ ȋṕšᶙṁ\n",
+                };
+                new.replace(start..end, replacement);
+                text.replace_range(start..end, replacement);
+            }
+
+            assert_eq!(new, text);
+
+            check(&old, &new);
+            check(&new, &old);
+        }
+    }
+}
+
+#[test]
+fn diff_ranges_multibyte_chars() {
+    let old = Rope::from("é".repeat(10_000));
+
+    let mut new = old.clone();
+    new.replace(10_000..10_002, "è");
+
+    let ranges = check(&old, &new);
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(ranges[0].old_bytes, 10_000..10_002);
+    assert_eq!(ranges[0].new_bytes, 10_000..10_002);
+}
+
+#[test]
+fn diff_ranges_at_end_of_text() {
+    let old = Rope::from("foo\n");
+
+    let mut new = old.clone();
+    new.insert(4, "bar");
+
+    let ranges = check(&old, &new);
+    assert_eq!(ranges.len(), 1);
+    assert_eq!(ranges[0].old_bytes, 4..4);
+    assert_eq!(ranges[0].old_lines, 1..1);
+    assert_eq!(ranges[0].new_lines, 1..2);
+
+    // The end of a text without a final line break is on its last line.
+    let old = Rope::from("foo");
+
+    let mut new = old.clone();
+    new.insert(3, "bar");
+
+    let ranges = check(&old, &new);
+    assert_eq!(ranges[0].old_bytes, 3..3);
+    assert_eq!(ranges[0].old_lines, 0..1);
+
+    let ranges = check(&Rope::new(), &Rope::from("foo"));
+    assert_eq!(ranges[0].old_lines, 0..0);
+    assert_eq!(ranges[0].new_lines, 0..1);
+}
@@ -0,0 +1,111 @@
+use crop::Rope;
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{
+    build_randomly, random_range, CURSED_LIPSUM, LARGE, MEDIUM, SMALL,
+};
+
+/// Applies a few random edits to both the rope and the string.
+fn edit_randomly(r: &mut Rope, s: &mut String) {
+    let mut rng = thread_rng();
+
+    for _ in 0..rng.gen_range(1..=4) {
+        let (start, end) = random_range(s);
+        let text = if rng.gen() { "" } else { "Ḽơᶉëᶆ\r\n" };
+        r.replace(start..end, text);
+        s.replace_range(start..end, text);
+    }
+}
+
+#[test]
+fn eq_snapshots() {
+    for text in [SMALL, MEDIUM, LARGE, &CURSED_LIPSUM.repeat(32)] {
+        let r = Rope::from(text);
+
+        for _ in 0..20 {
+            let mut snapshot = r.clone();
+            let mut s = text.to_owned();
+            edit_randomly(&mut snapshot, &mut s);
+
+            assert_eq!(r == snapshot, text == s);
+            assert_eq!(snapshot == r, s == text);
+            assert_eq!(snapshot, s);
+
+            for _ in 0..10 {
+                let (start, end) = random_range(text);
+
+                // The same range of the snapshot might not be valid anymore.
+                if end > s.len()
+                    || !s.is_char_boundary(start)
+                    || !s.is_char_boundary(end)
+                {
+                    continue;
+                }
+
+                let slice = r.byte_slice(start..end);
+                let other = snapshot.byte_slice(start..end);
+                let expected = text[start..end] == s[start..end];
+                assert_eq!(slice == other, expected);
+                assert_eq!(other == slice, expected);
+
+                let owned = Rope::from(other);
+                assert_eq!(owned == slice, expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn eq_undone_edits() {
+    let r = Rope::from(LARGE);
+
+    // Undoing an edit gives back an equal rope which only shares part of its
+    // nodes with the original one.
+    let mut snapshot = r.clone();
+    snapshot.insert(LARGE.len() / 2, "foo");
+    snapshot.delete(LARGE.len() / 2..LARGE.len() / 2 + 3);
+    assert_eq!(r, snapshot);
+    assert_eq!(r.byte_slice(1000..), snapshot.byte_slice(1000..));
+}
+
+#[test]
+fn eq_slices_of_same_rope() {
+    let s = CURSED_LIPSUM.repeat(64);
+    let r = Rope::from(s.as_str());
+
+    for _ in 0..100 {
+        let (start, end) = random_range(&s);
+        let len = end - start;
+
+        let mut other_start = thread_rng().gen_range(0..=s.len() - len);
+        while !s.is_char_boundary(other_start)
+            || !s.is_char_boundary(other_start + len)
+        {
+            other_start = thread_rng().gen_range(0..=s.len() - len);
+        }
+
+        let lhs = r.byte_slice(start..end);
+        let rhs = r.byte_slice(other_start..other_start + len);
+
+        let expected = s[start..end] == s[other_start..other_start + len];
+        assert_eq!(lhs == rhs, expected);
+        assert_eq!(lhs, r.byte_slice(start..end));
+    }
+}
+
+#[test]
+fn eq_different_chunking() {
+    for s in [SMALL, MEDIUM, LARGE] {
+        let built = build_randomly(s);
+        let r = Rope::from(s);
+
+        assert_eq!(built, r);
+
+        for _ in 0..20 {
+            let (start, end) = random_range(s);
+            assert_eq!(built.byte_slice(start..end), r.byte_slice(start..end));
+        }
+    }
+}
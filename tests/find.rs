@@ -0,0 +1,161 @@
+use crop::{Rope, RopeSlice};
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+/// Checks that the `find*` methods on the slice return the same matches as
+/// the corresponding methods on the string.
+fn check_str_pattern(slice: RopeSlice<'_>, s: &str, pattern: &str) {
+    let forward = s.match_indices(pattern).map(|(i, _)| i);
+    let backward = s.rmatch_indices(pattern).map(|(i, _)| i);
+
+    assert_eq!(
+        slice.find_iter(pattern).collect::<Vec<_>>(),
+        forward.collect::<Vec<_>>(),
+        "pattern: {pattern:?}"
+    );
+
+    assert_eq!(
+        slice.rfind_iter(pattern).collect::<Vec<_>>(),
+        backward.collect::<Vec<_>>(),
+        "pattern: {pattern:?}"
+    );
+
+    assert_eq!(slice.find(pattern), s.find(pattern));
+    assert_eq!(slice.rfind(pattern), s.rfind(pattern));
+}
+
+fn check_char_pattern(slice: RopeSlice<'_>, s: &str, pattern: char) {
+    assert_eq!(
+        slice.find_iter(pattern).collect::<Vec<_>>(),
+        s.match_indices(pattern).map(|(i, _)| i).collect::<Vec<_>>(),
+    );
+
+    assert_eq!(
+        slice.rfind_iter(pattern).collect::<Vec<_>>(),
+        s.rmatch_indices(pattern).map(|(i, _)| i).collect::<Vec<_>>(),
+    );
+}
+
+fn random_slice<'a>(r: &'a Rope, s: &'a str) -> (RopeSlice<'a>, &'a str) {
+    let mut rng = thread_rng();
+
+    let mut start = rng.gen_range(0..=s.len());
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+
+    let mut end = rng.gen_range(start..=s.len());
+    while !s.is_char_boundary(end) {
+        end += 1;
+    }
+
+    (r.byte_slice(start..end), &s[start..end])
+}
+
+#[test]
+fn find_empty_rope() {
+    let r = Rope::new();
+
+    assert_eq!(r.find('a'), None);
+    assert_eq!(r.rfind("a"), None);
+    assert_eq!(r.find(|_| true), None);
+    assert_eq!(r.find_iter("").collect::<Vec<_>>(), [0]);
+    assert_eq!(r.rfind_iter("").collect::<Vec<_>>(), [0]);
+}
+
+#[test]
+fn find_empty_pattern() {
+    for s in ["a", "aé🐻b", TINY, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+        check_str_pattern(r.byte_slice(..), s, "");
+    }
+}
+
+#[test]
+fn find_overlapping() {
+    let s = "a".repeat(1000);
+    let r = Rope::from(s.as_str());
+
+    for pattern in ["a", "aa", "aaa", "aaaaaaa"] {
+        check_str_pattern(r.byte_slice(..), &s, pattern);
+    }
+}
+
+#[test]
+fn find_spanning_chunks() {
+    let s = "foobar".repeat(500);
+    let r = Rope::from(s.as_str());
+
+    for pattern in ["obarfo", "barfoobarfoobarfoobar", "rf", "foobarx"] {
+        check_str_pattern(r.byte_slice(..), &s, pattern);
+    }
+}
+
+#[test]
+fn find_multibyte() {
+    let s = CURSED_LIPSUM.repeat(10);
+    let r = Rope::from(s.as_str());
+
+    for pattern in ["ᶆ ȋṕ", "ỡ", "\r\n", "ǭᵳ ʂǐť ӓṁệẗ,\r\n "] {
+        check_str_pattern(r.byte_slice(..), &s, pattern);
+    }
+
+    for pattern in ['ṕ', 'ʂ', '\n', 'x'] {
+        check_char_pattern(r.byte_slice(..), &s, pattern);
+    }
+}
+
+#[test]
+fn find_closure() {
+    let r = Rope::from(CURSED_LIPSUM);
+
+    assert_eq!(
+        r.find_iter(char::is_whitespace).collect::<Vec<_>>(),
+        CURSED_LIPSUM
+            .match_indices(char::is_whitespace)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>(),
+    );
+
+    assert_eq!(
+        r.rfind_iter(|ch: char| !ch.is_ascii()).collect::<Vec<_>>(),
+        CURSED_LIPSUM
+            .rmatch_indices(|ch: char| !ch.is_ascii())
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+fn find_random_slices() {
+    let mut rng = thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let r = Rope::from(s);
+
+        for _ in 0..10 {
+            let (slice, s) = random_slice(&r, s);
+
+            for pattern in ["e", "in", "amet", "sit amet", "\n", "xyz", ""] {
+                check_str_pattern(slice, s, pattern);
+            }
+
+            // Also search for a random substring of the slice itself.
+            if !s.is_empty() {
+                let mut start = rng.gen_range(0..s.len());
+                while !s.is_char_boundary(start) {
+                    start -= 1;
+                }
+                let mut end = start + rng.gen_range(1..=32);
+                while end < s.len() && !s.is_char_boundary(end) {
+                    end += 1;
+                }
+                let pattern = &s[start..end.min(s.len())];
+                check_str_pattern(slice, s, pattern);
+            }
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use crop::history::{Edit, History};
+use crop::Rope;
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM};
+
+fn apply(rope: &mut Rope, edits: &[Edit]) {
+    for edit in edits {
+        rope.replace(edit.byte_range.clone(), &edit.text);
+    }
+}
+
+#[test]
+fn history_typing_is_coalesced() {
+    let mut history = History::new(Rope::new());
+
+    for (offset, ch) in "Hello".char_indices() {
+        history.insert(offset, ch.encode_utf8(&mut [0; 4]));
+    }
+
+    history.insert(5, "\n");
+    history.insert(6, "World");
+
+    assert_eq!(history.rope(), "Hello\nWorld");
+    assert_eq!(history.revision_len(), 4);
+
+    history.undo();
+    assert_eq!(history.rope(), "Hello\n");
+    history.undo();
+    assert_eq!(history.rope(), "Hello");
+    history.undo();
+    assert_eq!(history.rope(), "");
+    assert!(history.undo().is_none());
+}
+
+#[test]
+fn history_backspace_is_coalesced() {
+    let mut history = History::new(Rope::from("foo bar"));
+
+    for end in (1..=7).rev() {
+        history.delete(end - 1..end);
+    }
+
+    assert_eq!(history.rope(), "");
+    assert_eq!(history.revision_len(), 2);
+
+    let inverse = history.undo().unwrap().to_vec();
+    assert_eq!(history.rope(), "foo bar");
+
+    let mut r = Rope::new();
+    apply(&mut r, &inverse);
+    assert_eq!(r, "foo bar");
+
+    // Deleting forward is coalesced too.
+    let mut history = History::new(Rope::from("foo bar"));
+    history.delete(0..1);
+    history.delete(0..1);
+    history.checkpoint();
+    history.delete(0..1);
+    assert_eq!(history.rope(), " bar");
+    assert_eq!(history.revision_len(), 3);
+}
+
+#[test]
+fn history_transactions() {
+    let mut history = History::new(Rope::from("foo"));
+
+    history.insert(3, " ");
+
+    let len = history.transaction(|tx| {
+        tx.insert(4, "bar");
+        tx.replace(0..3, "baz");
+        tx.rope().byte_len()
+    });
+
+    assert_eq!(len, 7);
+    assert_eq!(history.rope(), "baz bar");
+
+    // The transaction isn't coalesced with the previous insertion, nor with
+    // the next one.
+    history.insert(7, "!");
+    assert_eq!(history.revision_len(), 4);
+
+    let edits = history.undo().unwrap();
+    assert_eq!(edits, [Edit { byte_range: 7..8, text: String::new() }]);
+
+    let edits = history.undo().unwrap().to_vec();
+    assert_eq!(edits.len(), 2);
+    assert_eq!(history.rope(), "foo ");
+
+    let mut r = Rope::from("baz bar");
+    apply(&mut r, &edits);
+    assert_eq!(r, "foo ");
+
+    // Empty transactions don't create revisions.
+    history.transaction(|_| {});
+    assert_eq!(history.revision_len(), 4);
+}
+
+#[test]
+fn history_branches() {
+    let mut history = History::new(Rope::from("a"));
+
+    history.insert(1, "b");
+    let b = history.revision();
+
+    history.undo();
+    history.insert(1, "c");
+    let c = history.revision();
+    history.checkpoint();
+    history.insert(2, "d");
+    let d = history.revision();
+
+    history.undo();
+    history.undo();
+    assert_eq!(history.rope(), "a");
+
+    // Redo follows the last branch.
+    history.redo();
+    assert_eq!(history.revision(), c);
+    history.redo();
+    assert_eq!(history.revision(), d);
+    assert!(history.redo().is_none());
+
+    history.jump_to(b);
+    assert_eq!(history.rope(), "ab");
+
+    // After jumping, redoing from the root leads to the branch we jumped to.
+    history.undo();
+    history.redo();
+    assert_eq!(history.revision(), b);
+
+    history.jump_to(d);
+    assert_eq!(history.rope(), "acd");
+}
+
+#[test]
+#[should_panic]
+fn history_jump_to_out_of_bounds() {
+    let mut history = History::new(Rope::new());
+    history.jump_to(1);
+}
+
+#[test]
+fn history_random_undo_redo() {
+    let mut rng = thread_rng();
+
+    for s in [MEDIUM, &CURSED_LIPSUM.repeat(16)] {
+        let mut history = History::new(Rope::from(s));
+
+        // The text at every revision.
+        let mut texts = vec![s.to_owned()];
+
+        for _ in 0..200 {
+            match rng.gen_range(0..5) {
+                0 => {
+                    let current = history.rope().clone();
+                    let revision = history.revision();
+
+                    if let Some(edits) = history.undo() {
+                        let mut r = current;
+                        apply(&mut r, edits);
+                        assert_eq!(&r, history.rope());
+                        assert_ne!(history.revision(), revision);
+                    }
+                },
+                1 => {
+                    let current = history.rope().clone();
+
+                    if let Some(edits) = history.redo() {
+                        let mut r = current;
+                        apply(&mut r, edits);
+                        assert_eq!(&r, history.rope());
+                    }
+                },
+                2 => {
+                    let revision = rng.gen_range(0..history.revision_len());
+                    history.jump_to(revision);
+                },
+                _ => {
+                    let text = history.rope().to_string();
+
+                    let mut start = rng.gen_range(0..=text.len());
+                    while !text.is_char_boundary(start) {
+                        start -= 1;
+                    }
+
+                    let mut end =
+                        (start + rng.gen_range(0..=8)).min(text.len());
+                    while !text.is_char_boundary(end) {
+                        end += 1;
+                    }
+
+                    history.replace(start..end, "Ḽơᶉëᶆ");
+                },
+            }
+
+            // Edits coalesced into the current revision change its text, so
+            // it has to be recorded after every step.
+            texts.resize(history.revision_len(), String::new());
+            texts[history.revision()] = history.rope().to_string();
+        }
+
+        for (revision, text) in texts.iter().enumerate() {
+            history.jump_to(revision);
+            assert_eq!(history.rope(), text.as_str());
+        }
+    }
+}
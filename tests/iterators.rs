@@ -1,3 +1,5 @@
+#![allow(clippy::needless_as_bytes)]
+
 use crop::Rope;
 use rand::{thread_rng, Rng};
 
@@ -618,3 +620,251 @@ fn iter_raw_lines_over_random_slices() {
         }
     }
 }
+
+/// Drives `iter` with a random sequence of `next` and `next_back` calls,
+/// checking that it moves over `expected` starting from the `idx`-th item.
+fn check_walk<I, E>(mut iter: I, expected: &[E], mut idx: usize)
+where
+    I: DoubleEndedIterator,
+    I::Item: PartialEq<E> + core::fmt::Debug,
+    E: core::fmt::Debug,
+{
+    let mut rng = thread_rng();
+
+    for _ in 0..1000 {
+        let (lo, _) = iter.size_hint();
+        assert!(lo <= core::cmp::min(idx, expected.len() - idx));
+
+        if rng.gen() {
+            match expected.get(idx) {
+                Some(item) => {
+                    assert_eq!(iter.next().unwrap(), *item);
+                    idx += 1;
+                },
+                None => assert!(iter.next().is_none()),
+            }
+        } else if idx == 0 {
+            assert!(iter.next_back().is_none());
+        } else {
+            idx -= 1;
+            assert_eq!(iter.next_back().unwrap(), expected[idx]);
+        }
+    }
+}
+
+/// Tests that the `*_at` iterators yield the same items as the equivalent
+/// string iterators on both sides of a random starting position.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn iter_at_random() {
+    let mut rng = thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+        let chars = s.chars().collect::<Vec<_>>();
+        let lines = r.lines().collect::<Vec<_>>();
+
+        for _ in 0..20 {
+            let mut at = rng.gen_range(0..=s.len());
+            while !s.is_char_boundary(at) {
+                at += 1;
+            }
+
+            let bytes = r.bytes_at(at);
+            assert!(bytes.clone().eq(s[at..].bytes()));
+            assert!(bytes.clone().rev().eq(s[..at].bytes().rev()));
+            check_walk(bytes, s.as_bytes(), at);
+
+            assert!(r.chars_at(at).eq(s[at..].chars()));
+            assert!(r.chars_at(at).rev().eq(s[..at].chars().rev()));
+            let char_idx = s[..at].chars().count();
+            check_walk(r.chars_at(at), &chars, char_idx);
+
+            let chunks = r.chunks_at(at);
+            assert_eq!(chunks.clone().collect::<String>(), s[at..]);
+            let mut before = chunks.rev().collect::<Vec<_>>();
+            before.reverse();
+            assert_eq!(before.concat(), s[..at]);
+
+            let line_offset = rng.gen_range(0..=r.line_len());
+            let lines_at = r.lines_at(line_offset);
+            assert!(lines_at.clone().eq(lines[line_offset..].iter().copied()));
+            assert!(lines_at
+                .clone()
+                .rev()
+                .eq(lines[..line_offset].iter().rev().copied()));
+            check_walk(lines_at, &lines, line_offset);
+        }
+    }
+}
+
+#[test]
+fn iter_at_turn_around() {
+    let r = Rope::from("foo\nbar");
+
+    let mut bytes = r.bytes_at(3);
+    assert_eq!(Some(b'\n'), bytes.next());
+    assert_eq!(Some(b'b'), bytes.next());
+    assert_eq!(Some(b'b'), bytes.next_back());
+    assert_eq!(Some(b'\n'), bytes.next_back());
+    assert_eq!(Some(b'o'), bytes.next_back());
+    assert_eq!(2, bytes.byte_offset());
+
+    let mut chunks = r.chunks_at(3);
+    assert_eq!("\nbar", chunks.by_ref().collect::<String>());
+    assert_eq!(r.byte_len(), chunks.byte_offset());
+    let mut back = chunks.by_ref().rev().collect::<Vec<_>>();
+    back.reverse();
+    assert_eq!("foo\nbar", back.concat());
+    assert_eq!(0, chunks.byte_offset());
+    assert_eq!("foo\nbar", chunks.collect::<String>());
+
+    let mut lines = r.lines_at(1);
+    assert_eq!("bar", lines.next().unwrap());
+    assert_eq!("bar", lines.next_back().unwrap());
+    assert_eq!("foo", lines.next_back().unwrap());
+    assert_eq!(None, lines.next_back());
+    assert_eq!(0, lines.line_offset());
+
+    let r = Rope::new();
+
+    assert_eq!(None, r.bytes_at(0).next());
+    assert_eq!(None, r.chars_at(0).next_back());
+    assert_eq!(None, r.chunks_at(0).next());
+    assert_eq!(None, r.lines_at(0).next_back());
+}
+
+#[test]
+fn iter_at_over_slice() {
+    let r = Rope::from("foo\nbar\nbaz\nqux");
+    let s = r.byte_slice(4..11);
+
+    let mut chars = s.chars_at(3);
+    assert_eq!(Some('\n'), chars.next());
+    assert_eq!(Some('b'), chars.next());
+
+    let mut chars = s.chars_at(3).rev();
+    assert_eq!(Some('r'), chars.next());
+    assert_eq!(Some('a'), chars.next());
+    assert_eq!(Some('b'), chars.next());
+    assert_eq!(None, chars.next());
+
+    let mut lines = s.lines_at(1);
+    assert_eq!("baz", lines.next().unwrap());
+    assert_eq!(None, lines.next());
+    assert_eq!("baz", lines.next_back().unwrap());
+    assert_eq!("bar", lines.next_back().unwrap());
+    assert_eq!(None, lines.next_back());
+}
+
+#[should_panic]
+#[test]
+fn iter_at_out_of_bounds() {
+    let r = Rope::from("foo");
+    let _ = r.bytes_at(4);
+}
+
+/// Drives `iter` with a random sequence of `next`, `next_back`, `nth` and
+/// `nth_back` calls, checking that it yields the same items as `expected`.
+fn check_nth<I, E>(mut iter: I, expected: &[E])
+where
+    I: DoubleEndedIterator,
+    I::Item: PartialEq<E> + core::fmt::Debug,
+    E: core::fmt::Debug,
+{
+    let mut rng = thread_rng();
+
+    let (mut start, mut end) = (0, expected.len());
+
+    loop {
+        let n = rng.gen_range(0..=(end - start) / 2 + 1);
+
+        let (item, skipped, forward) = match rng.gen_range(0..4) {
+            0 => (iter.next(), 0, true),
+            1 => (iter.next_back(), 0, false),
+            2 => (iter.nth(n), n, true),
+            _ => (iter.nth_back(n), n, false),
+        };
+
+        if skipped >= end - start {
+            assert!(item.is_none());
+            assert!(iter.next().is_none());
+            assert!(iter.next_back().is_none());
+            return;
+        }
+
+        if forward {
+            start += skipped;
+            assert_eq!(item.unwrap(), expected[start]);
+            start += 1;
+        } else {
+            end -= skipped + 1;
+            assert_eq!(item.unwrap(), expected[end]);
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn iter_nth_random() {
+    let mut rng = thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut start = rng.gen_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.gen_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            let s = &s[start..end];
+
+            let bytes = s.bytes().collect::<Vec<_>>();
+            check_nth(slice.bytes(), &bytes);
+
+            let chars = s.chars().collect::<Vec<_>>();
+            check_nth(slice.chars(), &chars);
+
+            let lines = s.lines().collect::<Vec<_>>();
+            check_nth(slice.lines(), &lines);
+
+            let raw_lines = s.split_inclusive('\n').collect::<Vec<_>>();
+            check_nth(slice.raw_lines(), &raw_lines);
+        }
+
+        check_nth(r.bytes(), &s.bytes().collect::<Vec<_>>());
+        check_nth(r.chars(), &s.chars().collect::<Vec<_>>());
+        check_nth(r.lines(), &s.lines().collect::<Vec<_>>());
+        check_nth(r.raw_lines(), &s.split_inclusive('\n').collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn iter_nth_past_the_end() {
+    let r = Rope::from("foo\nbar\r\nbaz\n");
+
+    let mut lines = r.lines();
+    assert_eq!("bar", lines.nth(1).unwrap());
+    assert_eq!(1, lines.len());
+    assert_eq!(None, lines.nth(1));
+    assert_eq!(0, lines.len());
+    assert_eq!(None, lines.next());
+
+    let mut lines = r.lines();
+    assert_eq!("foo", lines.nth_back(2).unwrap());
+    assert_eq!(None, lines.nth_back(0));
+
+    let mut bytes = r.bytes();
+    assert_eq!(Some(b'b'), bytes.nth(4));
+    assert_eq!(Some(b'z'), bytes.nth_back(1));
+    assert_eq!(6, bytes.len());
+    assert_eq!(None, bytes.nth(6));
+    assert_eq!(None, bytes.next_back());
+}
@@ -0,0 +1,320 @@
+use crop::lsp::Encoding;
+use crop::Rope;
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TEXT_EMOJI, TINY};
+
+const ENCODINGS: [Encoding; 3] =
+    [Encoding::Utf8, Encoding::Utf16, Encoding::Utf32];
+
+#[test]
+fn byte_of_position_clamping() {
+    let r = Rope::from("a🐸\r\nb\n");
+
+    for encoding in ENCODINGS {
+        // Columns past the end of a line stop before its line break.
+        assert_eq!(r.byte_of_position(0, 100, encoding), 5);
+        assert_eq!(r.byte_of_position(1, 100, encoding), 8);
+
+        // The empty line after the final line break is the end of the text.
+        assert_eq!(r.byte_of_position(2, 0, encoding), 9);
+        assert_eq!(r.byte_of_position(2, 100, encoding), 9);
+        assert_eq!(r.byte_of_position(100, 0, encoding), 9);
+    }
+
+    // Columns inside the frog are rounded down to its start.
+    assert_eq!(r.byte_of_position(0, 2, Encoding::Utf8), 1);
+    assert_eq!(r.byte_of_position(0, 2, Encoding::Utf16), 1);
+    assert_eq!(r.byte_of_position(0, 3, Encoding::Utf16), 5);
+
+    let r = Rope::new();
+
+    for encoding in ENCODINGS {
+        assert_eq!(r.byte_of_position(0, 0, encoding), 0);
+        assert_eq!(r.byte_of_position(1, 1, encoding), 0);
+        assert_eq!(r.position_of_byte(0, encoding), (0, 0));
+    }
+}
+
+#[test]
+fn position_of_byte_line_breaks() {
+    let r = Rope::from("a🐸\r\nb\n");
+
+    assert_eq!(r.position_of_byte(5, Encoding::Utf8), (0, 5));
+    assert_eq!(r.position_of_byte(5, Encoding::Utf16), (0, 3));
+    assert_eq!(r.position_of_byte(5, Encoding::Utf32), (0, 2));
+
+    // Between the `\r` and the `\n`.
+    assert_eq!(r.position_of_byte(6, Encoding::Utf16), (0, 4));
+
+    assert_eq!(r.position_of_byte(7, Encoding::Utf16), (1, 0));
+    assert_eq!(r.position_of_byte(9, Encoding::Utf16), (2, 0));
+}
+
+#[test]
+#[should_panic]
+fn position_of_byte_out_of_bounds() {
+    let r = Rope::from("foo");
+    r.position_of_byte(4, Encoding::Utf8);
+}
+
+#[test]
+#[should_panic]
+fn position_of_byte_not_char_boundary() {
+    let r = Rope::from("🐸");
+    r.position_of_byte(2, Encoding::Utf16);
+}
+
+/// Checks the conversions against a naive implementation working on the
+/// whole string.
+#[test]
+fn positions_random() {
+    let mut rng = thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM, TEXT_EMOJI] {
+        let r = Rope::from(s);
+
+        let mut start = rng.gen_range(0..=s.len());
+        while !s.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        let slice = r.byte_slice(start..);
+        let full = s;
+        let s = &s[start..];
+
+        let line_len = s.matches('\n').count() + 1;
+
+        for _ in 0..100 {
+            let encoding = ENCODINGS[rng.gen_range(0..ENCODINGS.len())];
+
+            let mut byte_offset = rng.gen_range(0..=s.len());
+            while !s.is_char_boundary(byte_offset) {
+                byte_offset -= 1;
+            }
+
+            assert_eq!(
+                slice.position_of_byte(byte_offset, encoding),
+                position_of_byte(s, byte_offset, encoding),
+            );
+
+            assert_eq!(
+                r.position_of_byte(start + byte_offset, encoding),
+                position_of_byte(full, start + byte_offset, encoding),
+            );
+
+            let line = rng.gen_range(0..=line_len);
+            let column = rng.gen_range(0..=100);
+
+            assert_eq!(
+                slice.byte_of_position(line, column, encoding),
+                byte_of_position(s, line, column, encoding),
+            );
+
+            assert_eq!(
+                r.byte_of_position(line, column, encoding),
+                byte_of_position(full, line, column, encoding),
+            );
+        }
+    }
+}
+
+fn len(s: &str, encoding: Encoding) -> usize {
+    match encoding {
+        Encoding::Utf8 => s.len(),
+        Encoding::Utf16 => s.encode_utf16().count(),
+        Encoding::Utf32 => s.chars().count(),
+    }
+}
+
+/// Returns the byte ranges of the lines of the string, without their line
+/// breaks.
+fn lines(s: &str) -> Vec<(usize, usize)> {
+    let mut lines = Vec::new();
+    let mut start = 0;
+
+    for (idx, _) in s.match_indices('\n') {
+        let end = if s[..idx].ends_with('\r') { idx - 1 } else { idx };
+        lines.push((start, end));
+        start = idx + 1;
+    }
+
+    lines.push((start, s.len()));
+    lines
+}
+
+fn byte_of_position(
+    s: &str,
+    line: usize,
+    column: usize,
+    encoding: Encoding,
+) -> usize {
+    let Some(&(start, end)) = lines(s).get(line) else {
+        return s.len();
+    };
+
+    let mut byte_offset = start;
+
+    for ch in s[start..end].chars() {
+        let next = byte_offset + ch.len_utf8();
+        if len(&s[start..next], encoding) > column {
+            break;
+        }
+        byte_offset = next;
+    }
+
+    byte_offset
+}
+
+fn position_of_byte(
+    s: &str,
+    byte_offset: usize,
+    encoding: Encoding,
+) -> (usize, usize) {
+    let line = s[..byte_offset].matches('\n').count();
+    let start = s[..byte_offset].rfind('\n').map_or(0, |idx| idx + 1);
+    (line, len(&s[start..byte_offset], encoding))
+}
+
+#[cfg(feature = "utf16-metric")]
+mod apply_lsp_changes {
+    use std::ops::Range;
+
+    use crop::lsp::{ContentChange, ContentChangeError, Encoding};
+    use crop::Rope;
+    use rand::{thread_rng, Rng};
+
+    use super::position_of_byte;
+    use crate::common::{CURSED_LIPSUM, MEDIUM};
+
+    fn change(range: Range<(usize, usize)>, text: &str) -> ContentChange {
+        ContentChange { range: Some(range), text: text.to_owned() }
+    }
+
+    #[test]
+    fn apply_lsp_changes_sequential() {
+        let mut r = Rope::from("foo\r\nbar\n");
+
+        r.apply_lsp_changes(&[
+            change((0, 3)..(1, 0), " "),
+            // This refers to the document after the first change.
+            change((0, 100)..(0, 100), "!"),
+            change((1, 0)..(1, 0), "🦀"),
+            // This is in the middle of the crab, so it's rounded down.
+            change((1, 1)..(1, 1), "x"),
+        ])
+        .unwrap();
+
+        assert_eq!(r, "foo bar!\nx🦀");
+
+        r.apply_lsp_changes(&[
+            ContentChange { range: None, text: "baz".to_owned() },
+            change((0, 0)..(0, 1), "B"),
+        ])
+        .unwrap();
+
+        assert_eq!(r, "Baz");
+    }
+
+    #[test]
+    fn apply_lsp_changes_errors() {
+        let mut r = Rope::from("foo\nbar");
+
+        assert_eq!(
+            r.apply_lsp_changes(&[
+                change((0, 0)..(0, 0), "\n"),
+                change((1, 2)..(1, 1), ""),
+            ]),
+            Err(ContentChangeError::StartAfterEnd {
+                change_idx: 1,
+                range: (1, 2)..(1, 1),
+            })
+        );
+
+        assert_eq!(
+            r.apply_lsp_changes(&[
+                change((1, 0)..(1, 3), ""),
+                change((3, 0)..(2, 0), ""),
+            ]),
+            Err(ContentChangeError::StartAfterEnd {
+                change_idx: 1,
+                range: (3, 0)..(2, 0),
+            })
+        );
+
+        // The rope is left untouched if any of the changes is invalid.
+        assert_eq!(r, "foo\nbar");
+    }
+
+    #[test]
+    fn apply_lsp_changes_end_of_document() {
+        let mut r = Rope::from("a\nb");
+        r.apply_lsp_changes(&[change((0, 0)..(2, 0), "")]).unwrap();
+        assert_eq!(r, "");
+
+        // The empty line after a final line break is part of the document.
+        let mut r = Rope::from("a\n");
+        r.apply_lsp_changes(&[change((1, 0)..(1, 0), "b")]).unwrap();
+        assert_eq!(r, "a\nb");
+
+        // Lines past the last one are clamped to the end of the document.
+        r.apply_lsp_changes(&[change((0, 1)..(5, 3), "!")]).unwrap();
+        assert_eq!(r, "a!");
+    }
+
+    fn position(s: &str, byte_offset: usize) -> (usize, usize) {
+        position_of_byte(s, byte_offset, Encoding::Utf16)
+    }
+
+    /// Checks that batches of random changes give the same text as applying
+    /// them to a `String`.
+    #[test]
+    fn apply_lsp_changes_random() {
+        let mut rng = thread_rng();
+
+        for s in [MEDIUM, CURSED_LIPSUM] {
+            let mut r = Rope::from(s);
+            let mut expected = s.to_owned();
+
+            for _ in 0..50 {
+                let mut changes = Vec::new();
+
+                for _ in 0..rng.gen_range(1..5) {
+                    let random_offset = |rng: &mut rand::rngs::ThreadRng| {
+                        let mut offset = rng.gen_range(0..=expected.len());
+                        while !expected.is_char_boundary(offset) {
+                            offset -= 1;
+                        }
+                        // Clients never send positions between a `\r` and
+                        // a `\n`, since they're both line breaks for them.
+                        if expected[..offset].ends_with('\r') {
+                            offset -= 1;
+                        }
+                        offset
+                    };
+
+                    let start = random_offset(&mut rng);
+                    let end = random_offset(&mut rng).max(start);
+
+                    let text = match rng.gen_range(0..4) {
+                        0 => "",
+                        1 => "\n",
+                        2 => "\r\n🦀",
+                        _ => "Ḽơᶉëᶆ",
+                    };
+
+                    let range = position(&expected, start)
+                        ..position(&expected, end);
+
+                    expected.replace_range(start..end, text);
+                    changes.push(change(range, text));
+                }
+
+                r.apply_lsp_changes(&changes).unwrap();
+                assert_eq!(r, expected);
+            }
+        }
+    }
+}
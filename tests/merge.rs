@@ -0,0 +1,175 @@
+use crop::merge::{merge3, Conflict};
+use crop::Rope;
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{MEDIUM, SMALL};
+
+/// Replaces a few random lines of the rope, returning the (sorted and
+/// non-overlapping) ranges of lines that were replaced.
+fn edit_randomly(r: &mut Rope, tag: &str) -> Vec<std::ops::Range<usize>> {
+    let mut rng = thread_rng();
+
+    let mut line_idxs = (0..rng.gen_range(1..=4))
+        .map(|_| rng.gen_range(0..r.line_len()))
+        .collect::<Vec<_>>();
+
+    line_idxs.sort_unstable();
+    line_idxs.dedup();
+
+    for &line_idx in line_idxs.iter().rev() {
+        let start = r.byte_of_line(line_idx);
+        let end = r.byte_of_line(line_idx + 1);
+        r.replace(start..end, format!("{tag} {line_idx}\n"));
+    }
+
+    line_idxs.into_iter().map(|idx| idx..idx + 1).collect()
+}
+
+#[test]
+fn merge_line_breaks() {
+    let base = Rope::from("a\nb\n");
+
+    // Only their line breaks change, which mustn't be dropped.
+    let theirs = Rope::from("a\r\nb\r\n");
+
+    let merge = merge3(&base, &base, &theirs);
+    assert!(merge.is_clean());
+    assert_eq!(merge.rope(), "a\r\nb\r\n");
+
+    let base = Rope::from("a\nb\nc\n");
+    let ours = Rope::from("A\nb\nc\n");
+    let theirs = Rope::from("a\nb\nc\r\n");
+
+    let merge = merge3(&base, &ours, &theirs);
+    assert!(merge.is_clean());
+    assert_eq!(merge.rope(), "A\nb\nc\r\n");
+
+    // Changing the line break of a line the other side edited conflicts.
+    let theirs = Rope::from("a\r\nb\nc\n");
+
+    let merge = merge3(&base, &ours, &theirs);
+    assert_eq!(
+        merge.conflicts(),
+        [Conflict { base: 0..2, ours: 0..2, theirs: 0..3, merged: 0..2 }]
+    );
+}
+
+#[test]
+fn merge_no_changes() {
+    let base = Rope::from(SMALL);
+
+    let merge = merge3(&base, &base, &base);
+    assert!(merge.is_clean());
+    assert_eq!(merge.rope(), &base);
+}
+
+#[test]
+fn merge_one_side() {
+    let base = Rope::from(MEDIUM);
+
+    let mut ours = base.clone();
+    edit_randomly(&mut ours, "ours");
+
+    let merge = merge3(&base, &ours, &base);
+    assert!(merge.is_clean());
+    assert_eq!(merge.rope(), &ours);
+
+    let merge = merge3(&base, &base, &ours);
+    assert!(merge.is_clean());
+    assert_eq!(merge.into_rope(), ours);
+}
+
+#[test]
+fn merge_same_changes() {
+    let base = Rope::from(MEDIUM);
+
+    let mut ours = base.clone();
+    edit_randomly(&mut ours, "same");
+
+    let theirs = ours.clone();
+
+    let merge = merge3(&base, &ours, &theirs);
+    assert!(merge.is_clean());
+    assert_eq!(merge.rope(), &ours);
+}
+
+#[test]
+fn merge_random_edits() {
+    let base = Rope::from(MEDIUM);
+
+    for _ in 0..50 {
+        let mut ours = base.clone();
+        let ours_edits = edit_randomly(&mut ours, "ours");
+
+        let mut theirs = base.clone();
+        let theirs_edits = edit_randomly(&mut theirs, "theirs");
+
+        let merge = merge3(&base, &ours, &theirs);
+
+        // Edits to the same or adjacent lines conflict.
+        let touching = ours_edits.iter().any(|o| {
+            theirs_edits.iter().any(|t| o.start <= t.end && t.start <= o.end)
+        });
+
+        assert_eq!(merge.is_clean(), !touching);
+
+        for conflict in merge.conflicts() {
+            assert_eq!(
+                merge.rope().byte_slice(conflict.merged.clone()),
+                ours.byte_slice(conflict.ours.clone())
+            );
+        }
+
+        if merge.is_clean() {
+            // Applying both sets of edits in any order gives the same result.
+            let mut expected = ours.clone();
+            for edit in theirs_edits.iter().rev() {
+                let start = theirs.byte_of_line(edit.start);
+                let end = theirs.byte_of_line(edit.end);
+                let replacement = theirs.byte_slice(start..end).to_string();
+
+                let start = expected.byte_of_line(edit.start);
+                let end = expected.byte_of_line(edit.end);
+                expected.replace(start..end, replacement);
+            }
+            assert_eq!(merge.rope(), &expected);
+        }
+    }
+}
+
+#[test]
+fn merge_conflict_markers() {
+    let base = Rope::from("a\nb\nc\nd");
+    let ours = Rope::from("a\nb\nc\nours");
+    let theirs = Rope::from("a\nB\nc\ntheirs\n");
+
+    let merge = merge3(&base, &ours, &theirs);
+
+    assert_eq!(
+        merge.conflicts(),
+        [Conflict { base: 6..7, ours: 6..10, theirs: 6..13, merged: 6..10 }]
+    );
+
+    // The change to the second line doesn't conflict.
+    assert_eq!(merge.rope(), "a\nB\nc\nours");
+
+    assert_eq!(
+        merge.render_conflicts("buffer", "disk"),
+        "a\nB\nc\n<<<<<<< buffer\nours\n=======\ntheirs\n>>>>>>> disk\n"
+    );
+}
+
+#[test]
+fn merge_slices() {
+    let r = Rope::from("a\nb\nc\nd\ne\nf\n");
+
+    let base = r.line_slice(1..5);
+    let ours = r.line_slice(0..5);
+    let theirs = r.line_slice(1..6);
+
+    let merge = merge3(base, ours, theirs);
+    assert!(merge.is_clean());
+    assert_eq!(merge.rope(), &r);
+}
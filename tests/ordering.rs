@@ -0,0 +1,122 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashSet};
+use std::hash::{Hash, Hasher};
+
+use crop::Rope;
+
+mod common;
+
+use common::{
+    build_randomly, random_range, CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY,
+};
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn hash_empty() {
+    let r = Rope::new();
+    assert_eq!(hash(&r), hash(&Rope::from("")));
+    assert_eq!(hash(&r), hash(&r.byte_slice(..)));
+    assert_eq!(hash(&r), hash(&Rope::from("foo").byte_slice(1..1)));
+}
+
+/// Checks that the hash of a `Rope` or `RopeSlice` only depends on its text,
+/// and not on how it's split into chunks.
+#[test]
+fn hash_independent_of_chunks() {
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+        let built = build_randomly(s);
+
+        assert_eq!(hash(&built), hash(&r));
+        assert_eq!(hash(&r.byte_slice(..)), hash(&r));
+        assert_eq!(hash(&built.byte_slice(..)), hash(&r));
+
+        for _ in 0..10 {
+            let (start, end) = random_range(s);
+            let expected = hash(&Rope::from(&s[start..end]));
+            assert_eq!(hash(&r.byte_slice(start..end)), expected);
+            assert_eq!(hash(&built.byte_slice(start..end)), expected);
+        }
+    }
+}
+
+#[test]
+fn hash_set_of_ropes() {
+    let mut set = HashSet::new();
+    set.insert(Rope::from(MEDIUM));
+    set.insert(build_randomly(MEDIUM));
+    set.insert(Rope::from(SMALL));
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&build_randomly(SMALL)));
+}
+
+#[test]
+fn cmp_matches_str() {
+    for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+        let built = build_randomly(s);
+
+        for _ in 0..50 {
+            let (lhs_start, lhs_end) = random_range(s);
+            let (rhs_start, rhs_end) = random_range(s);
+
+            let lhs_str = &s[lhs_start..lhs_end];
+            let rhs_str = &s[rhs_start..rhs_end];
+            let expected = lhs_str.cmp(rhs_str);
+
+            let lhs = r.byte_slice(lhs_start..lhs_end);
+            let rhs = built.byte_slice(rhs_start..rhs_end);
+
+            assert_eq!(lhs.cmp(&rhs), expected);
+            assert_eq!(rhs.cmp(&lhs), expected.reverse());
+            assert_eq!(lhs.partial_cmp(rhs_str), Some(expected));
+            assert_eq!(lhs_str.partial_cmp(&rhs), Some(expected));
+
+            let lhs = Rope::from(lhs);
+            assert_eq!(lhs.cmp(&Rope::from(rhs)), expected);
+            assert_eq!(lhs.partial_cmp(&rhs), Some(expected));
+            assert_eq!(rhs.partial_cmp(&lhs), Some(expected.reverse()));
+            assert_eq!(lhs.partial_cmp(&rhs_str.to_owned()), Some(expected));
+            assert_eq!(rhs_str.partial_cmp(&lhs), Some(expected.reverse()));
+        }
+    }
+}
+
+#[test]
+fn cmp_prefixes() {
+    let r = Rope::from("foo");
+
+    assert_eq!(r.partial_cmp(""), Some(Ordering::Greater));
+    assert_eq!(r.partial_cmp("fo"), Some(Ordering::Greater));
+    assert_eq!(r.partial_cmp("foo"), Some(Ordering::Equal));
+    assert_eq!(r.partial_cmp("foo\n"), Some(Ordering::Less));
+    assert_eq!(r.partial_cmp("fop"), Some(Ordering::Less));
+
+    assert_eq!(r.byte_slice(..2).cmp(&r.byte_slice(..)), Ordering::Less);
+    assert_eq!(r.byte_slice(1..).cmp(&r.byte_slice(..)), Ordering::Greater);
+    assert_eq!(Rope::new().cmp(&r), Ordering::Less);
+    assert_eq!(Rope::new().cmp(&Rope::new()), Ordering::Equal);
+}
+
+#[test]
+fn sort_ropes() {
+    let lines = CURSED_LIPSUM.lines().collect::<Vec<_>>();
+
+    let set =
+        lines.iter().map(|&line| Rope::from(line)).collect::<BTreeSet<_>>();
+
+    let mut expected = lines.clone();
+    expected.sort();
+    expected.dedup();
+
+    assert_eq!(set.len(), expected.len());
+    for (rope, line) in set.iter().zip(expected) {
+        assert_eq!(rope, line);
+    }
+}
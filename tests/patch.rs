@@ -0,0 +1,177 @@
+use crop::diff::LineDiff;
+use crop::{PatchError, Rope};
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL};
+
+/// Applies random line edits to the rope.
+fn edit_randomly(r: &mut Rope) {
+    let mut rng = thread_rng();
+
+    for _ in 0..rng.gen_range(1..=8) {
+        let line_len = r.line_len();
+        let start = rng.gen_range(0..=line_len);
+        let end = (start + rng.gen_range(0..=4)).min(line_len);
+        let (start, end) = (r.byte_of_line(start), r.byte_of_line(end));
+
+        let replacement = match rng.gen_range(0..3) {
+            0 => "",
+            1 => "foo\n\nbar\n",
+            _ => "Ḽơᶉëᶆ ȋṕšᶙṁ\n",
+        };
+
+        r.replace(start..end, replacement);
+    }
+}
+
+#[test]
+fn patch_round_trip() {
+    for s in [SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let old = Rope::from(s);
+
+        for _ in 0..10 {
+            let mut new = old.clone();
+            edit_randomly(&mut new);
+
+            let diff = LineDiff::new(&old, &new);
+
+            for context_len in [0, 1, 3] {
+                let patch = diff
+                    .unified()
+                    .context_len(context_len)
+                    .header("a/file", "b/file")
+                    .to_string();
+
+                let mut patched = old.clone();
+                patched.apply_patch(&patch).unwrap();
+                assert_eq!(patched, new);
+            }
+        }
+    }
+}
+
+#[test]
+fn patch_missing_trailing_newline() {
+    let old = Rope::from("foo\nbar");
+    let new = Rope::from("foo\nbaz\n");
+
+    let patch = LineDiff::new(&old, &new).unified().to_string();
+
+    let mut r = old.clone();
+    r.apply_patch(&patch).unwrap();
+    assert_eq!(r, new);
+
+    let patch = LineDiff::new(&new, &old).unified().to_string();
+
+    let mut r = new.clone();
+    r.apply_patch(&patch).unwrap();
+    assert_eq!(r, old);
+}
+
+#[test]
+fn patch_crlf() {
+    let mut r = Rope::from("a\r\nb\r\n");
+    r.apply_patch("@@ -2 +2 @@\n-b\r\n+c\r\n").unwrap();
+    assert_eq!(r, "a\r\nc\r\n");
+
+    // The line breaks of the deleted and context lines have to match.
+    assert_eq!(
+        r.apply_patch("@@ -2 +2 @@\n-c\n+d\n"),
+        Err(PatchError::Mismatch { hunk_idx: 0, old_lines: 1..2 })
+    );
+
+    let pairs = [
+        ("a\r\nb\r\nc\r\n", "a\r\nB\r\nc\r\n"),
+        ("a\nb\nc\n", "a\r\nb\nc\r\n"),
+        ("a\r\nb\r\nc", "a\r\nb\r\nc\r\n"),
+        ("a\r\nb\r", "a\nb\r\n"),
+    ];
+
+    for (old, new) in pairs {
+        let (old, new) = (Rope::from(old), Rope::from(new));
+
+        for (old, new) in [(&old, &new), (&new, &old)] {
+            let patch = LineDiff::new(old, new).unified().to_string();
+
+            let mut r = old.clone();
+            r.apply_patch(&patch).unwrap();
+            assert_eq!(&r, new);
+        }
+    }
+}
+
+#[test]
+fn patch_with_offset() {
+    let old = Rope::from("a\nb\nc\nd\ne\n");
+    let new = Rope::from("a\nb\nC\nd\ne\n");
+
+    let patch = LineDiff::new(&old, &new).unified().context_len(1).to_string();
+    assert_eq!(patch, "@@ -2,3 +2,3 @@\n b\n-c\n+C\n d\n");
+
+    // The same patch still applies after adding or removing lines before the
+    // hunk.
+    let mut r = Rope::from("0\n1\n2\na\nb\nc\nd\ne\n");
+    r.apply_patch(&patch).unwrap();
+    assert_eq!(r, "0\n1\n2\na\nb\nC\nd\ne\n");
+
+    let mut r = Rope::from("b\nc\nd\ne\n");
+    r.apply_patch(&patch).unwrap();
+    assert_eq!(r, "b\nC\nd\ne\n");
+}
+
+#[test]
+fn patch_later_hunks_follow_offset() {
+    let mut r = Rope::from("x\nx\na\nb\nc\nd\ne\nf\ng\nh\n");
+
+    let patch = "@@ -1,2 +1,2 @@\n a\n-b\n+B\n@@ -6,2 +6,2 @@\n f\n-g\n+G\n";
+
+    r.apply_patch(patch).unwrap();
+    assert_eq!(r, "x\nx\na\nB\nc\nd\ne\nf\nG\nh\n");
+}
+
+#[test]
+fn patch_mismatch() {
+    let mut r = Rope::from("a\nb\nc\nd\n");
+
+    let patch = "--- a/file\n\
+                 +++ b/file\n\
+                 @@ -1,2 +1,2 @@\n a\n-b\n+B\n\
+                 @@ -3,2 +3,2 @@\n-c\n+C\n e\n";
+
+    assert_eq!(
+        r.apply_patch(patch),
+        Err(PatchError::Mismatch { hunk_idx: 1, old_lines: 2..4 })
+    );
+
+    // The first hunk isn't applied either.
+    assert_eq!(r, "a\nb\nc\nd\n");
+}
+
+#[test]
+fn patch_malformed() {
+    let mut r = Rope::from("a\nb\n");
+
+    assert_eq!(
+        r.apply_patch("@@ -1 +1\n-a\n+b\n"),
+        Err(PatchError::Malformed { line_idx: 0 })
+    );
+
+    assert_eq!(
+        r.apply_patch("@@ -1,2 +1,2 @@\n a\n*b\n"),
+        Err(PatchError::Malformed { line_idx: 2 })
+    );
+
+    // The patch ends before the end of the hunk.
+    assert_eq!(
+        r.apply_patch("@@ -1,2 +1,2 @@\n a\n"),
+        Err(PatchError::Malformed { line_idx: 2 })
+    );
+
+    assert_eq!(r, "a\nb\n");
+
+    // A patch without hunks doesn't change anything.
+    r.apply_patch("--- a/file\n+++ b/file\n").unwrap();
+    assert_eq!(r, "a\nb\n");
+}
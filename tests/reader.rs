@@ -0,0 +1,132 @@
+mod common;
+
+#[cfg(feature = "std")]
+mod tests {
+    use std::io::{BufRead, Read, Seek, SeekFrom};
+
+    use crop::Rope;
+    use rand::{thread_rng, Rng};
+
+    use crate::common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+    #[test]
+    fn reader_empty() {
+        let r = Rope::new();
+        let mut reader = r.reader();
+
+        assert!(reader.fill_buf().unwrap().is_empty());
+        assert_eq!(reader.read(&mut [0; 8]).unwrap(), 0);
+        assert_eq!(reader.seek(SeekFrom::End(0)).unwrap(), 0);
+    }
+
+    #[test]
+    fn reader_read_to_end() {
+        for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+            let r = Rope::from(s);
+
+            let mut buf = Vec::new();
+            r.reader().read_to_end(&mut buf).unwrap();
+            assert_eq!(buf, s.as_bytes());
+        }
+    }
+
+    #[test]
+    fn reader_fill_buf_yields_chunks() {
+        let r = Rope::from(MEDIUM);
+        let mut reader = r.reader();
+
+        for chunk in r.chunks().filter(|chunk| !chunk.is_empty()) {
+            let buf = reader.fill_buf().unwrap();
+            assert_eq!(buf.as_ptr(), chunk.as_ptr());
+            assert_eq!(buf, chunk.as_bytes());
+            let len = buf.len();
+            reader.consume(len);
+        }
+
+        assert!(reader.fill_buf().unwrap().is_empty());
+        assert_eq!(reader.position(), MEDIUM.len());
+    }
+
+    #[test]
+    fn reader_small_reads() {
+        let mut rng = thread_rng();
+
+        let r = Rope::from(SMALL);
+        let mut reader = r.reader();
+        let mut bytes = Vec::new();
+
+        loop {
+            let mut buf = vec![0; rng.gen_range(1..=16)];
+            let read = reader.read(&mut buf).unwrap();
+            if read == 0 {
+                break;
+            }
+            bytes.extend_from_slice(&buf[..read]);
+        }
+
+        assert_eq!(bytes, SMALL.as_bytes());
+    }
+
+    #[test]
+    fn reader_seek_random() {
+        let mut rng = thread_rng();
+
+        for s in [TINY, SMALL, MEDIUM, CURSED_LIPSUM] {
+            let r = Rope::from(s);
+
+            let mut start = rng.gen_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.gen_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            let bytes = &s.as_bytes()[start..end];
+            let mut reader = slice.reader();
+
+            for _ in 0..20 {
+                // Byte offsets don't have to be on char boundaries.
+                let offset = rng.gen_range(0..=bytes.len());
+
+                let pos = match rng.gen_range(0..3) {
+                    0 => SeekFrom::Start(offset as u64),
+                    1 => SeekFrom::End(offset as i64 - bytes.len() as i64),
+                    _ => SeekFrom::Current(
+                        offset as i64 - reader.position() as i64,
+                    ),
+                };
+
+                assert_eq!(reader.seek(pos).unwrap(), offset as u64);
+                assert_eq!(reader.stream_position().unwrap(), offset as u64);
+
+                let mut buf = vec![0; rng.gen_range(0..=64)];
+                let read = reader.read(&mut buf).unwrap();
+                let expected = &bytes[offset..];
+                assert_eq!(&buf[..read], &expected[..read]);
+                assert_eq!(read, buf.len().min(expected.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn reader_seek_out_of_bounds() {
+        let r = Rope::from("Hello");
+        let mut reader = r.reader();
+
+        assert_eq!(reader.seek(SeekFrom::Start(10)).unwrap(), 10);
+        assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+
+        assert!(reader.seek(SeekFrom::Current(-11)).is_err());
+        assert!(reader.seek(SeekFrom::End(-6)).is_err());
+
+        assert_eq!(reader.seek(SeekFrom::Current(-9)).unwrap(), 1);
+
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "ello");
+    }
+}
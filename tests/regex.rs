@@ -0,0 +1,152 @@
+mod common;
+
+#[cfg(feature = "regex")]
+mod tests {
+    use crop::regex::Regex;
+    use crop::{Rope, RopeSlice};
+    use rand::{thread_rng, Rng};
+    use regex_automata::meta;
+
+    use crate::common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+    /// The maximum length of the slices searched in the random tests, to
+    /// keep patterns matching at every offset from taking forever.
+    const MAX_SLICE_LEN: usize = 16384;
+
+    const PATTERNS: &[&str] = &[
+        r"\w+",
+        r"(?m)^.*$",
+        r"(?m)^\s*$",
+        r"\b\w{3}\b",
+        r"(?-u:\b)\w{3}(?-u:\b)",
+        r"[aeiou]{2,}",
+        r"sit amet,?\s+\w",
+        r"\r?\n",
+        r"a*",
+        r"",
+        r"ṁ|ẗ|\p{Greek}",
+        // Too big for the default cache capacity of the lazy DFA.
+        r"\w{100}",
+    ];
+
+    /// Checks that searching the slice gives the same results as searching
+    /// the string it's equal to.
+    fn check(slice: RopeSlice<'_>, s: &str, pattern: &str) {
+        let regex = Regex::new(pattern).unwrap();
+
+        let expected = meta::Regex::new(pattern).unwrap();
+
+        assert_eq!(
+            slice.find_regex_iter(&regex).collect::<Vec<_>>(),
+            expected.find_iter(s).map(|m| m.range()).collect::<Vec<_>>(),
+            "pattern: {pattern:?}"
+        );
+
+        assert_eq!(
+            slice.find_regex(&regex),
+            expected.find(s).map(|m| m.range()),
+            "pattern: {pattern:?}"
+        );
+    }
+
+    #[test]
+    fn regex_empty() {
+        let r = Rope::new();
+
+        for &pattern in PATTERNS {
+            check(r.byte_slice(..), "", pattern);
+        }
+    }
+
+    #[test]
+    fn regex_cursed_lipsum() {
+        let s = CURSED_LIPSUM.repeat(8);
+        let r = Rope::from(s.as_str());
+
+        for &pattern in PATTERNS {
+            check(r.byte_slice(..), &s, pattern);
+        }
+    }
+
+    #[test]
+    fn regex_random_slices() {
+        let mut rng = thread_rng();
+
+        for s in [TINY, SMALL, MEDIUM, LARGE] {
+            let r = Rope::from(s);
+
+            for _ in 0..5 {
+                let mut start = rng.gen_range(0..=s.len());
+                while !s.is_char_boundary(start) {
+                    start += 1;
+                }
+
+                let max_end = (start + MAX_SLICE_LEN).min(s.len());
+
+                let mut end = rng.gen_range(start..=max_end);
+                while !s.is_char_boundary(end) {
+                    end += 1;
+                }
+
+                for &pattern in PATTERNS {
+                    check(r.byte_slice(start..end), &s[start..end], pattern);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn regex_captures() {
+        let s = "foo=1, bar=, baz=42";
+        let r = Rope::from(s);
+
+        let regex = Regex::new(r"(?<key>b\w+)=(\d+)?").unwrap();
+
+        let captures = r.regex_captures(&regex).unwrap();
+        assert_eq!(captures.group_len(), 3);
+        assert_eq!(captures.get(0), Some(7..11));
+        assert_eq!(captures.name("key"), Some(7..10));
+        assert_eq!(captures.get(2), None);
+        assert_eq!(captures.get(3), None);
+
+        let captures = r.byte_slice(10..).regex_captures(&regex).unwrap();
+        assert_eq!(captures.get(0), Some(3..9));
+        assert_eq!(captures.name("key"), Some(3..6));
+        assert_eq!(captures.get(2), Some(7..9));
+
+        let regex = Regex::new(r"qux").unwrap();
+        assert!(r.regex_captures(&regex).is_none());
+    }
+
+    #[test]
+    fn regex_captures_look_around() {
+        let r = Rope::from("foo.bar\nbaz");
+
+        // The word boundary and the line anchor depend on the chars right
+        // outside the match.
+        let regex = Regex::new(r"(?m)\b(\w+)$").unwrap();
+
+        let captures = r.regex_captures(&regex).unwrap();
+        assert_eq!(captures.get(1), Some(4..7));
+    }
+
+    #[test]
+    fn regex_replace_all() {
+        let mut r = Rope::from(CURSED_LIPSUM.repeat(4));
+
+        let regex = Regex::new(r"\r\n").unwrap();
+        let replaced = r.replace_all_regex(&regex, "\n");
+        assert_eq!(replaced, CURSED_LIPSUM.matches("\r\n").count() * 4);
+        assert_eq!(r, CURSED_LIPSUM.replace("\r\n", "\n").repeat(4));
+
+        let mut r = Rope::from("a1 b22 c333");
+
+        let regex = Regex::new(r"([a-z])(?<digits>\d+)").unwrap();
+        assert_eq!(r.replace_all_regex(&regex, "${digits}$1$$"), 3);
+        assert_eq!(r, "1a$ 22b$ 333c$");
+
+        let regex = Regex::new(r"x").unwrap();
+        assert_eq!(r.replace_all_regex(&regex, "y"), 0);
+        assert_eq!(r, "1a$ 22b$ 333c$");
+    }
+}
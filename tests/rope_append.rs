@@ -0,0 +1,160 @@
+use crop::Rope;
+use rand::Rng;
+
+mod common;
+
+use common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TEXT, TINY};
+
+#[test]
+fn append_empty() {
+    let mut r = Rope::new();
+    r.append(Rope::new());
+    r.assert_invariants();
+    assert_eq!(r, "");
+
+    let mut r = Rope::from(TEXT);
+    r.append(Rope::new());
+    r.assert_invariants();
+    assert_eq!(r, TEXT);
+
+    let mut r = Rope::new();
+    r.append(Rope::from(TEXT));
+    r.assert_invariants();
+    assert_eq!(r, TEXT);
+}
+
+#[test]
+fn append_trailing_newline() {
+    let mut r = Rope::from("foo\n");
+    r.append(Rope::from("bar"));
+    r.assert_invariants();
+    assert_eq!(r.line_len(), 2);
+
+    let mut r = Rope::from("foo");
+    r.append(Rope::from("bar\n"));
+    r.assert_invariants();
+    assert_eq!(r.line_len(), 1);
+
+    let mut r = Rope::from("foo\n");
+    r.append(Rope::new());
+    r.assert_invariants();
+    assert_eq!(r.line_len(), 1);
+}
+
+/// Tests that appending Ropes of all sizes to each other, in both directions,
+/// always results in the concatenation of their contents.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn append_random() {
+    let mut rng = rand::thread_rng();
+
+    let texts = [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM];
+
+    for left in texts {
+        for right in texts {
+            let mut r = Rope::from(left);
+            r.append(Rope::from(right));
+            r.assert_invariants();
+            assert_eq!(r, format!("{left}{right}"));
+        }
+    }
+
+    for s in texts {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut at = rng.gen_range(0..=s.len());
+            while !s.is_char_boundary(at) {
+                at += 1;
+            }
+
+            let mut left = Rope::from(r.byte_slice(..at));
+            left.append(Rope::from(r.byte_slice(at..)));
+            left.assert_invariants();
+            assert_eq!(left, s);
+        }
+    }
+}
+
+#[test]
+fn append_shares_the_appended_rope() {
+    let r = Rope::from(LARGE);
+    let mut doubled = r.clone();
+    doubled.append(r.clone());
+    doubled.assert_invariants();
+    assert_eq!(doubled, format!("{LARGE}{LARGE}"));
+
+    // The original `Rope` is left untouched.
+    r.assert_invariants();
+    assert_eq!(r, LARGE);
+}
+
+#[test]
+fn split_off_0() {
+    let mut r = Rope::from(TEXT);
+    let right = r.split_off(0);
+    r.assert_invariants();
+    right.assert_invariants();
+    assert_eq!(r, "");
+    assert_eq!(right, TEXT);
+
+    let mut r = Rope::from(TEXT);
+    let right = r.split_off(TEXT.len());
+    r.assert_invariants();
+    right.assert_invariants();
+    assert_eq!(r, TEXT);
+    assert_eq!(right, "");
+}
+
+#[should_panic]
+#[test]
+fn split_off_1() {
+    let mut r = Rope::from(TEXT);
+    let _ = r.split_off(TEXT.len() + 1);
+}
+
+#[should_panic]
+#[test]
+fn split_off_2() {
+    let mut r = Rope::from("🐸");
+    let _ = r.split_off(2);
+}
+
+#[test]
+fn split_at_trailing_newline() {
+    let (left, right) = Rope::from("foo\nbar\n").split_at(4);
+    left.assert_invariants();
+    right.assert_invariants();
+    assert_eq!(left.line_len(), 1);
+    assert_eq!(right.line_len(), 1);
+}
+
+/// Tests that splitting a Rope at a random byte offset always matches
+/// splitting the equivalent string.
+#[cfg_attr(miri, ignore)]
+#[test]
+fn split_off_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+        let r = Rope::from(s);
+
+        for _ in 0..20 {
+            let mut at = rng.gen_range(0..=s.len());
+            while !s.is_char_boundary(at) {
+                at += 1;
+            }
+
+            let (left, right) = r.clone().split_at(at);
+            left.assert_invariants();
+            right.assert_invariants();
+            assert_eq!(left, s[..at]);
+            assert_eq!(right, s[at..]);
+
+            let mut joined = left;
+            joined.append(right);
+            joined.assert_invariants();
+            assert_eq!(joined, s);
+        }
+    }
+}
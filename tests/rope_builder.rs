@@ -1,7 +1,8 @@
 mod common;
 
-use common::LARGE;
+use common::{CURSED_LIPSUM, LARGE};
 use crop::{Rope, RopeBuilder};
+use rand::{thread_rng, Rng};
 
 #[test]
 fn builder_line_len() {
@@ -59,3 +60,166 @@ fn builder_crlf_1() {
     r.assert_invariants();
     assert_eq!(r, "aaa\r\nbbb");
 }
+
+/// Returns the bytes of `CURSED_LIPSUM` with a few of them replaced by
+/// random ones, which will likely make it invalid UTF-8.
+fn corrupted_lipsum() -> Vec<u8> {
+    let mut rng = thread_rng();
+
+    let mut bytes = CURSED_LIPSUM.as_bytes().to_vec();
+
+    for _ in 0..rng.gen_range(0..4) {
+        let idx = rng.gen_range(0..bytes.len());
+        bytes[idx] = rng.gen();
+    }
+
+    bytes
+}
+
+/// Splits the bytes at random offsets.
+fn random_pieces(bytes: &[u8]) -> Vec<&[u8]> {
+    let mut rng = thread_rng();
+
+    let mut pieces = Vec::new();
+
+    let mut start = 0;
+
+    while start < bytes.len() {
+        let end = rng.gen_range(start..=bytes.len().min(start + 8));
+        pieces.push(&bytes[start..end]);
+        start = end;
+    }
+
+    pieces
+}
+
+#[test]
+fn builder_append_bytes_split_chars() {
+    for _ in 0..20 {
+        let mut b = RopeBuilder::new();
+
+        for piece in random_pieces(CURSED_LIPSUM.as_bytes()) {
+            b.append_bytes(piece).unwrap();
+        }
+
+        let r = b.try_build().unwrap();
+        r.assert_invariants();
+        assert_eq!(r, CURSED_LIPSUM);
+    }
+}
+
+#[test]
+fn builder_append_bytes_invalid() {
+    for _ in 0..100 {
+        let bytes = corrupted_lipsum();
+
+        let mut b = RopeBuilder::new();
+
+        let mut result = Ok(());
+
+        for piece in random_pieces(&bytes) {
+            if let Err(err) = b.append_bytes(piece) {
+                result = Err(err);
+                break;
+            }
+        }
+
+        let result = result.and_then(|()| b.try_build().map(|_| ()));
+
+        match (result, core::str::from_utf8(&bytes)) {
+            (Ok(()), Ok(_)) => {},
+            (Err(err), Err(expected)) => {
+                assert_eq!(err.byte_offset(), expected.valid_up_to());
+                assert_eq!(err.error_len(), expected.error_len());
+            },
+            (result, expected) => panic!("{result:?} != {expected:?}"),
+        }
+    }
+}
+
+#[test]
+fn builder_append_bytes_lossy() {
+    for _ in 0..100 {
+        let bytes = corrupted_lipsum();
+
+        let mut b = RopeBuilder::new();
+
+        for piece in random_pieces(&bytes) {
+            b.append_bytes_lossy(piece);
+        }
+
+        let r = b.build();
+        r.assert_invariants();
+        assert_eq!(r, String::from_utf8_lossy(&bytes));
+    }
+}
+
+#[test]
+fn builder_append_after_incomplete_bytes() {
+    let mut b = RopeBuilder::new();
+    b.append_bytes(b"foo\xE2\x82").unwrap();
+    b.append("bar");
+    assert_eq!(b.build(), "foo\u{FFFD}bar");
+}
+
+#[test]
+fn builder_append_bytes_after_error() {
+    let mut b = RopeBuilder::new();
+
+    let err = b.append_bytes(b"foo\xFFbar").err().unwrap();
+    assert_eq!(err.byte_offset(), 3);
+
+    // The bytes dropped after the first error are still counted.
+    let err = b.append_bytes(b"baz\xE2\x82qux").err().unwrap();
+    assert_eq!(err.byte_offset(), 10);
+
+    // The invalid sequence starts with the bytes held back by the previous
+    // call.
+    b.append_bytes(b"\xE2\x82").unwrap();
+    let err = b.append_bytes(b"!\xFF").err().unwrap();
+    assert_eq!(err.byte_offset(), 15);
+    assert_eq!(err.error_len(), Some(2));
+
+    let err = b.append_bytes(b"\xFF").err().unwrap();
+    assert_eq!(err.byte_offset(), 19);
+
+    assert_eq!(b.build(), "foobaz");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn rope_from_reader() {
+    /// A reader returning at most `n` bytes at a time.
+    struct Trickle<'a>(&'a [u8], usize);
+
+    impl std::io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.0.len().min(buf.len()).min(self.1);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    let r = Rope::from_reader(Trickle(LARGE.as_bytes(), 7)).unwrap();
+    r.assert_invariants();
+    assert_eq!(r, LARGE);
+
+    for _ in 0..20 {
+        let bytes = corrupted_lipsum();
+
+        let lossy = Rope::from_reader_lossy(Trickle(&bytes, 3)).unwrap();
+        assert_eq!(lossy, String::from_utf8_lossy(&bytes));
+
+        let strict = Rope::from_reader(Trickle(&bytes, 3));
+
+        match (strict, core::str::from_utf8(&bytes)) {
+            (Ok(r), Ok(s)) => assert_eq!(r, s),
+            (Err(crop::FromReaderError::Utf8(err)), Err(expected)) => {
+                assert_eq!(err.byte_offset(), expected.valid_up_to());
+                assert_eq!(err.error_len(), expected.error_len());
+            },
+            (result, expected) => panic!("{result:?} != {expected:?}"),
+        }
+    }
+}
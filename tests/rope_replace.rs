@@ -352,6 +352,90 @@ fn replace_random() {
     }
 }
 
+#[test]
+fn replace_with_slice_0() {
+    let other = Rope::from(TEXT);
+
+    let mut r = Rope::from("aaaabbbbcccc");
+    r.replace_with_slice(4..8, other.byte_slice(..5));
+    r.assert_invariants();
+    assert_eq!("aaaaHellocccc", r);
+
+    r.replace_with_slice(.., other.byte_slice(..));
+    r.assert_invariants();
+    assert_eq!(TEXT, r);
+
+    r.replace_with_slice(.., other.byte_slice(..0));
+    r.assert_invariants();
+    assert_eq!("", r);
+
+    r.insert_slice(0, other.byte_slice(127 - 36..));
+    r.assert_invariants();
+    assert_eq!("こんにちは、みんなさん！", r);
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn insert_slice_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let mut r = Rope::from(s);
+        let mut s = s.to_owned();
+
+        for _ in 0..10 {
+            let insert_at = rng.gen_range(0..=r.byte_len());
+
+            let other = r.clone();
+
+            let insert = {
+                let start = rng.gen_range(0..=r.byte_len());
+                let end = rng.gen_range(start..=r.byte_len());
+                start..end
+            };
+
+            r.insert_slice(insert_at, other.byte_slice(insert.clone()));
+            s.insert_str(insert_at, &other.byte_slice(insert).to_string());
+
+            r.assert_invariants();
+            assert_eq!(s, r);
+        }
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[test]
+fn replace_with_slice_random() {
+    let mut rng = rand::thread_rng();
+
+    for s in [TINY, SMALL, MEDIUM, LARGE] {
+        let mut r = Rope::from(s);
+        let mut s = s.to_owned();
+
+        for _ in 0..20 {
+            let replace_range = {
+                let start = rng.gen_range(0..=r.byte_len());
+                let end = rng.gen_range(start..=r.byte_len());
+                start..end
+            };
+
+            let other = r.clone();
+
+            let replace_with = {
+                let start = rng.gen_range(0..=r.byte_len());
+                let end = rng.gen_range(start..=r.byte_len());
+                other.byte_slice(start..end)
+            };
+
+            r.replace_with_slice(replace_range.clone(), replace_with);
+            s.replace_range(replace_range, &replace_with.to_string());
+
+            r.assert_invariants();
+            assert_eq!(s, r);
+        }
+    }
+}
+
 /// ```
 /// Root
 /// ├── "aaa\r"
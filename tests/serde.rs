@@ -0,0 +1,109 @@
+mod common;
+
+#[cfg(feature = "serde")]
+mod tests {
+    use crop::{Rope, RopeBuilder};
+    use rand::{thread_rng, Rng};
+
+    use crate::common::{CURSED_LIPSUM, LARGE, MEDIUM};
+
+    #[test]
+    fn serde_serialize_chunks() {
+        let r = Rope::from(MEDIUM);
+
+        let json = serde_json::to_value(&r).unwrap();
+
+        let chunks = r.chunks().collect::<Vec<_>>();
+
+        assert_eq!(json, serde_json::to_value(chunks).unwrap());
+    }
+
+    #[test]
+    fn serde_empty() {
+        let r = Rope::new();
+
+        let json = serde_json::to_string(&r).unwrap();
+        assert_eq!(json, "[]");
+
+        let r: Rope = serde_json::from_str(&json).unwrap();
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        for s in [CURSED_LIPSUM, MEDIUM, LARGE] {
+            let r = Rope::from(s);
+
+            let json = serde_json::to_string(&r).unwrap();
+
+            let de: Rope = serde_json::from_str(&json).unwrap();
+            de.assert_invariants();
+            assert_eq!(de, r);
+            assert_eq!(de.line_len(), r.line_len());
+        }
+    }
+
+    #[test]
+    fn serde_round_trip_slices() {
+        let mut rng = thread_rng();
+
+        let s = CURSED_LIPSUM.repeat(16);
+        let r = Rope::from(s.as_str());
+
+        for _ in 0..50 {
+            let mut start = rng.gen_range(0..=s.len());
+            while !s.is_char_boundary(start) {
+                start += 1;
+            }
+
+            let mut end = rng.gen_range(start..=s.len());
+            while !s.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+
+            let json = serde_json::to_string(&slice).unwrap();
+
+            let de: Rope = serde_json::from_str(&json).unwrap();
+            de.assert_invariants();
+            assert_eq!(de, &s[start..end]);
+            assert_eq!(de.line_len(), slice.line_len());
+        }
+    }
+
+    #[test]
+    fn serde_crlf_split_across_chunks() {
+        let de: Rope =
+            serde_json::from_str(r#"["aaa\r", "\nbbb\r", "\n", "\r"]"#)
+                .unwrap();
+
+        de.assert_invariants();
+        assert_eq!(de, "aaa\r\nbbb\r\n\r");
+        assert_eq!(de.line_len(), 3);
+        assert_eq!(de.line(0), "aaa");
+        assert_eq!(de.line(1), "bbb");
+
+        // Build a rope whose chunks split some of its CRLFs and check that
+        // they survive a round trip.
+        let mut builder = RopeBuilder::new();
+        for _ in 0..1000 {
+            builder.append("foo\r").append("\nbar\r\n");
+        }
+        let r = builder.build();
+
+        let json = serde_json::to_string(&r).unwrap();
+
+        let de: Rope = serde_json::from_str(&json).unwrap();
+        de.assert_invariants();
+        assert_eq!(de, r);
+        assert_eq!(de.line_len(), r.line_len());
+        assert_eq!(de.line(999), "bar");
+    }
+
+    #[test]
+    fn serde_invalid() {
+        assert!(serde_json::from_str::<Rope>(r#""foo""#).is_err());
+        assert!(serde_json::from_str::<Rope>(r#"["foo", 42]"#).is_err());
+    }
+}
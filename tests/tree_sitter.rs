@@ -0,0 +1,183 @@
+mod common;
+
+#[cfg(feature = "tree-sitter")]
+mod tests {
+    use std::ops::Range;
+
+    use crop::tree_sitter::{chunk_callback, input_edit};
+    use crop::Rope;
+    use rand::{thread_rng, Rng};
+    use tree_sitter::{Parser, Point, Tree};
+
+    use crate::common::{CURSED_LIPSUM, LARGE, MEDIUM, SMALL, TINY};
+
+    const RUST: &str = "fn main() {
+    let crab = \"🦀\";
+    println!(\"Hello, {crab}!\");
+}
+
+struct Foo {
+    bar: usize,
+}
+";
+
+    /// Reads the whole text by calling the callback at the end of the
+    /// previous chunk, like tree-sitter does.
+    #[test]
+    fn chunk_callback_sequential() {
+        for s in [TINY, SMALL, MEDIUM, LARGE, CURSED_LIPSUM] {
+            let r = Rope::from(s);
+
+            let mut read = chunk_callback(&r);
+            let mut offset = 0;
+            let mut bytes = Vec::new();
+
+            loop {
+                let chunk = read(offset, Point::default());
+                if chunk.is_empty() {
+                    break;
+                }
+                bytes.extend_from_slice(chunk);
+                offset += chunk.len();
+            }
+
+            assert_eq!(bytes, s.as_bytes());
+        }
+    }
+
+    #[test]
+    fn chunk_callback_random() {
+        let mut rng = thread_rng();
+
+        for s in [MEDIUM, LARGE, CURSED_LIPSUM] {
+            let r = Rope::from(s);
+
+            let mut start = rng.gen_range(0..=r.byte_len() / 2);
+            while !r.is_char_boundary(start) {
+                start -= 1;
+            }
+
+            let mut end = rng.gen_range(start..=r.byte_len());
+            while !r.is_char_boundary(end) {
+                end += 1;
+            }
+
+            let slice = r.byte_slice(start..end);
+            let s = &s[start..end];
+
+            let mut read = chunk_callback(slice);
+
+            for _ in 0..100 {
+                // Also read at offsets which are not on a code point
+                // boundary.
+                let offset = rng.gen_range(0..=s.len());
+
+                let chunk = read(offset, Point::default());
+
+                assert!(s.as_bytes()[offset..].starts_with(chunk));
+                assert_eq!(chunk.is_empty(), offset == s.len());
+            }
+        }
+    }
+
+    #[test]
+    fn input_edit_points() {
+        let r = Rope::from("foo\r\nbär\nbaz");
+
+        let edit = input_edit(&r, 8..10, "\na\n\nbc");
+        assert_eq!(edit.start_byte, 8);
+        assert_eq!(edit.old_end_byte, 10);
+        assert_eq!(edit.new_end_byte, 14);
+        assert_eq!(edit.start_position, Point::new(1, 3));
+        assert_eq!(edit.old_end_position, Point::new(2, 0));
+        assert_eq!(edit.new_end_position, Point::new(4, 2));
+
+        let edit = input_edit(&r, r.byte_len().., "qux");
+        assert_eq!(edit.start_position, Point::new(2, 3));
+        assert_eq!(edit.old_end_position, Point::new(2, 3));
+        assert_eq!(edit.new_end_position, Point::new(2, 6));
+
+        let edit = input_edit(&r, .., "");
+        assert_eq!(edit.start_position, Point::new(0, 0));
+        assert_eq!(edit.old_end_position, Point::new(2, 3));
+        assert_eq!(edit.new_end_position, Point::new(0, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn input_edit_out_of_bounds() {
+        let r = Rope::from("foo");
+        let _ = input_edit(&r, 2..4, "");
+    }
+
+    /// Checks that reparsing a `Rope` after editing the old tree with the
+    /// `InputEdit`s returns the same tree as parsing it from scratch.
+    #[test]
+    fn incremental_parse() {
+        let mut rng = thread_rng();
+
+        let mut parser = Parser::new();
+        parser.set_language(tree_sitter_rust::language()).unwrap();
+
+        let mut r = Rope::from(RUST.repeat(50));
+
+        let mut tree = parse(&mut parser, &r, None);
+
+        let edits: [(&str, &str); 4] = [
+            ("fn main() {\n", "fn main() {\n    let x = 1;\n\n"),
+            ("\"🦀\"", "'🦀'"),
+            ("    bar: usize,\n", ""),
+            ("crab", "cr\nab"),
+        ];
+
+        for _ in 0..50 {
+            let (pattern, replacement) =
+                edits[rng.gen_range(0..edits.len())];
+
+            let matches = r.find_iter(pattern).collect::<Vec<_>>();
+
+            if matches.is_empty() {
+                continue;
+            }
+
+            let start = matches[rng.gen_range(0..matches.len())];
+            let end = start + pattern.len();
+
+            let edit = input_edit(&r, start..end, replacement);
+            r.replace(start..end, replacement);
+            tree.edit(&edit);
+
+            let incremental = parse(&mut parser, &r, Some(&tree));
+            let from_scratch = parse(&mut parser, &r, None);
+
+            assert_eq!(nodes(&incremental), nodes(&from_scratch));
+
+            tree = incremental;
+        }
+    }
+
+    fn parse(
+        parser: &mut Parser,
+        r: &Rope,
+        old_tree: Option<&Tree>,
+    ) -> Tree {
+        parser.parse_with(&mut chunk_callback(r), old_tree).unwrap()
+    }
+
+    fn nodes(tree: &Tree) -> Vec<(&'static str, Range<usize>, Range<Point>)> {
+        let mut nodes = Vec::new();
+        let mut stack = vec![tree.root_node()];
+
+        while let Some(node) = stack.pop() {
+            nodes.push((
+                node.kind(),
+                node.start_byte()..node.end_byte(),
+                node.start_position()..node.end_position(),
+            ));
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+
+        nodes
+    }
+}
@@ -0,0 +1,176 @@
+use crop::changes::{xform, ChangeSet};
+use crop::Rope;
+use rand::{thread_rng, Rng};
+
+mod common;
+
+use common::{CURSED_LIPSUM, MEDIUM, SMALL, TINY};
+
+/// Returns a random `ChangeSet` over `s`, editing it in `max_edits` places at
+/// most.
+fn random_changes(s: &str, max_edits: usize) -> ChangeSet {
+    let mut rng = thread_rng();
+
+    let mut offsets = (0..rng.gen_range(0..=2 * max_edits))
+        .map(|_| {
+            let mut offset = rng.gen_range(0..=s.len());
+            while !s.is_char_boundary(offset) {
+                offset += 1;
+            }
+            offset
+        })
+        .collect::<Vec<_>>();
+
+    offsets.sort_unstable();
+
+    let replacements = offsets.chunks_exact(2).map(|range| {
+        let text = match rng.gen_range(0..4) {
+            0 => "",
+            1 => "\n",
+            2 => "Ḽơᶉëᶆ",
+            _ => "foo bar",
+        };
+        (range[0]..range[1], text)
+    });
+
+    ChangeSet::from_replacements(s.len(), replacements.collect::<Vec<_>>())
+}
+
+fn apply(rope: &Rope, changes: &[&ChangeSet]) -> Rope {
+    let mut rope = rope.clone();
+    for changes in changes {
+        changes.apply(&mut rope);
+    }
+    rope
+}
+
+fn texts() -> [String; 4] {
+    [
+        TINY.to_owned(),
+        SMALL.to_owned(),
+        MEDIUM[..MEDIUM.len() / 4].to_owned(),
+        CURSED_LIPSUM.repeat(4),
+    ]
+}
+
+#[test]
+fn xform_converges() {
+    for s in texts() {
+        let r = Rope::from(s.as_str());
+
+        for max_edits in [1, 4, 32] {
+            for _ in 0..50 {
+                let a = random_changes(&s, max_edits);
+                let b = random_changes(&s, max_edits);
+
+                let (a_prime, b_prime) = xform(&a, &b);
+
+                assert_eq!(a_prime.byte_len(), b.byte_len_after());
+                assert_eq!(b_prime.byte_len(), a.byte_len_after());
+                assert_eq!(a_prime.byte_len_after(), b_prime.byte_len_after());
+
+                let ab = apply(&r, &[&a, &b_prime]);
+                let ba = apply(&r, &[&b, &a_prime]);
+                assert_eq!(ab, ba);
+
+                // Composing either path gives the same result too.
+                let composed = a.clone().compose(b_prime);
+                assert_eq!(apply(&r, &[&composed]), ab);
+            }
+        }
+    }
+}
+
+#[test]
+fn xform_against_sequences() {
+    for s in texts() {
+        let r = Rope::from(s.as_str());
+
+        for _ in 0..50 {
+            let a = random_changes(&s, 4);
+            let b = random_changes(&s, 4);
+
+            let after_b = apply(&r, &[&b]).to_string();
+            let c = random_changes(&after_b, 4);
+
+            // A client that made `a` receives `b` and `c` at once, while the
+            // server that applied them receives `a`.
+            let bc = b.clone().compose(c.clone());
+            let (a_prime, bc_prime) = xform(&a, &bc);
+
+            assert_eq!(
+                apply(&r, &[&a, &bc_prime]),
+                apply(&r, &[&bc, &a_prime])
+            );
+
+            // Applying `b` and `c` one by one converges too.
+            let (a_b, b_prime) = xform(&a, &b);
+            let (a_bc, c_prime) = xform(&a_b, &c);
+
+            assert_eq!(
+                apply(&r, &[&a, &b_prime, &c_prime]),
+                apply(&r, &[&b, &c, &a_bc])
+            );
+        }
+    }
+}
+
+#[test]
+fn xform_with_no_op() {
+    for s in texts() {
+        let no_op =
+            ChangeSet::from_replacements(s.len(), Vec::<(_, &str)>::new());
+
+        for _ in 0..20 {
+            let a = random_changes(&s, 8);
+
+            let (a_prime, no_op_prime) = xform(&a, &no_op);
+            assert_eq!(a_prime, a);
+            assert!(no_op_prime.is_empty());
+
+            let (no_op_prime, a_prime) = xform(&no_op, &a);
+            assert_eq!(a_prime, a);
+            assert!(no_op_prime.is_empty());
+        }
+    }
+}
+
+#[test]
+fn xform_insertions_at_same_position() {
+    let r = Rope::from("ab");
+
+    let a = ChangeSet::from_replacements(2, [(1..1, "foo")]);
+    let b = ChangeSet::from_replacements(2, [(1..1, "bar")]);
+
+    // The insertion of the first `ChangeSet` always comes first, no matter
+    // which client applies which.
+    let (a_prime, b_prime) = xform(&a, &b);
+    assert_eq!(apply(&r, &[&a, &b_prime]), "afoobarb");
+    assert_eq!(apply(&r, &[&b, &a_prime]), "afoobarb");
+
+    let (b_prime, a_prime) = xform(&b, &a);
+    assert_eq!(apply(&r, &[&b, &a_prime]), "abarfoob");
+    assert_eq!(apply(&r, &[&a, &b_prime]), "abarfoob");
+}
+
+#[test]
+fn xform_overlapping_deletions() {
+    let r = Rope::from("Hello World");
+
+    let a = ChangeSet::from_replacements(11, [(3..8, "")]);
+    let b = ChangeSet::from_replacements(11, [(5..11, "!")]);
+
+    let (a_prime, b_prime) = xform(&a, &b);
+
+    let expected = "Hel!";
+    assert_eq!(apply(&r, &[&a, &b_prime]), expected);
+    assert_eq!(apply(&r, &[&b, &a_prime]), expected);
+}
+
+#[test]
+#[should_panic]
+fn xform_different_lengths() {
+    let a = ChangeSet::from_replacements(3, [(0..1, "")]);
+    let b = ChangeSet::from_replacements(4, [(0..1, "")]);
+    let _ = xform(&a, &b);
+}